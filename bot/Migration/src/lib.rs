@@ -13,6 +13,11 @@ mod m20240815_231538_ping_history;
 mod m20240826_215627_server_user_relation;
 mod m20240831_133253_user_subscription;
 mod m20240831_134027_guild_subscription;
+mod m20261017_100000_command_usage;
+mod m20261018_100000_activity_media_kind;
+mod m20261019_100000_activity_sent_episode;
+mod m20261020_100000_followed_user;
+mod m20261021_100000_activity_last_chapter;
 
 pub struct Migrator;
 
@@ -33,6 +38,34 @@ impl MigratorTrait for Migrator {
 			Box::new(m20240826_215627_server_user_relation::Migration),
 			Box::new(m20240831_133253_user_subscription::Migration),
 			Box::new(m20240831_134027_guild_subscription::Migration),
+			Box::new(m20261017_100000_command_usage::Migration),
+			Box::new(m20261018_100000_activity_media_kind::Migration),
+			Box::new(m20261019_100000_activity_sent_episode::Migration),
+			Box::new(m20261020_100000_followed_user::Migration),
+			Box::new(m20261021_100000_activity_last_chapter::Migration),
 		]
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sea_orm_migration::sea_orm::Database;
+
+	#[tokio::test]
+	async fn test_migration_sqlite() {
+		let connection = Database::connect("sqlite::memory:").await.unwrap();
+
+		Migrator::up(&connection, None).await.unwrap();
+
+		let pending = Migrator::get_pending_migrations(&connection).await.unwrap();
+
+		assert!(pending.is_empty());
+
+		Migrator::down(&connection, None).await.unwrap();
+
+		let applied = Migrator::get_applied_migrations(&connection).await.unwrap();
+
+		assert!(applied.is_empty())
+	}
+}
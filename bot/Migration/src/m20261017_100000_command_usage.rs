@@ -0,0 +1,61 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(CommandUsage::Table)
+					.if_not_exists()
+					.col(big_integer(CommandUsage::Id).auto_increment().primary_key())
+					.col(string(CommandUsage::CommandName))
+					.col(string(CommandUsage::UserId))
+					.col(timestamp(CommandUsage::UsedAt))
+					.to_owned(),
+			)
+			.await?;
+
+		// Used by the per user hourly limit and the /user command_usage view.
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_command_usage_user_command_used_at")
+					.table(CommandUsage::Table)
+					.col(CommandUsage::UserId)
+					.col(CommandUsage::CommandName)
+					.col(CommandUsage::UsedAt)
+					.to_owned(),
+			)
+			.await?;
+
+		// Used by the aggregation queries and the retention pruning.
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_command_usage_used_at")
+					.table(CommandUsage::Table)
+					.col(CommandUsage::UsedAt)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(CommandUsage::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum CommandUsage {
+	Table,
+	Id,
+	CommandName,
+	UserId,
+	UsedAt,
+}
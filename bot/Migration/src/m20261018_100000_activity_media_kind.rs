@@ -0,0 +1,56 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// The existing activities are anime episodes.
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.add_column(string(ActivityData::MediaKind).default("anime"))
+					.to_owned(),
+			)
+			.await?;
+
+		// Id of the series on the chapter release feed, only set for manga.
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.add_column(string_null(ActivityData::ExternalId))
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.drop_column(ActivityData::ExternalId)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.drop_column(ActivityData::MediaKind)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum ActivityData {
+	Table,
+	MediaKind,
+	ExternalId,
+}
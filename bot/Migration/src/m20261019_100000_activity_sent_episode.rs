@@ -0,0 +1,37 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// Last episode sent by the activity, or skipped when it aired while the bot was stopped. An
+		// episode is not sent twice when its next episode could not be found right after the send.
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.add_column(integer_null(ActivityData::SentEpisode))
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.drop_column(ActivityData::SentEpisode)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum ActivityData {
+	Table,
+	SentEpisode,
+}
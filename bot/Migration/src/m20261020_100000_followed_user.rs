@@ -0,0 +1,54 @@
+use crate::m20240815_180000_guild_data::GuildData;
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(FollowedUser::Table)
+					.if_not_exists()
+					.col(string(FollowedUser::GuildId))
+					.col(integer(FollowedUser::AnilistId))
+					.primary_key(
+						Index::create()
+							.col(FollowedUser::GuildId)
+							.col(FollowedUser::AnilistId),
+					)
+					.col(string(FollowedUser::Webhook))
+					// Id of the last activity sent, the activities with a greater id are new.
+					.col(integer(FollowedUser::LastActivityId))
+					.col(timestamp(FollowedUser::CreatedAt).default(Expr::current_timestamp()))
+					.foreign_key(
+						ForeignKey::create()
+							.name("FK_server_followed_user")
+							.to(GuildData::Table, GuildData::GuildId)
+							.from(FollowedUser::Table, FollowedUser::GuildId)
+							.on_delete(ForeignKeyAction::Cascade)
+							.on_update(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(FollowedUser::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+pub enum FollowedUser {
+	Table,
+	GuildId,
+	AnilistId,
+	Webhook,
+	LastActivityId,
+	CreatedAt,
+}
@@ -0,0 +1,37 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// Last chapter sent by a manga activity as written by the feed, `12.5` can't be stored in
+		// the episode column. The activities without it fall back to their episode.
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.add_column(string_null(ActivityData::LastChapter))
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ActivityData::Table)
+					.drop_column(ActivityData::LastChapter)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum ActivityData {
+	Table,
+	LastChapter,
+}
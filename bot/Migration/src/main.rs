@@ -5,6 +5,18 @@ use std::env;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+
+	// sqlite create the database file on connection (mode=rwc) so only postgres need it to be created.
+	if database_url.starts_with("postgres") {
+		create_postgres_database(&database_url).await?;
+	}
+
+	cli::run_cli(migration::Migrator).await;
+
+	Ok(())
+}
+
+async fn create_postgres_database(database_url: &str) -> Result<(), Box<dyn std::error::Error>> {
 	let db_name = database_url
 		.split("/")
 		.last()
@@ -12,7 +24,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 		.split("?")
 		.collect::<Vec<&str>>()[0];
 	println!("db_name: {}", db_name);
-	match PgPool::connect(&database_url).await {
+	match PgPool::connect(database_url).await {
 		Ok(_) => (),
 		Err(_) => {
 			let database_url = database_url.replace(db_name, "");
@@ -22,7 +34,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 				.await?;
 		},
 	};
-	cli::run_cli(migration::Migrator).await;
 
 	Ok(())
 }
@@ -1,9 +1,25 @@
+use std::env;
 use std::error::Error;
+use std::path::PathBuf;
 
 fn main() -> Result<(), Box<dyn Error>> {
 	cynic_codegen::register_schema("anilist")
 		.from_sdl_file("schemas/anilist.graphql")?
 		.as_default()?;
 
+	let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+
+	tonic_build::configure()
+		.file_descriptor_set_path(out_dir.join("kasuki_descriptor.bin"))
+		.compile_protos(
+			&[
+				"proto/info.proto",
+				"proto/shard.proto",
+				"proto/command.proto",
+				"proto/guild.proto",
+			],
+			&["proto"],
+		)?;
+
 	Ok(())
 }
@@ -22,7 +22,7 @@ pub async fn autocomplete(ctx: Context, autocomplete_interaction: CommandInterac
 		&ctx,
 		autocomplete_interaction,
 		var,
-		bot_data.anilist_cache.clone(),
+		bot_data.anilist_client.clone(),
 	)
 	.await;
 }
@@ -0,0 +1,33 @@
+use serenity::all::{CommandInteraction, Context};
+use tracing::trace;
+
+use crate::constant::DEFAULT_STRING;
+use crate::event_handler::BotData;
+use crate::helper::get_option::subcommand_group::get_option_map_string_autocomplete_subcommand_group;
+use crate::structure::autocomplete::anilist::media::{
+	send_auto_complete, MediaAutocompleteVariables, MediaFormat, MediaType,
+};
+
+pub async fn autocomplete(ctx: Context, autocomplete_interaction: CommandInteraction) {
+	let map = get_option_map_string_autocomplete_subcommand_group(&autocomplete_interaction);
+	let bot_data = ctx.data::<BotData>().clone();
+	trace!("{:?}", map);
+
+	let manga_search = map
+		.get(&String::from("manga_name"))
+		.unwrap_or(DEFAULT_STRING);
+
+	let var = MediaAutocompleteVariables {
+		search: Some(manga_search.as_str()),
+		in_media_format: Some(vec![Some(MediaFormat::Manga), Some(MediaFormat::OneShot)]),
+		media_type: Some(MediaType::Manga),
+	};
+
+	send_auto_complete(
+		&ctx,
+		autocomplete_interaction,
+		var,
+		bot_data.anilist_client.clone(),
+	)
+	.await;
+}
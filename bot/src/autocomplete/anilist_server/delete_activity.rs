@@ -2,7 +2,6 @@ use crate::constant::{AUTOCOMPLETE_COUNT_LIMIT, DEFAULT_STRING};
 use crate::database::activity_data::Column;
 use crate::database::prelude::ActivityData;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::get_option::subcommand_group::get_option_map_string_autocomplete_subcommand_group;
 use sea_orm::ColumnTrait;
 use sea_orm::EntityTrait;
@@ -11,7 +10,6 @@ use serenity::all::{
 	AutocompleteChoice, CommandInteraction, Context as SerenityContext, CreateAutocompleteResponse,
 	CreateInteractionResponse,
 };
-use tracing::error;
 
 pub async fn autocomplete(ctx: SerenityContext, autocomplete_interaction: CommandInteraction) {
 	let map = get_option_map_string_autocomplete_subcommand_group(&autocomplete_interaction);
@@ -26,18 +24,9 @@ pub async fn autocomplete(ctx: SerenityContext, autocomplete_interaction: Comman
 		None => String::from("0"),
 	};
 
-	let connection = match sea_orm::Database::connect(get_url(bot_data.config.db.clone())).await {
-		Ok(conn) => conn,
-		Err(e) => {
-			error!(?e);
-
-			return;
-		},
-	};
-
 	let activities = match ActivityData::find()
 		.filter(Column::ServerId.eq(&guild_id))
-		.all(&connection)
+		.all(&*bot_data.db_connection)
 		.await
 	{
 		Ok(data) => data,
@@ -1,2 +1,3 @@
 pub mod add_anime_activity;
+pub mod add_manga_activity;
 pub mod delete_activity;
@@ -29,7 +29,7 @@ pub async fn autocomplete(ctx: Context, autocomplete_interaction: CommandInterac
 	let operation = CharacterAutocomplete::build(var);
 
 	let data: GraphQlResponse<CharacterAutocomplete> =
-		match make_request_anilist(operation, false, bot_data.anilist_cache.clone()).await {
+		match make_request_anilist(operation, false, bot_data.anilist_client.clone()).await {
 			Ok(data) => data,
 			Err(e) => {
 				tracing::debug!(?e);
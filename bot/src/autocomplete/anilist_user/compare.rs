@@ -1,18 +1,16 @@
 use std::sync::Arc;
 
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use serenity::all::{
 	AutocompleteChoice, CommandInteraction, Context as SerenityContext, CreateAutocompleteResponse,
 	CreateInteractionResponse,
 };
-use tokio::sync::RwLock;
 use tracing::log::trace;
 
 use crate::constant::DEFAULT_STRING;
 use crate::event_handler::BotData;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::autocomplete::anilist::user::{UserAutocomplete, UserAutocompleteVariables};
 use anyhow::Result;
 use small_fixed_array::FixedString;
@@ -29,13 +27,13 @@ pub async fn autocomplete(ctx: SerenityContext, autocomplete_interaction: Comman
 		.get(&FixedString::from_str_trunc("username"))
 		.unwrap_or(DEFAULT_STRING);
 
-	choice.extend(get_choices(user1, bot_data.anilist_cache.clone()).await);
+	choice.extend(get_choices(user1, bot_data.anilist_client.clone()).await);
 
 	let user2 = map
 		.get(&FixedString::from_str_trunc("username2"))
 		.unwrap_or(DEFAULT_STRING);
 
-	choice.extend(get_choices(user2, bot_data.anilist_cache.clone()).await);
+	choice.extend(get_choices(user2, bot_data.anilist_client.clone()).await);
 
 	let data = CreateAutocompleteResponse::new().set_choices(choice);
 
@@ -46,9 +44,7 @@ pub async fn autocomplete(ctx: SerenityContext, autocomplete_interaction: Comman
 		.await;
 }
 
-async fn get_choices(
-	search: &str, anilist_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Vec<AutocompleteChoice> {
+async fn get_choices(search: &str, anilist_client: Arc<AnilistClient>) -> Vec<AutocompleteChoice> {
 	trace!("{:?}", search);
 
 	let var = UserAutocompleteVariables {
@@ -58,7 +54,7 @@ async fn get_choices(
 	let operation = UserAutocomplete::build(var);
 
 	let data: Result<GraphQlResponse<UserAutocomplete>> =
-		make_request_anilist(operation, false, anilist_cache).await;
+		make_request_anilist(operation, false, anilist_client).await;
 
 	let data = match data {
 		Ok(data) => data,
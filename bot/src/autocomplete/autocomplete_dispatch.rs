@@ -1,92 +1,55 @@
-use crate::autocomplete::anilist_server::{add_anime_activity, delete_activity};
-use crate::autocomplete::anilist_user::{
-	anime, character, compare, ln, manga, search, staff, studio, user,
+use crate::command::command_dispatch::check_if_module_is_on;
+use crate::command::guess_kind::guess_command_kind;
+use crate::command::registry::{get_command, InteractionKind};
+use crate::event_handler::BotData;
+use serenity::all::{
+	CommandInteraction, Context, CreateAutocompleteResponse, CreateInteractionResponse,
 };
-use crate::autocomplete::game::steam_game_info;
-use crate::autocomplete::management::give_premium_sub::give_premium_sub_autocomplete;
-use crate::autocomplete::vn;
-use crate::autocomplete::vn::{game, producer};
-use crate::helper::get_option::subcommand_group::get_subcommand;
-use serenity::all::{CommandInteraction, Context};
-use tracing::trace;
+use tracing::{error, trace};
 
 pub async fn autocomplete_dispatching(ctx: Context, autocomplete_interaction: CommandInteraction) {
 	trace!(?ctx, ?autocomplete_interaction);
-	match autocomplete_interaction.data.name.as_str() {
-		"admin" => admin_autocomplete(ctx, autocomplete_interaction).await,
-		"anime" => anime::autocomplete(ctx, autocomplete_interaction).await,
-		"ln" => ln::autocomplete(ctx, autocomplete_interaction).await,
-		"manga" => manga::autocomplete(ctx, autocomplete_interaction).await,
-		"user" => user::autocomplete(ctx, autocomplete_interaction).await,
-		"character" => character::autocomplete(ctx, autocomplete_interaction).await,
-		"compare" => compare::autocomplete(ctx, autocomplete_interaction).await,
-		"register" => user::autocomplete(ctx, autocomplete_interaction).await,
-		"staff" => staff::autocomplete(ctx, autocomplete_interaction).await,
-		"studio" => studio::autocomplete(ctx, autocomplete_interaction).await,
-		"search" => search::autocomplete(ctx, autocomplete_interaction).await,
-		"seiyuu" => staff::autocomplete(ctx, autocomplete_interaction).await,
-		"steam" => steam_autocomplete(ctx, autocomplete_interaction).await,
-		"vn" => vn_autocomplete(ctx, autocomplete_interaction).await,
-		"give_premium_sub" => give_premium_sub_autocomplete(ctx, autocomplete_interaction).await,
-		_ => {},
-	}
-}
 
-async fn admin_autocomplete(ctx: Context, autocomplete_interaction: CommandInteraction) {
-	if autocomplete_interaction
-		.data
-		.options
-		.first()
-		.unwrap()
-		.name
-		.as_str()
-		== "anilist"
-	{
-		anilist_admin_autocomplete(ctx, autocomplete_interaction).await
-	}
-}
+	let (_, name) = guess_command_kind(&autocomplete_interaction);
 
-async fn vn_autocomplete(ctx: Context, autocomplete_interaction: CommandInteraction) {
-	match autocomplete_interaction
-		.data
-		.options
-		.first()
-		.unwrap()
-		.name
-		.as_str()
-	{
-		"game" => game::autocomplete(ctx, autocomplete_interaction).await,
-		"character" => vn::character::autocomplete(ctx, autocomplete_interaction).await,
-		"producer" => producer::autocomplete(ctx, autocomplete_interaction).await,
-		_ => {},
-	}
-}
+	let command = match get_command(InteractionKind::Slash, name.as_str()) {
+		Some(command) => command,
+		None => return,
+	};
 
-async fn anilist_admin_autocomplete(ctx: Context, autocomplete_interaction: CommandInteraction) {
-	let interaction = autocomplete_interaction.clone();
-	let subcommand = get_subcommand(&interaction).unwrap();
+	let autocomplete = match command.autocomplete {
+		Some(autocomplete) => autocomplete,
+		None => return,
+	};
 
-	let subcommand_name = subcommand.name;
+	if let Some(module) = command.module {
+		let bot_data = ctx.data::<BotData>().clone();
 
-	match subcommand_name {
-		"add_anime_activity" => {
-			add_anime_activity::autocomplete(ctx, autocomplete_interaction).await
-		},
-		"delete_activity" => delete_activity::autocomplete(ctx, autocomplete_interaction).await,
-		_ => {},
-	}
-}
+		let guild_id = match autocomplete_interaction.guild_id {
+			Some(id) => id.to_string(),
+			None => String::from("0"),
+		};
+
+		let is_module_on = check_if_module_is_on(guild_id, module, bot_data.db_connection.clone())
+			.await
+			.unwrap_or_else(|e| {
+				error!("Failed to get the module status. {}", e);
+
+				false
+			});
 
-async fn steam_autocomplete(ctx: Context, autocomplete_interaction: CommandInteraction) {
-	if autocomplete_interaction
-		.data
-		.options
-		.first()
-		.unwrap()
-		.name
-		.as_str()
-		== "game"
-	{
-		steam_game_info::autocomplete(ctx, autocomplete_interaction).await
+		// answer with no choice, the command itself replies with the module disabled message
+		if !is_module_on {
+			let builder =
+				CreateInteractionResponse::Autocomplete(CreateAutocompleteResponse::new());
+
+			let _ = autocomplete_interaction
+				.create_response(&ctx.http, builder)
+				.await;
+
+			return;
+		}
 	}
+
+	autocomplete(ctx, autocomplete_interaction).await
 }
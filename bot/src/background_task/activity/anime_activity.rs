@@ -2,110 +2,189 @@ use std::io::{Cursor, Read};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::background_task::activity::scheduler::{split_overdue, ScheduledActivity};
 use crate::command::admin::anilist::add_activity::get_minimal_anime_media;
-use crate::config::DbConfig;
+use crate::constant::{ACTIVITY_KIND_ANIME, ACTIVITY_RETRY_DELAY, ACTIVITY_SCHEDULER_MAX_SLEEP};
 use crate::database::activity_data;
-use crate::database::activity_data::Model;
+use crate::database::activity_data::{Column, Model};
 use crate::database::prelude::ActivityData;
-use crate::get_url;
+use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
+use crate::helper::make_graphql_cached::AnilistClient;
 use crate::structure::message::anilist_user::send_activity::load_localization_send_activity;
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
 use base64::engine::general_purpose::STANDARD;
 use base64::read::DecoderReader;
-use chrono::{DateTime, Utc};
-use moka::future::Cache;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use sea_orm::ActiveValue::Set;
-use sea_orm::QueryFilter;
-use sea_orm::{ColumnTrait, DeleteResult};
+use sea_orm::{ColumnTrait, Condition, QueryFilter, TransactionTrait};
 use sea_orm::{DatabaseConnection, EntityTrait};
 use serenity::builder::{CreateAttachment, EditWebhook, ExecuteWebhook};
 use serenity::model::webhook::Webhook;
 use serenity::prelude::Context as SerenityContext;
-use tokio::sync::RwLock;
-use tracing::{error, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
-pub async fn manage_activity(
-	ctx: SerenityContext, anilist_cache: Arc<RwLock<Cache<String, String>>>, db_config: DbConfig,
-) {
-	send_activity(&ctx, anilist_cache, db_config).await;
+/// Sends the anime activities when their episode airs. The activities are loaded in the
+/// scheduler of the bot data, then the loop sleeps until the first one is due or a new one is
+/// added. The activities missed for less than `grace` seconds are sent right away.
+
+pub async fn run_activity_scheduler(
+	ctx: SerenityContext, anilist_client: Arc<AnilistClient>, connection: Arc<DatabaseConnection>,
+	grace: u64,
+) -> Result<()> {
+	let scheduler = ctx.data::<BotData>().activity_scheduler.clone();
+
+	load_activities(&ctx, anilist_client.clone(), connection.clone(), grace).await?;
+
+	loop {
+		let now = Utc::now().timestamp();
+
+		for activity in scheduler.pop_due(now) {
+			tokio::spawn(send_scheduled(
+				ctx.clone(),
+				activity,
+				anilist_client.clone(),
+				connection.clone(),
+			));
+		}
+
+		// The sleep is capped so a change of the system clock is noticed.
+		let sleep = scheduler
+			.next_send_at()
+			.map(|send_at| (send_at - now).max(0) as u64)
+			.unwrap_or(ACTIVITY_SCHEDULER_MAX_SLEEP)
+			.min(ACTIVITY_SCHEDULER_MAX_SLEEP);
+
+		scheduler.wait(Duration::from_secs(sleep)).await;
+	}
 }
 
-async fn send_activity(
-	ctx: &SerenityContext, anilist_cache: Arc<RwLock<Cache<String, String>>>, db_config: DbConfig,
-) {
-	let now = Utc::now().naive_utc();
+/// Loads the anime activities in the scheduler. The ones missed for longer than `grace`
+/// seconds, while the bot was stopped, are moved to their next episode without being sent.
 
-	let connection = match sea_orm::Database::connect(get_url(db_config.clone())).await {
-		Ok(connection) => connection,
-		Err(e) => {
-			error!("{}", e);
+async fn load_activities(
+	ctx: &SerenityContext, anilist_client: Arc<AnilistClient>, connection: Arc<DatabaseConnection>,
+	grace: u64,
+) -> Result<()> {
+	let rows = ActivityData::find()
+		.filter(Column::MediaKind.eq(ACTIVITY_KIND_ANIME))
+		.all(&*connection)
+		.await
+		.context("Failed to load the anime activities")?;
 
-			return;
-		},
+	let (rows, missed) = split_overdue(rows, Utc::now().timestamp(), grace);
+
+	info!(
+		"Loaded {} anime activities, {} were missed.",
+		rows.len(),
+		missed.len()
+	);
+
+	ctx.data::<BotData>()
+		.activity_scheduler
+		.replace(rows.iter().map(ScheduledActivity::from_row).collect());
+
+	if missed.is_empty() {
+		return Ok(());
+	}
+
+	let ctx = ctx.clone();
+
+	tokio::spawn(async move {
+		for row in missed {
+			warn!(
+				"The episode {} of {} in {} aired too long ago to be sent.",
+				row.episode, row.anime_id, row.server_id
+			);
+
+			if let Err(e) = advance_activity(&ctx, &row, anilist_client.clone(), &connection).await
+			{
+				error!(
+					"Failed to move the activity of {} to its next episode. {:#}",
+					row.anime_id, e
+				);
+			}
+		}
+	});
+
+	Ok(())
+}
+
+/// Sends the activity when it was not changed since it was scheduled, then moves it to its next
+/// episode. It is left in the database once the bot is shutting down, the next start sends it.
+
+async fn send_scheduled(
+	ctx: SerenityContext, activity: ScheduledActivity, anilist_client: Arc<AnilistClient>,
+	connection: Arc<DatabaseConnection>,
+) {
+	let bot_data = ctx.data::<BotData>().clone();
+
+	let Some(_work) = bot_data.shutdown.start_work() else {
+		return;
 	};
 
-	let rows = match ActivityData::find()
-		.filter(<activity_data::Entity as EntityTrait>::Column::Timestamp.eq(now))
-		.all(&connection)
+	let row = match ActivityData::find_by_id((activity.anime_id, activity.server_id.clone()))
+		.one(&*connection)
 		.await
 	{
-		Ok(rows) => rows,
+		Ok(row) => row,
 		Err(e) => {
-			error!("{}", e);
+			error!(
+				"Failed to read the activity of {}. {:#}",
+				activity.anime_id, e
+			);
+
+			bot_data.activity_scheduler.schedule(retry(activity));
 
 			return;
 		},
 	};
 
-	for row in rows {
-		if now != row.timestamp {
-			continue;
-		}
+	// The activity was removed or moved to another episode since it was scheduled.
+	let Some(row) = row.filter(|row| row.timestamp == activity.timestamp) else {
+		debug!(
+			"The activity of {} in {} is outdated.",
+			activity.anime_id, activity.server_id
+		);
+
+		return;
+	};
 
+	// The episode is already sent when only its next episode could not be found before.
+	if row.sent_episode != Some(row.episode) {
 		let guild_id = row.server_id.clone();
 
-		if row.delay != 0 {
-			let anilist_cache = anilist_cache.clone();
-
-			let db_config = db_config.clone();
-
-			let ctx_clone = ctx.clone();
-			tokio::spawn(async move {
-				tokio::time::sleep(Duration::from_secs(row.delay as u64)).await;
-
-				if let Err(e) =
-					send_specific_activity(&row, guild_id, &ctx_clone, anilist_cache, db_config)
-						.await
-				{
-					error!("{}", e)
-				}
-			});
-		} else {
-			let anilist_cache = anilist_cache.clone();
-
-			let db_config = db_config.clone();
-			let ctx_clone = ctx.clone();
-			tokio::spawn(async move {
-				if let Err(e) =
-					send_specific_activity(&row, guild_id, &ctx_clone, anilist_cache, db_config)
-						.await
-				{
-					error!("{}", e);
-				}
-			});
+		if let Err(e) = send_specific_activity(&row, guild_id, &ctx, connection.clone()).await {
+			error!("{:#}", e);
 		}
 	}
+
+	if let Err(e) = advance_activity(&ctx, &row, anilist_client, &connection).await {
+		error!(
+			"Failed to move the activity of {} to its next episode. {:#}",
+			row.anime_id, e
+		);
+
+		bot_data.activity_scheduler.schedule(retry(activity));
+	}
+}
+
+/// The activity again in `ACTIVITY_RETRY_DELAY` seconds.
+
+fn retry(activity: ScheduledActivity) -> ScheduledActivity {
+	ScheduledActivity {
+		send_at: Utc::now().timestamp() + ACTIVITY_RETRY_DELAY,
+		..activity
+	}
 }
 
-#[instrument(skip(ctx, anilist_cache, db_config))]
+#[instrument(skip(ctx, connection))]
 
 async fn send_specific_activity(
-	row: &Model, guild_id: String, ctx: &SerenityContext,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>, db_config: DbConfig,
+	row: &Model, guild_id: String, ctx: &SerenityContext, connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let localised_text =
-		load_localization_send_activity(guild_id.clone(), db_config.clone()).await?;
+		load_localization_send_activity(guild_id.clone(), None, connection.clone()).await?;
 
 	let mut webhook = Webhook::from_url(&ctx.http, &row.webhook).await?;
 
@@ -134,17 +213,11 @@ async fn send_specific_activity(
 	let builder_message = ExecuteWebhook::new().embed(embed);
 
 	webhook.execute(&ctx.http, false, builder_message).await?;
-	let row_clone = row.clone();
-	tokio::spawn(async move {
-		if let Err(e) = update_info(&row_clone, &guild_id, anilist_cache, db_config).await {
-			error!("Failed to update info: {}", e);
-		}
-	});
 
 	Ok(())
 }
 
-fn decode_image(image: &str) -> Result<Vec<u8>> {
+pub fn decode_image(image: &str) -> Result<Vec<u8>> {
 	let cursor = Cursor::new(image);
 
 	let mut decoder = DecoderReader::new(cursor, &STANDARD);
@@ -156,77 +229,141 @@ fn decode_image(image: &str) -> Result<Vec<u8>> {
 	Ok(decoded_bytes)
 }
 
-async fn update_info(
-	row: &Model, guild_id: &str, anilist_cache: Arc<RwLock<Cache<String, String>>>,
-	db_config: DbConfig,
-) -> Result<()> {
-	let media = get_minimal_anime_media(row.anime_id.to_string(), anilist_cache).await?;
-
-	let next_airing = match media.next_airing_episode {
-		Some(airing) => airing,
-		None => {
-			trace!("No next airing episode for anime_id: {}", row.anime_id);
-
-			remove_activity(row, guild_id, db_config.clone())
-				.await
-				.context("failed to delete activity")?;
-			return Ok(());
-		},
-	};
+/// What happens to an activity once its episode is sent.
+#[derive(Debug, PartialEq)]
+
+enum ActivityTransition {
+	/// The activity waits for the next episode.
+	Next {
+		episode: i32,
+		timestamp: NaiveDateTime,
+	},
+	/// The anime has no next episode, the activity is removed.
+	Removed,
+	/// The next episode is not known yet, only the send is recorded.
+	Unknown,
+}
 
-	let title = media.title.ok_or(anyhow!("No title"))?;
+async fn next_transition(
+	row: &Model, anilist_client: Arc<AnilistClient>,
+) -> Result<ActivityTransition> {
+	let media = get_minimal_anime_media(row.anime_id.to_string(), anilist_client).await?;
 
-	let name = title
-		.english
-		.or(title.romaji)
-		.unwrap_or_else(|| "Unknown".to_string());
+	let Some(next_airing) = media.next_airing_episode else {
+		trace!("No next airing episode for anime_id: {}", row.anime_id);
 
-	let connection: DatabaseConnection = sea_orm::Database::connect(get_url(db_config)).await?;
+		return Ok(ActivityTransition::Removed);
+	};
 
 	let timestamp = DateTime::<Utc>::from_timestamp(next_airing.airing_at as i64, 0)
 		.unwrap_or_default()
 		.naive_utc();
 
-	let new_activity = activity_data::ActiveModel {
-		anime_id: Set(row.anime_id),
-		timestamp: Set(timestamp),
-		server_id: Set(guild_id.to_string()),
-		webhook: Set(row.webhook.clone()),
-		episode: Set(next_airing.episode),
-		name: Set(name),
-		delay: Set(row.delay),
-		image: Set(row.image.clone()),
-		..Default::default()
+	// AniList can still list the episode that just aired as the next one.
+	if timestamp <= row.timestamp {
+		return Ok(ActivityTransition::Unknown);
+	}
+
+	Ok(ActivityTransition::Next {
+		episode: next_airing.episode,
+		timestamp,
+	})
+}
+
+/// Records the episode of the activity as sent, then moves the activity to its next episode or
+/// removes it when the anime is over. The activity is scheduled again for its next episode, or
+/// retried later when the next episode is not known yet.
+
+async fn advance_activity(
+	ctx: &SerenityContext, row: &Model, anilist_client: Arc<AnilistClient>,
+	connection: &DatabaseConnection,
+) -> Result<()> {
+	let transition = match next_transition(row, anilist_client).await {
+		Ok(transition) => transition,
+		Err(e) => {
+			warn!(
+				"Failed to get the next episode of {}. {:#}",
+				row.anime_id, e
+			);
+
+			ActivityTransition::Unknown
+		},
 	};
 
-	ActivityData::insert(new_activity).exec(&connection).await?;
+	if !apply_transition(row, &transition, connection).await? {
+		debug!(
+			"The activity of {} in {} changed since it was sent.",
+			row.anime_id, row.server_id
+		);
+
+		return Ok(());
+	}
+
+	let scheduler = ctx.data::<BotData>().activity_scheduler.clone();
+
+	match transition {
+		ActivityTransition::Next { timestamp, .. } => scheduler.schedule(ScheduledActivity::new(
+			row.anime_id,
+			row.server_id.clone(),
+			timestamp,
+			row.delay,
+		)),
+		ActivityTransition::Removed => {},
+		ActivityTransition::Unknown => scheduler.schedule(retry(ScheduledActivity::from_row(row))),
+	}
 
 	Ok(())
 }
 
-async fn remove_activity(row: &Model, guild_id: &str, db_config: DbConfig) -> Result<DeleteResult> {
-	trace!(
-		"Attempting to remove activity for anime_id: {} in guild: {}",
-		row.anime_id,
-		guild_id
-	);
-
-	let connection: DatabaseConnection = sea_orm::Database::connect(get_url(db_config)).await?;
+/// Applies the transition and records the send of the episode in one transaction. The row is
+/// only changed when it still holds the episode of `row`, returns false when it was removed or
+/// changed since it was read.
+
+async fn apply_transition(
+	row: &Model, transition: &ActivityTransition, connection: &DatabaseConnection,
+) -> Result<bool> {
+	let unchanged = Condition::all()
+		.add(Column::AnimeId.eq(row.anime_id))
+		.add(Column::ServerId.eq(row.server_id.clone()))
+		.add(Column::Timestamp.eq(row.timestamp));
+
+	let txn = connection.begin().await?;
+
+	let rows_affected = match transition {
+		ActivityTransition::Next { episode, timestamp } => {
+			ActivityData::update_many()
+				.set(activity_data::ActiveModel {
+					episode: Set(*episode),
+					timestamp: Set(*timestamp),
+					sent_episode: Set(Some(row.episode)),
+					..Default::default()
+				})
+				.filter(unchanged)
+				.exec(&txn)
+				.await?
+				.rows_affected
+		},
+		ActivityTransition::Removed => {
+			ActivityData::delete_many()
+				.filter(unchanged)
+				.exec(&txn)
+				.await?
+				.rows_affected
+		},
+		ActivityTransition::Unknown => {
+			ActivityData::update_many()
+				.set(activity_data::ActiveModel {
+					sent_episode: Set(Some(row.episode)),
+					..Default::default()
+				})
+				.filter(unchanged)
+				.exec(&txn)
+				.await?
+				.rows_affected
+		},
+	};
 
-	let delete_result = ActivityData::delete(activity_data::ActiveModel {
-		anime_id: Set(row.anime_id),
-		server_id: Set(guild_id.to_string()),
-		..Default::default()
-	})
-	.exec(&connection)
-	.await?;
-
-	trace!(
-		"Removed {} row(s) for anime_id: {} in guild: {}",
-		delete_result.rows_affected,
-		row.anime_id,
-		guild_id
-	);
+	txn.commit().await?;
 
-	Ok(delete_result)
+	Ok(rows_affected != 0)
 }
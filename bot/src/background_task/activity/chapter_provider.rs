@@ -0,0 +1,295 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::error_management::kasuki_error::KasukiError;
+use crate::metrics::METRICS;
+
+/// Number of releases read on each check, more than the chapters released between two checks.
+
+const RELEASE_COUNT: u32 = 10;
+
+/// Number of series read when searching a title, an exact title is looked for among them.
+
+const SERIES_SEARCH_COUNT: u32 = 5;
+
+/// A series of the provider.
+#[derive(Debug, Clone, PartialEq)]
+
+pub struct Series {
+	pub id: String,
+	pub title: String,
+}
+
+/// A chapter released for a manga.
+#[derive(Debug, Clone, PartialEq)]
+
+pub struct ChapterRelease {
+	/// Chapter as written by the feed, `12`, `12.5` or `10-12`.
+	pub chapter: String,
+	/// The scanlation group or publisher that released it.
+	pub group: Option<String>,
+}
+
+impl ChapterRelease {
+	/// The highest chapter of the release, `12` for `10-12` and `12.5` for `12.5`.
+
+	pub fn number(&self) -> Option<f64> {
+		self.chapter
+			.split(['-', '~'])
+			.filter_map(|part| part.trim().parse::<f64>().ok())
+			.filter(|number| number.is_finite())
+			.max_by(f64::total_cmp)
+	}
+}
+
+/// Source of the chapter releases of the manga activities.
+
+#[async_trait]
+
+pub trait ChapterProvider: Send + Sync {
+	/// The series titled `title` on the provider, or its closest match.
+	async fn find_series(&self, title: &str) -> Result<Option<Series>>;
+
+	/// The latest releases of the series.
+	async fn latest_releases(&self, series_id: &str) -> Result<Vec<ChapterRelease>>;
+}
+
+/// The releases of `releases` after `last_chapter`, one per chapter in release order.
+
+pub fn new_releases(releases: &[ChapterRelease], last_chapter: f64) -> Vec<ChapterRelease> {
+	let mut new: Vec<(f64, ChapterRelease)> = releases
+		.iter()
+		.filter_map(|release| Some((release.number()?, release.clone())))
+		.filter(|(number, _)| *number > last_chapter)
+		.collect();
+
+	new.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+	// The same chapter released by several groups is only sent once.
+	new.dedup_by(|(a, _), (b, _)| a == b);
+
+	new.into_iter().map(|(_, release)| release).collect()
+}
+
+/// Releases of the MangaUpdates API, `base_url` is replaced by a local server in the tests.
+
+pub struct MangaUpdatesProvider {
+	http_client: Client,
+	base_url: String,
+}
+
+#[derive(Deserialize)]
+
+struct SearchResponse<T> {
+	results: Vec<SearchResult<T>>,
+}
+
+#[derive(Deserialize)]
+
+struct SearchResult<T> {
+	record: T,
+}
+
+#[derive(Deserialize)]
+
+struct SeriesRecord {
+	series_id: i64,
+	title: String,
+}
+
+#[derive(Deserialize)]
+
+struct ReleaseRecord {
+	chapter: String,
+	#[serde(default)]
+	groups: Vec<ReleaseGroup>,
+}
+
+#[derive(Deserialize)]
+
+struct ReleaseGroup {
+	name: String,
+}
+
+impl MangaUpdatesProvider {
+	pub fn new(http_client: Client, base_url: &str) -> Self {
+		Self {
+			http_client,
+			base_url: base_url.trim_end_matches('/').to_string(),
+		}
+	}
+
+	async fn search<T: for<'de> Deserialize<'de>>(
+		&self, path: &str, body: serde_json::Value,
+	) -> Result<Vec<T>> {
+		let response = self
+			.http_client
+			.post(format!("{}{}", self.base_url, path))
+			.json(&body)
+			.send()
+			.await
+			.map_err(|e| {
+				METRICS.record_api_request("mangaupdates", "error".to_string());
+
+				KasukiError::UpstreamApi(format!("MangaUpdates request failed: {}", e))
+			})?;
+
+		let status = response.status();
+
+		METRICS.record_api_request("mangaupdates", status.as_u16().to_string());
+
+		if !status.is_success() {
+			return Err(
+				KasukiError::UpstreamApi(format!("MangaUpdates answered {}", status)).into(),
+			);
+		}
+
+		let response: SearchResponse<T> = response
+			.json()
+			.await
+			.context("Failed to parse the MangaUpdates response")?;
+
+		Ok(response
+			.results
+			.into_iter()
+			.map(|result| result.record)
+			.collect())
+	}
+}
+
+#[async_trait]
+
+impl ChapterProvider for MangaUpdatesProvider {
+	async fn find_series(&self, title: &str) -> Result<Option<Series>> {
+		let series: Vec<SeriesRecord> = self
+			.search(
+				"/series/search",
+				json!({ "search": title, "perpage": SERIES_SEARCH_COUNT }),
+			)
+			.await?;
+
+		// The search is fuzzy, the first result is only used when no title is exact.
+		let series = series
+			.iter()
+			.find(|series| series.title.to_lowercase() == title.to_lowercase())
+			.or(series.first());
+
+		Ok(series.map(|series| Series {
+			id: series.series_id.to_string(),
+			title: series.title.clone(),
+		}))
+	}
+
+	async fn latest_releases(&self, series_id: &str) -> Result<Vec<ChapterRelease>> {
+		let releases: Vec<ReleaseRecord> = self
+			.search(
+				"/releases/search",
+				json!({
+					"search": series_id,
+					"search_type": "series",
+					"perpage": RELEASE_COUNT,
+				}),
+			)
+			.await?;
+
+		Ok(releases
+			.into_iter()
+			.map(|release| ChapterRelease {
+				chapter: release.chapter,
+				group: release.groups.into_iter().next().map(|group| group.name),
+			})
+			.collect())
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use axum::routing::post;
+	use axum::{Json, Router};
+
+	fn release(chapter: &str) -> ChapterRelease {
+		ChapterRelease {
+			chapter: chapter.to_string(),
+			group: None,
+		}
+	}
+
+	#[test]
+
+	fn test_new_releases() {
+		assert_eq!(release("10-12").number(), Some(12.0));
+
+		assert_eq!(release("12.5").number(), Some(12.5));
+
+		assert_eq!(release("extra").number(), None);
+
+		let releases =
+			[release("14"), release("13"), release("13"), release("12.5"), release("12")];
+
+		assert_eq!(
+			new_releases(&releases, 12.0),
+			vec![release("12.5"), release("13"), release("14")]
+		);
+
+		assert_eq!(
+			new_releases(&releases, 12.5),
+			vec![release("13"), release("14")]
+		);
+
+		assert!(new_releases(&releases, 14.0).is_empty());
+	}
+
+	#[tokio::test]
+
+	async fn test_manga_updates_stub() {
+		// Stands in for the MangaUpdates API.
+		let app = Router::new()
+			.route(
+				"/v1/series/search",
+				post(|| async {
+					Json(json!({ "results": [
+						{ "record": { "series_id": 41, "title": "One Piece Party" } },
+						{ "record": { "series_id": 42, "title": "One Piece" } },
+					] }))
+				}),
+			)
+			.route(
+				"/v1/releases/search",
+				post(|Json(body): Json<serde_json::Value>| async move {
+					assert_eq!(body["search"], "42");
+
+					Json(json!({ "results": [
+						{ "record": { "chapter": "101", "groups": [{ "name": "Group" }] } },
+						{ "record": { "chapter": "100", "groups": [] } },
+					] }))
+				}),
+			);
+
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+		let address = listener.local_addr().unwrap();
+
+		tokio::spawn(async move { axum::serve(listener, app).await });
+
+		let provider = MangaUpdatesProvider::new(Client::new(), &format!("http://{}/v1/", address));
+
+		let series = provider.find_series("one piece").await.unwrap().unwrap();
+
+		assert_eq!(series.id, "42");
+
+		let releases = provider.latest_releases(&series.id).await.unwrap();
+
+		assert_eq!(
+			new_releases(&releases, 100.0),
+			vec![ChapterRelease {
+				chapter: "101".to_string(),
+				group: Some("Group".to_string()),
+			}]
+		);
+	}
+}
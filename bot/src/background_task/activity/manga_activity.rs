@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serenity::builder::{CreateAttachment, EditWebhook, ExecuteWebhook};
+use serenity::model::webhook::Webhook;
+use serenity::prelude::Context as SerenityContext;
+use tracing::{error, instrument, trace, warn};
+
+use crate::background_task::activity::anime_activity::decode_image;
+use crate::background_task::activity::chapter_provider::{
+	new_releases, ChapterProvider, ChapterRelease,
+};
+use crate::constant::{ACTIVITY_KIND_MANGA, MAX_WEBHOOK_EMBEDS};
+use crate::database::activity_data;
+use crate::database::activity_data::{Column, Model};
+use crate::database::prelude::ActivityData;
+use crate::event_handler::BotData;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::structure::message::anilist_user::send_manga_activity::load_localization_send_manga_activity;
+
+/// Sends the chapters released since the last check of every manga activity.
+
+pub async fn manage_manga_activity(
+	ctx: SerenityContext, chapter_provider: Arc<dyn ChapterProvider>,
+	connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let rows = ActivityData::find()
+		.filter(Column::MediaKind.eq(ACTIVITY_KIND_MANGA))
+		.all(&*connection)
+		.await?;
+
+	// The releases of a series are requested once for every guild following it.
+	let mut series: HashMap<String, Vec<Model>> = HashMap::new();
+
+	for row in rows {
+		match row.external_id.clone() {
+			Some(series_id) => series.entry(series_id).or_default().push(row),
+			None => warn!("The manga activity {} has no series id.", row.anime_id),
+		}
+	}
+
+	for (series_id, rows) in series {
+		let releases = match chapter_provider.latest_releases(&series_id).await {
+			Ok(releases) => releases,
+			Err(e) => {
+				warn!("Failed to get the releases of {}. {:#}", series_id, e);
+
+				continue;
+			},
+		};
+
+		for row in rows {
+			let new = new_releases(&releases, last_chapter(&row));
+
+			if new.is_empty() {
+				continue;
+			}
+
+			if let Err(e) = send_chapters(&ctx, &row, &new, connection.clone()).await {
+				error!("{:#}", e);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Sends the chapters then saves the last one. Nothing is sent once the bot is shutting down,
+/// the chapters are still new at the next check.
+#[instrument(skip(ctx, row, connection), fields(manga_id = row.anime_id, guild_id = row.server_id))]
+
+async fn send_chapters(
+	ctx: &SerenityContext, row: &Model, releases: &[ChapterRelease],
+	connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let bot_data = ctx.data::<BotData>().clone();
+
+	let Some(_work) = bot_data.shutdown.start_work() else {
+		return Ok(());
+	};
+
+	let localised_text =
+		load_localization_send_manga_activity(row.server_id.clone(), None, connection.clone())
+			.await?;
+
+	let mut webhook = Webhook::from_url(&ctx.http, &row.webhook).await?;
+
+	trace!("Decoding image");
+
+	let decoded_bytes = decode_image(&row.image)?;
+
+	let trimmed_name = row.name.chars().take(100).collect::<String>();
+
+	let attachment = CreateAttachment::bytes(decoded_bytes, "avatar");
+
+	let edit_webhook = EditWebhook::new().name(trimmed_name).avatar(&attachment);
+
+	webhook.edit(&ctx.http, edit_webhook).await?;
+
+	// Only the latest chapters are sent when more were released since the last check.
+	let skip = releases.len().saturating_sub(MAX_WEBHOOK_EMBEDS);
+
+	let embeds = releases
+		.iter()
+		.skip(skip)
+		.map(|release| {
+			let mut description = localised_text
+				.desc
+				.replace("$chapter$", &release.chapter)
+				.replace("$manga$", &row.name);
+
+			if let Some(group) = &release.group {
+				description = format!(
+					"{}\n{}",
+					description,
+					localised_text.group.replace("$group$", group)
+				);
+			}
+
+			get_default_embed(None)
+				.description(description)
+				.url(format!("https://anilist.co/manga/{}", row.anime_id))
+				.title(&localised_text.title)
+		})
+		.collect::<Vec<_>>();
+
+	webhook
+		.execute(&ctx.http, false, ExecuteWebhook::new().embeds(embeds))
+		.await?;
+
+	let last_chapter = releases
+		.iter()
+		.filter_map(ChapterRelease::number)
+		.max_by(f64::total_cmp)
+		.unwrap_or(last_chapter(row));
+
+	ActivityData::update(activity_data::ActiveModel {
+		anime_id: Unchanged(row.anime_id),
+		server_id: Unchanged(row.server_id.clone()),
+		episode: Set(last_chapter.floor() as i32),
+		last_chapter: Set(Some(last_chapter.to_string())),
+		timestamp: Set(Utc::now().naive_utc()),
+		..Default::default()
+	})
+	.exec(&*connection)
+	.await?;
+
+	Ok(())
+}
+
+/// The last chapter sent by the activity, its episode when it was added before the decimal
+/// chapters were stored.
+
+fn last_chapter(row: &Model) -> f64 {
+	row.last_chapter
+		.as_deref()
+		.and_then(|chapter| chapter.parse().ok())
+		.unwrap_or(row.episode as f64)
+}
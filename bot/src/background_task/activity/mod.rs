@@ -1 +1,5 @@
 pub mod anime_activity;
+pub mod chapter_provider;
+pub mod manga_activity;
+pub mod scheduler;
+pub mod user_activity;
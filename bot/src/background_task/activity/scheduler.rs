@@ -0,0 +1,196 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use tokio::sync::Notify;
+
+use crate::database::activity_data::Model;
+
+/// An anime activity waiting for its episode to air, ordered by its send time.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+
+pub struct ScheduledActivity {
+	/// Unix timestamp of the send, the airing time plus the delay of the guild.
+	pub send_at: i64,
+	pub anime_id: i32,
+	pub server_id: String,
+	/// Airing time of the episode when it was scheduled, the row was changed since when it
+	/// differs.
+	pub timestamp: NaiveDateTime,
+}
+
+impl ScheduledActivity {
+	pub fn new(anime_id: i32, server_id: String, timestamp: NaiveDateTime, delay: i32) -> Self {
+		Self {
+			send_at: timestamp.and_utc().timestamp() + delay as i64,
+			anime_id,
+			server_id,
+			timestamp,
+		}
+	}
+
+	pub fn from_row(row: &Model) -> Self {
+		Self::new(
+			row.anime_id,
+			row.server_id.clone(),
+			row.timestamp,
+			row.delay,
+		)
+	}
+}
+
+/// The anime activities by send time. The scheduler loop sleeps until the first one is due or
+/// a new one is scheduled.
+#[derive(Default)]
+
+pub struct ActivityScheduler {
+	queue: Mutex<BinaryHeap<Reverse<ScheduledActivity>>>,
+	changed: Notify,
+}
+
+impl ActivityScheduler {
+	/// Adds an activity and wakes the scheduler loop, the activity may be due before the one it
+	/// waits for.
+
+	pub fn schedule(&self, activity: ScheduledActivity) {
+		if let Ok(mut queue) = self.queue.lock() {
+			queue.push(Reverse(activity));
+		}
+
+		self.changed.notify_one();
+	}
+
+	/// Replaces every scheduled activity, used when the activities are loaded from the database.
+
+	pub fn replace(&self, activities: Vec<ScheduledActivity>) {
+		if let Ok(mut queue) = self.queue.lock() {
+			*queue = activities.into_iter().map(Reverse).collect();
+		}
+
+		self.changed.notify_one();
+	}
+
+	/// Removes and returns the activities due at `now`, the earliest first.
+
+	pub fn pop_due(&self, now: i64) -> Vec<ScheduledActivity> {
+		let Ok(mut queue) = self.queue.lock() else {
+			return Vec::new();
+		};
+
+		let mut due = Vec::new();
+
+		while queue
+			.peek()
+			.is_some_and(|Reverse(activity)| activity.send_at <= now)
+		{
+			if let Some(Reverse(activity)) = queue.pop() {
+				due.push(activity);
+			}
+		}
+
+		due
+	}
+
+	/// Send time of the next activity.
+
+	pub fn next_send_at(&self) -> Option<i64> {
+		self.queue
+			.lock()
+			.ok()?
+			.peek()
+			.map(|Reverse(activity)| activity.send_at)
+	}
+
+	/// Waits until an activity is scheduled or `timeout` is reached.
+
+	pub async fn wait(&self, timeout: Duration) {
+		let _ = tokio::time::timeout(timeout, self.changed.notified()).await;
+	}
+}
+
+/// Splits the activities loaded on boot between the ones to schedule and the ones missed for
+/// longer than `grace` seconds, which are moved to their next episode without being sent.
+
+pub fn split_overdue(rows: Vec<Model>, now: i64, grace: u64) -> (Vec<Model>, Vec<Model>) {
+	rows.into_iter()
+		.partition(|row| ScheduledActivity::from_row(row).send_at >= now - grace as i64)
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use chrono::DateTime;
+
+	fn activity(anime_id: i32, send_at: i64) -> ScheduledActivity {
+		ScheduledActivity::new(
+			anime_id,
+			"1".to_string(),
+			DateTime::from_timestamp(send_at, 0).unwrap().naive_utc(),
+			0,
+		)
+	}
+
+	#[test]
+
+	fn test_pop_due() {
+		let scheduler = ActivityScheduler::default();
+
+		scheduler.schedule(activity(3, 300));
+
+		scheduler.schedule(activity(1, 100));
+
+		scheduler.schedule(activity(2, 200));
+
+		assert_eq!(scheduler.next_send_at(), Some(100));
+
+		assert!(scheduler.pop_due(99).is_empty());
+
+		assert_eq!(
+			scheduler.pop_due(200),
+			vec![activity(1, 100), activity(2, 200)]
+		);
+
+		assert_eq!(scheduler.next_send_at(), Some(300));
+
+		scheduler.replace(vec![activity(4, 50)]);
+
+		assert_eq!(scheduler.pop_due(1000), vec![activity(4, 50)]);
+
+		assert_eq!(scheduler.next_send_at(), None);
+	}
+
+	#[test]
+
+	fn test_split_overdue() {
+		let row = |anime_id: i32, timestamp: i64, delay: i32| Model {
+			anime_id,
+			server_id: "1".to_string(),
+			episode: 1,
+			webhook: String::new(),
+			name: String::new(),
+			image: String::new(),
+			delay,
+			timestamp: DateTime::from_timestamp(timestamp, 0).unwrap().naive_utc(),
+			media_kind: "anime".to_string(),
+			external_id: None,
+			sent_episode: None,
+			last_chapter: None,
+		};
+
+		let (scheduled, missed) = split_overdue(
+			vec![row(1, 2000, 0), row(2, 900, 0), row(3, 100, 0), row(4, 100, 850)],
+			1000,
+			100,
+		);
+
+		let ids = |rows: Vec<Model>| rows.iter().map(|row| row.anime_id).collect::<Vec<_>>();
+
+		// The delay of the guild counts, the activity is sent at the airing plus the delay.
+		assert_eq!(ids(scheduled), vec![1, 2, 4]);
+
+		assert_eq!(ids(missed), vec![3]);
+	}
+}
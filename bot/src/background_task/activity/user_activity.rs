@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use cynic::{GraphQlResponse, QueryBuilder};
+use sea_orm::ActiveValue::{Set, Unchanged};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serenity::all::{CreateEmbed, ExecuteWebhook, Timestamp, Webhook};
+use serenity::prelude::Context as SerenityContext;
+use tracing::{error, instrument, warn};
+
+use crate::constant::{
+	FOLLOWED_USER_BATCH, FOLLOWED_USER_MAX_PAGES, FOLLOWED_USER_PER_PAGE, MAX_WEBHOOK_EMBEDS,
+};
+use crate::database::followed_user;
+use crate::database::followed_user::Model;
+use crate::database::prelude::FollowedUser;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
+use crate::structure::message::anilist_user::send_user_activity::{
+	load_localization_send_user_activity, SendUserActivityLocalised,
+};
+use crate::structure::run::anilist::user_activity::{
+	ActivityUnion, ListActivity, UserActivity, UserActivityVariables,
+};
+
+/// Sends the list updates of the followed users since the last check. The users are requested
+/// by batches of `FOLLOWED_USER_BATCH` in one AniList request.
+
+pub async fn manage_followed_users(
+	ctx: SerenityContext, anilist_client: Arc<AnilistClient>, connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let follows = FollowedUser::find().all(&*connection).await?;
+
+	// A user followed in several guilds is requested once.
+	let mut users: HashMap<i32, Vec<Model>> = HashMap::new();
+
+	for follow in follows {
+		users.entry(follow.anilist_id).or_default().push(follow);
+	}
+
+	let user_ids: Vec<i32> = users.keys().copied().collect();
+
+	for batch in user_ids.chunks(FOLLOWED_USER_BATCH) {
+		let last_seen = stop_activity_id(batch.iter().flat_map(|user_id| users[user_id].iter()));
+
+		let activities = match fetch_activities(batch, last_seen, anilist_client.clone()).await {
+			Ok(activities) => activities,
+			Err(e) => {
+				warn!("Failed to get the activities of {:?}. {:#}", batch, e);
+
+				continue;
+			},
+		};
+
+		for follow in batch.iter().flat_map(|user_id| users[user_id].iter()) {
+			let new = activities_after(&activities, follow.anilist_id, follow.last_activity_id);
+
+			if new.is_empty() {
+				continue;
+			}
+
+			if let Err(e) = send_user_activities(&ctx, follow, &new, connection.clone()).await {
+				error!("{:#}", e);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// The activity id the pages of a batch are read back to, the oldest last activity of its
+/// follows. A follow of a user without any activity has the id 0, it only needs the first page
+/// and is left out so it doesn't make every check read all the pages.
+
+fn stop_activity_id<'a>(follows: impl Iterator<Item = &'a Model>) -> i32 {
+	follows
+		.map(|follow| follow.last_activity_id)
+		.filter(|last_activity_id| *last_activity_id > 0)
+		.min()
+		.unwrap_or(i32::MAX)
+}
+
+/// The list updates of `user_ids`, the newest first. The pages are read until one holds an
+/// activity older than `last_seen`, at most `FOLLOWED_USER_MAX_PAGES`.
+
+pub async fn fetch_activities(
+	user_ids: &[i32], last_seen: i32, anilist_client: Arc<AnilistClient>,
+) -> Result<Vec<ListActivity>> {
+	let mut activities = Vec::new();
+
+	for page in 1..=FOLLOWED_USER_MAX_PAGES {
+		let operation = UserActivity::build(UserActivityVariables {
+			user_ids: Some(user_ids.iter().map(|user_id| Some(*user_id)).collect()),
+			page: Some(page),
+			per_page: Some(FOLLOWED_USER_PER_PAGE),
+		});
+
+		let response: GraphQlResponse<UserActivity> =
+			make_request_anilist(operation, true, anilist_client.clone()).await?;
+
+		let page = response
+			.data
+			.and_then(|data| data.page)
+			.ok_or(anyhow!("No activity page in the response"))?;
+
+		let has_next_page = page
+			.page_info
+			.and_then(|page_info| page_info.has_next_page)
+			.unwrap_or_default();
+
+		let list: Vec<ListActivity> = page
+			.activities
+			.unwrap_or_default()
+			.into_iter()
+			.flatten()
+			.filter_map(|activity| match activity {
+				ActivityUnion::ListActivity(activity) => Some(activity),
+				ActivityUnion::Unknown => None,
+			})
+			.collect();
+
+		// The next pages only hold older activities.
+		let seen = list
+			.last()
+			.map_or(true, |activity| activity.id <= last_seen);
+
+		activities.extend(list);
+
+		if seen || !has_next_page {
+			break;
+		}
+	}
+
+	Ok(activities)
+}
+
+/// The activities of `user_id` after `last_activity_id`, the oldest first.
+
+pub fn activities_after(
+	activities: &[ListActivity], user_id: i32, last_activity_id: i32,
+) -> Vec<ListActivity> {
+	let mut new: Vec<ListActivity> = activities
+		.iter()
+		.filter(|activity| activity.user_id == Some(user_id) && activity.id > last_activity_id)
+		.cloned()
+		.collect();
+
+	new.sort_by_key(|activity| activity.id);
+
+	new
+}
+
+#[instrument(skip(ctx, follow, activities, connection), fields(anilist_id = follow.anilist_id, guild_id = follow.guild_id))]
+
+async fn send_user_activities(
+	ctx: &SerenityContext, follow: &Model, activities: &[ListActivity],
+	connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let localised_text =
+		load_localization_send_user_activity(follow.guild_id.clone(), None, connection.clone())
+			.await?;
+
+	let webhook = Webhook::from_url(&ctx.http, &follow.webhook).await?;
+
+	for chunk in activities.chunks(MAX_WEBHOOK_EMBEDS) {
+		let embeds = chunk
+			.iter()
+			.map(|activity| activity_embed(activity, &localised_text))
+			.collect::<Vec<_>>();
+
+		let mut builder = ExecuteWebhook::new().embeds(embeds);
+
+		// The webhook is shared with the anime activities, it takes the name of the user.
+		if let Some(user) = chunk.first().and_then(|activity| activity.user.clone()) {
+			builder = builder.username(user.name);
+
+			if let Some(avatar) = user.avatar.and_then(|avatar| avatar.large) {
+				builder = builder.avatar_url(avatar);
+			}
+		}
+
+		webhook.execute(&ctx.http, false, builder).await?;
+
+		let last_activity_id = chunk
+			.iter()
+			.map(|activity| activity.id)
+			.max()
+			.unwrap_or(follow.last_activity_id);
+
+		FollowedUser::update(followed_user::ActiveModel {
+			guild_id: Unchanged(follow.guild_id.clone()),
+			anilist_id: Unchanged(follow.anilist_id),
+			last_activity_id: Set(last_activity_id),
+			..Default::default()
+		})
+		.exec(&*connection)
+		.await?;
+	}
+
+	Ok(())
+}
+
+fn activity_embed(
+	activity: &ListActivity, localised_text: &SendUserActivityLocalised,
+) -> CreateEmbed<'static> {
+	let user_name = activity
+		.user
+		.as_ref()
+		.map(|user| user.name.clone())
+		.unwrap_or_default();
+
+	let media = activity.media.clone();
+
+	let title = media
+		.as_ref()
+		.and_then(|media| media.title.clone())
+		.and_then(|title| title.english.or(title.romaji))
+		.unwrap_or_else(|| "Unknown".to_string());
+
+	let status = activity.status.clone().unwrap_or_default();
+
+	let description = match &activity.progress {
+		Some(progress) => localised_text
+			.progress_desc
+			.replace("$status$", &status)
+			.replace("$progress$", progress),
+		None => localised_text.desc.replace("$status$", &status),
+	}
+	.replace("$media$", &title);
+
+	let mut embed = get_default_embed(None)
+		.title(localised_text.title.replace("$user$", &user_name))
+		.description(description);
+
+	if let Some(url) = activity.site_url.clone() {
+		embed = embed.url(url);
+	}
+
+	if let Some(cover) = media
+		.and_then(|media| media.cover_image)
+		.and_then(|cover_image| cover_image.large)
+	{
+		embed = embed.thumbnail(cover);
+	}
+
+	if let Ok(timestamp) = Timestamp::from_unix_timestamp(activity.created_at as i64) {
+		embed = embed.timestamp(timestamp);
+	}
+
+	embed
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	fn activity(id: i32, user_id: i32) -> ListActivity {
+		ListActivity {
+			id,
+			user_id: Some(user_id),
+			status: Some("watched episode".to_string()),
+			progress: Some("5".to_string()),
+			site_url: None,
+			created_at: 0,
+			user: None,
+			media: None,
+		}
+	}
+
+	#[test]
+
+	fn test_activities_after() {
+		let activities = [activity(40, 1), activity(30, 2), activity(20, 1), activity(10, 1)];
+
+		let ids = |activities: Vec<ListActivity>| {
+			activities
+				.iter()
+				.map(|activity| activity.id)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(ids(activities_after(&activities, 1, 10)), vec![20, 40]);
+
+		assert_eq!(ids(activities_after(&activities, 2, 10)), vec![30]);
+
+		assert!(activities_after(&activities, 1, 40).is_empty());
+	}
+
+	#[test]
+
+	fn test_stop_activity_id() {
+		let follow = |last_activity_id: i32| Model {
+			guild_id: "1".to_string(),
+			anilist_id: 1,
+			webhook: String::new(),
+			last_activity_id,
+			created_at: chrono::DateTime::UNIX_EPOCH.naive_utc(),
+		};
+
+		assert_eq!(
+			stop_activity_id([follow(0), follow(30), follow(20)].iter()),
+			20
+		);
+
+		assert_eq!(stop_activity_id([follow(0)].iter()), i32::MAX);
+	}
+}
@@ -2,317 +2,382 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
-use moka::future::Cache;
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{DatabaseConnection, EntityTrait};
 use serde_json::Value;
 use serenity::all::Context as SerenityContext;
 use tokio::sync::RwLock;
-use tokio::time::{interval, sleep};
 use tracing::{debug, error, info};
 
-use crate::background_task::activity::anime_activity::manage_activity;
+use crate::background_task::activity::anime_activity::run_activity_scheduler;
+use crate::background_task::activity::manga_activity::manage_manga_activity;
+use crate::background_task::activity::user_activity::manage_followed_users;
 use crate::background_task::server_image::calculate_user_color::color_management;
 use crate::background_task::server_image::generate_server_image::server_image_management;
-use crate::background_task::update_random_stats::update_random_stats_launcher;
-use crate::config::{ DbConfig, ImageConfig};
-use crate::constant::{
-	TIME_BEFORE_SERVER_IMAGE, TIME_BETWEEN_ACTIVITY_CHECK, TIME_BETWEEN_BLACKLISTED_USER_UPDATE,
-	TIME_BETWEEN_BOT_INFO, TIME_BETWEEN_GAME_UPDATE, TIME_BETWEEN_PING_UPDATE,
-	TIME_BETWEEN_SERVER_IMAGE_UPDATE, TIME_BETWEEN_USER_COLOR_UPDATE,
-};
+use crate::background_task::update_random_stats::update_random_stats;
+use crate::cache::CacheBackend;
+use crate::config::ImageConfig;
+use crate::constant::ACTIVITY_SCHEDULER_MAX_SLEEP;
 use crate::database::ping_history::ActiveModel;
 use crate::database::prelude::PingHistory;
-use crate::event_handler::{BotData};
-use crate::get_url;
+use crate::event_handler::BotData;
+use crate::helper::command_usage::prune_command_usage;
+use crate::helper::make_graphql_cached::AnilistClient;
+use crate::metrics::{ShardLabels, METRICS};
 use crate::structure::steam_game_id_struct::get_game;
-pub async fn thread_management_launcher(
-	ctx: SerenityContext, bot_data: Arc<BotData>, db_config: DbConfig,
-) {
-	let anilist_cache = bot_data.anilist_cache.clone();
 
-	let apps = bot_data.apps.clone();
+/// Starts every background task under the supervisor of `bot_data`, with the intervals of the
+/// `[tasks]` section of the config.
 
-	let user_blacklist_server_image = bot_data.user_blacklist_server_image.clone();
+pub fn thread_management_launcher(ctx: SerenityContext, bot_data: Arc<BotData>) {
+	let supervisor = bot_data.tasks.clone();
+
+	let tasks = bot_data.config.tasks.clone();
+
+	let every = |seconds: u64| Duration::from_secs(seconds);
+
+	let anilist_client = bot_data.anilist_client.clone();
 
 	let connection = bot_data.db_connection.clone();
 
-	tokio::spawn(launch_activity_management_thread(
-		ctx.clone(),
-		anilist_cache.clone(),
-		db_config.clone(),
-	));
+	{
+		let (ctx, anilist_client, connection) =
+			(ctx.clone(), anilist_client.clone(), connection.clone());
+
+		let grace = tasks.activity_catch_up_grace;
+
+		// The scheduler only returns on failure, it is then restarted and reloads the activities.
+		supervisor.spawn(
+			"activity",
+			Duration::ZERO,
+			every(ACTIVITY_SCHEDULER_MAX_SLEEP),
+			move || {
+				run_activity_scheduler(
+					ctx.clone(),
+					anilist_client.clone(),
+					connection.clone(),
+					grace,
+				)
+			},
+		);
+	}
 
-	tokio::spawn(launch_game_management_thread(apps));
+	{
+		let (ctx, chapter_provider, connection) = (
+			ctx.clone(),
+			bot_data.chapter_provider.clone(),
+			connection.clone(),
+		);
+
+		supervisor.spawn(
+			"manga_activity",
+			Duration::ZERO,
+			every(tasks.manga_activity_check),
+			move || {
+				manage_manga_activity(ctx.clone(), chapter_provider.clone(), connection.clone())
+			},
+		);
+	}
 
-	tokio::spawn(ping_manager_thread(ctx.clone(), db_config.clone()));
+	{
+		let (ctx, anilist_client, connection) =
+			(ctx.clone(), anilist_client.clone(), connection.clone());
+
+		supervisor.spawn(
+			"followed_user",
+			Duration::ZERO,
+			every(tasks.followed_user_check),
+			move || manage_followed_users(ctx.clone(), anilist_client.clone(), connection.clone()),
+		);
+	}
 
-	tokio::spawn(update_user_blacklist(user_blacklist_server_image.clone()));
+	{
+		let apps = bot_data.apps.clone();
+
+		supervisor.spawn(
+			"game_list",
+			Duration::ZERO,
+			every(tasks.game_update),
+			move || update_game_list(apps.clone()),
+		);
+	}
 
-	tokio::spawn(update_random_stats_launcher(anilist_cache.clone()));
+	{
+		let (ctx, connection) = (ctx.clone(), connection.clone());
+
+		supervisor.spawn(
+			"ping_history",
+			Duration::ZERO,
+			every(tasks.ping_update),
+			move || update_ping_history(ctx.clone(), connection.clone()),
+		);
+	}
 
-	tokio::spawn(update_bot_info(ctx.clone(), bot_data.clone()));
+	{
+		let blacklist = bot_data.user_blacklist_server_image.clone();
+
+		supervisor.spawn(
+			"blacklist",
+			Duration::ZERO,
+			every(tasks.blacklist_update),
+			move || update_user_blacklist(blacklist.clone()),
+		);
+	}
 
-	sleep(Duration::from_secs(1)).await;
+	{
+		let anilist_client = anilist_client.clone();
+
+		supervisor.spawn(
+			"random_stats",
+			Duration::ZERO,
+			every(tasks.random_stats_update),
+			move || update_random_stats_task(anilist_client.clone()),
+		);
+	}
 
-	sleep(Duration::from_secs(TIME_BEFORE_SERVER_IMAGE)).await;
+	{
+		let (ctx, bot_data) = (ctx.clone(), bot_data.clone());
+
+		supervisor.spawn(
+			"bot_info",
+			Duration::ZERO,
+			every(tasks.bot_info_update),
+			move || update_bot_info(ctx.clone(), bot_data.clone()),
+		);
+	}
 
-	tokio::spawn(launch_user_color_management_thread(
-		ctx.clone(),
-		user_blacklist_server_image.clone(),
-		bot_data.clone(),
-	));
+	{
+		let connection = connection.clone();
+
+		let retention_days = bot_data.config.bot.command_usage_retention_days;
+
+		supervisor.spawn(
+			"command_usage_prune",
+			Duration::ZERO,
+			every(tasks.command_usage_prune),
+			move || prune_old_command_usage(connection.clone(), retention_days),
+		);
+	}
 
-	sleep(Duration::from_secs(TIME_BEFORE_SERVER_IMAGE)).await;
+	{
+		let cache_backend = bot_data.cache_backend.clone();
+
+		supervisor.spawn(
+			"cache_sweep",
+			Duration::ZERO,
+			every(tasks.cache_sweep),
+			move || remove_expired_cache(cache_backend.clone()),
+		);
+	}
 
-	let image_config = bot_data.config.image.clone();
+	{
+		let (ctx, bot_data) = (ctx.clone(), bot_data.clone());
+
+		supervisor.spawn(
+			"user_color",
+			every(tasks.server_image_delay),
+			every(tasks.user_color_update),
+			move || update_user_color(ctx.clone(), bot_data.clone()),
+		);
+	}
 
-	tokio::spawn(launch_server_image_management_thread(
-		ctx.clone(),
-		image_config,
-		connection,
-	));
+	{
+		let image_config = bot_data.config.image.clone();
+
+		supervisor.spawn(
+			"server_image",
+			every(tasks.server_image_delay.saturating_mul(2)),
+			every(tasks.server_image_update),
+			move || update_server_image(ctx.clone(), image_config.clone(), connection.clone()),
+		);
+	}
 
 	info!("Done spawning thread manager.");
 }
 
-async fn ping_manager_thread(ctx: SerenityContext, db_config: DbConfig) {
-	// Log a message indicating that the ping thread is being launched
-	info!("Launching the ping thread!");
+/// Saves the latency of every shard in the ping history. Fails while the shard manager is not
+/// set, the supervisor retries it.
 
+async fn update_ping_history(
+	ctx: SerenityContext, connection: Arc<DatabaseConnection>,
+) -> Result<()> {
 	// Get the ShardManager from the data
-	let shard_manager = match ctx
+	let shard_manager = ctx
 		.data::<BotData>()
 		.shard_manager
 		.clone()
 		.read()
 		.await
 		.clone()
-	{
-		Some(shard_manager) => shard_manager,
-		None => {
-			tokio::time::sleep(Duration::from_secs(TIME_BETWEEN_PING_UPDATE)).await;
-			Box::pin(ping_manager_thread(ctx, db_config)).await;
-			return;
-		},
-	};
-
-	// Define an interval for periodic updates
-	let mut interval = tokio::time::interval(Duration::from_secs(TIME_BETWEEN_PING_UPDATE));
-
-	// Main loop for managing pings
-	let connection = match sea_orm::Database::connect(get_url(db_config.clone())).await {
-		Ok(connection) => connection,
-		Err(e) => {
-			error!("Failed to connect to the database. {:#?}", e);
-
-			return;
-		},
-	};
-
-	loop {
-		// Wait for the next interval tick
-		interval.tick().await;
-
-		// Lock the shard manager and iterate over the runners
-		let runner = shard_manager.runners.lock().await;
-
-		for (shard_id, shard) in runner.iter() {
-			// Extract latency and current timestamp information
-			let latency = shard.latency.unwrap_or_default().as_millis().to_string();
-
-			let now = chrono::Utc::now().naive_utc();
-
-			match PingHistory::insert(ActiveModel {
-				shard_id: Set(shard_id.to_string()),
-				latency: Set(latency),
-				timestamp: Set(now),
-				..Default::default()
+		.ok_or(anyhow!("The shard manager is not ready"))?;
+
+	// Lock the shard manager and iterate over the runners
+	let runner = shard_manager.runners.lock().await;
+
+	for (shard_id, shard) in runner.iter() {
+		// Extract latency and current timestamp information
+		let latency = shard.latency.unwrap_or_default();
+
+		METRICS
+			.shard_latency
+			.get_or_create(&ShardLabels {
+				shard: shard_id.to_string(),
 			})
-			.exec(&connection)
-			.await
-			{
-				Ok(_) => {
-					debug!("Updated ping history for shard {}.", shard_id);
-				},
-				Err(e) => {
-					error!(
-						"Failed to update ping history for shard {}. {:#?}",
-						shard_id, e
-					);
-				},
-			}
+			.set(latency.as_secs_f64());
+
+		let latency = latency.as_millis().to_string();
+
+		let now = chrono::Utc::now().naive_utc();
+
+		match PingHistory::insert(ActiveModel {
+			shard_id: Set(shard_id.to_string()),
+			latency: Set(latency),
+			timestamp: Set(now),
+			..Default::default()
+		})
+		.exec(&*connection)
+		.await
+		{
+			Ok(_) => {
+				debug!("Updated ping history for shard {}.", shard_id);
+			},
+			Err(e) => {
+				error!(
+					"Failed to update ping history for shard {}. {:#?}",
+					shard_id, e
+				);
+			},
 		}
+	}
+
+	Ok(())
+}
 
-		drop(runner);
+/// Removes the expired entries of the cache backend.
+
+async fn remove_expired_cache(cache_backend: Arc<dyn CacheBackend>) -> Result<()> {
+	let removed = cache_backend.remove_expired().await?;
+
+	if removed > 0 {
+		debug!("Removed {} expired cache entries.", removed);
 	}
+
+	Ok(())
 }
 
-/// Asynchronously launches the user color management thread.
+/// Calculates the color of the members of every guild.
 ///
 /// # Arguments
 ///
 /// * `ctx` - A `Context` instance used to access the bot's data and cache.
-/// * `db_type` - A `String` representing the type of database being used.
-/// * `user_blacklist_server_image` - An `Arc` wrapped `RwLock` containing a list of strings for user blacklist and server image management.
+/// * `bot_data` - The shared bot data, with the user blacklist of the server image.
 ///
 
-async fn launch_user_color_management_thread(
-	ctx: SerenityContext, user_blacklist_server_image: Arc<RwLock<Vec<String>>>,
-	bot_data: Arc<BotData>,
-) {
-	// Create an interval for periodic updates
-	let mut interval = interval(Duration::from_secs(TIME_BETWEEN_USER_COLOR_UPDATE));
-
-	// Log a message indicating that the user color management thread is being launched
-	info!("Launching the user color management thread!");
-
-	// Enter a loop that waits for the next interval tick and triggers color management
-	loop {
-		// Wait for the next interval tick
-		interval.tick().await;
-
-		// Get the guilds from the context cache
-		let guilds = ctx.cache.guilds();
-
-		// Perform color management for the guilds
-		color_management(
-			&guilds,
-			&ctx,
-			user_blacklist_server_image.clone(),
-			bot_data.clone(),
-		)
-		.await;
-	}
+async fn update_user_color(ctx: SerenityContext, bot_data: Arc<BotData>) -> Result<()> {
+	// Get the guilds from the context cache
+	let guilds = ctx.cache.guilds();
+
+	// Perform color management for the guilds
+	color_management(
+		&guilds,
+		&ctx,
+		bot_data.user_blacklist_server_image.clone(),
+		bot_data.clone(),
+	)
+	.await;
+
+	Ok(())
 }
 
-/// Asynchronously launches the game management thread.
+/// Deletes the command usage older than the retention period from the database.
 ///
 /// # Arguments
 ///
-/// * `apps` - An `Arc` wrapped `RwLock` containing a `HashMap` of `String` keys and `u128` values.
+/// * `connection` - The shared database connection.
+/// * `retention_days` - Number of days of command usage to keep.
 ///
 
-async fn launch_game_management_thread(apps: Arc<RwLock<HashMap<String, u128>>>) {
-	// Create an interval for periodic updates
-	let mut interval = interval(Duration::from_secs(TIME_BETWEEN_GAME_UPDATE));
+async fn prune_old_command_usage(
+	connection: Arc<DatabaseConnection>, retention_days: u32,
+) -> Result<()> {
+	let older_than = Utc::now().naive_utc() - chrono::Duration::days(retention_days as i64);
 
-	// Log a message indicating that the steam management thread is being launched
-	info!("Launching the steam management thread!");
-
-	// Loop indefinitely
-	loop {
-		// Wait for the next interval tick
-		interval.tick().await;
+	let removed = prune_command_usage(&connection, older_than)
+		.await
+		.context("Failed to prune the command usage")?;
 
-		// Get the game with the provided apps and wait for the result
-		get_game(apps.clone()).await;
+	if removed != 0 {
+		debug!("Removed {} old command usage.", removed);
 	}
+
+	Ok(())
 }
 
-/// This function is responsible for launching the activity management thread.
-/// It takes a `Context` and a `String` as arguments, and does not return anything.
+/// Updates the list of the steam games.
 ///
-/// The `Context` is used to access the bot's data and cache.
-/// The `String` is the type of database being used.
+/// # Arguments
 ///
-/// The function creates an interval for periodic updates and logs a message indicating that the thread is being launched.
-/// It then enters a loop that waits for the next interval tick and spawns a new task to manage the bot's activity.
-/// The task is cloned from the `Context` and `db_type` arguments.
+/// * `apps` - An `Arc` wrapped `RwLock` containing a `HashMap` of `String` keys and `u128` values.
 ///
 
-async fn launch_activity_management_thread(
-	ctx: SerenityContext, anilist_cache: Arc<RwLock<Cache<String, String>>>, db_config: DbConfig,
-) {
-	// Create an interval for periodic updates
-	let mut interval = interval(Duration::from_secs(TIME_BETWEEN_ACTIVITY_CHECK));
-
-	// Log a message indicating that the activity management thread is being launched
-	info!("Launching the activity management thread!");
-
-	// Enter a loop that waits for the next interval tick and spawns a new task to manage the bot's activity
-	loop {
-		// Wait for the next interval tick
-		interval.tick().await;
-
-		// Clone the context and db_type arguments
-		let ctx = ctx.clone();
-
-		// Spawn a new task to manage the bot's activity
-		tokio::spawn(manage_activity(
-			ctx,
-			anilist_cache.clone(),
-			db_config.clone(),
-		));
-	}
+async fn update_game_list(apps: Arc<RwLock<HashMap<String, u128>>>) -> Result<()> {
+	// Get the game with the provided apps and wait for the result
+	get_game(apps).await;
+
+	Ok(())
 }
 
-/// This function is responsible for launching the server image management thread.
-/// It takes a `Context` as an argument.
+/// Generates the image of every server.
 ///
 /// # Arguments
 ///
 /// * `ctx` - A `Context` instance which is used in the server image management function.
 ///
 
-async fn launch_server_image_management_thread(
+async fn update_server_image(
 	ctx: SerenityContext, image_config: ImageConfig, connection: Arc<DatabaseConnection>,
-) {
-	// Log a message indicating that the server image management thread is being launched
-	info!("Launching the server image management thread!");
+) -> Result<()> {
+	// Call the server_image_management function with the provided context, database type, and image configuration
+	server_image_management(&ctx, image_config, connection).await;
 
-	// Create an interval for periodic updates
-	let mut interval = interval(Duration::from_secs(TIME_BETWEEN_SERVER_IMAGE_UPDATE));
+	Ok(())
+}
 
-	// Loop indefinitely
-	loop {
-		// Wait for the next interval tick
-		interval.tick().await;
+async fn update_random_stats_task(anilist_client: Arc<AnilistClient>) -> Result<()> {
+	update_random_stats(anilist_client)
+		.await
+		.context("Failed to update the random stats")?;
 
-		// Call the server_image_management function with the provided context, database type, and image configuration
-		server_image_management(&ctx, image_config.clone(), connection.clone()).await;
-	}
+	Ok(())
 }
 
-/// Asynchronously updates the user blacklist based on the retrieved data from a URL.
+/// Updates the user blacklist based on the retrieved data from a URL.
 ///
 /// # Arguments
 ///
 /// * `blacklist_lock` - An `Arc` wrapped `RwLock` containing the blacklist data.
 ///
 
-async fn update_user_blacklist(blacklist_lock: Arc<RwLock<Vec<String>>>) {
-	// Create an interval for periodic updates
-	let mut interval =
-		tokio::time::interval(Duration::from_secs(TIME_BETWEEN_BLACKLISTED_USER_UPDATE));
-
-	loop {
-		// Wait for the interval to tick
-		interval.tick().await;
-
-		// Fetch the blacklist data from a URL
-		let blacklist_url =
-			"https://raw.githubusercontent.com/ValgulNecron/kasuki/dev/blacklist.json";
+async fn update_user_blacklist(blacklist_lock: Arc<RwLock<Vec<String>>>) -> Result<()> {
+	// Fetch the blacklist data from a URL
+	let blacklist_url = "https://raw.githubusercontent.com/ValgulNecron/kasuki/dev/blacklist.json";
 
-		let blacklist_response = reqwest::get(blacklist_url)
-			.await
-			.expect("Failed to get blacklist");
-
-		// Parse the JSON response into a Value type
-		let blacklist_json: Value = blacklist_response
-			.json()
-			.await
-			.expect("Failed to parse blacklist");
+	let blacklist_response = reqwest::get(blacklist_url)
+		.await
+		.context("Failed to get blacklist")?;
 
-		// Extract user IDs from the JSON array
-		let user_ids: Vec<String> = match blacklist_json["user_id"].as_array() {
-			Some(arr) => arr,
-			None => {
-				error!("Failed to get user_id from blacklist");
+	// Parse the JSON response into a Value type
+	let blacklist_json: Value = blacklist_response
+		.json()
+		.await
+		.context("Failed to parse blacklist")?;
 
-				continue;
-			},
-		}
+	// Extract user IDs from the JSON array
+	let user_ids: Vec<String> = blacklist_json["user_id"]
+		.as_array()
+		.context("Failed to get user_id from blacklist")?
 		.iter()
 		.map(|id| match id.as_str() {
 			Some(id) => id.to_string(),
@@ -324,18 +389,19 @@ async fn update_user_blacklist(blacklist_lock: Arc<RwLock<Vec<String>>>) {
 		})
 		.collect();
 
-		// Write the updated blacklist to the shared data structure
-		let mut blacklist = blacklist_lock.write().await;
+	// Write the updated blacklist to the shared data structure
+	let mut blacklist = blacklist_lock.write().await;
 
-		blacklist.clear();
+	blacklist.clear();
 
-		blacklist.shrink_to_fit();
+	blacklist.shrink_to_fit();
 
-		*blacklist = user_ids;
-	}
+	*blacklist = user_ids;
+
+	Ok(())
 }
 
-/// Asynchronously updates the bot information based on the context and bot data.
+/// Updates the bot information based on the context and bot data.
 ///
 /// # Arguments
 ///
@@ -343,27 +409,18 @@ async fn update_user_blacklist(blacklist_lock: Arc<RwLock<Vec<String>>>) {
 /// * `bot_data` - An `Arc` reference to the `BotData` struct.
 ///
 
-async fn update_bot_info(context: SerenityContext, bot_data: Arc<BotData>) {
-	// Create a time interval for updating bot info
-	let mut update_interval = tokio::time::interval(Duration::from_secs(TIME_BETWEEN_BOT_INFO));
-
-	loop {
-		// Wait for the update interval
-		update_interval.tick().await;
+async fn update_bot_info(context: SerenityContext, bot_data: Arc<BotData>) -> Result<()> {
+	// Retrieve the current bot information
+	let current_bot_info = context
+		.http
+		.get_current_application_info()
+		.await
+		.context("Failed to get bot info")?;
 
-		// Retrieve the current bot information
-		let current_bot_info = match context.http.get_current_application_info().await {
-			Ok(info) => info,
-			Err(e) => {
-				error!("Failed to get bot info: {:?}", e);
+	// Acquire a lock on bot info and update it with the current information
+	let mut bot_info_lock = bot_data.bot_info.write().await;
 
-				continue;
-			},
-		};
+	*bot_info_lock = Some(current_bot_info);
 
-		// Acquire a lock on bot info and update it with the current information
-		let mut bot_info_lock = bot_data.bot_info.write().await;
-
-		*bot_info_lock = Some(current_bot_info);
-	}
+	Ok(())
 }
@@ -1,4 +1,5 @@
 pub mod activity;
 pub mod background_launcher;
 pub mod server_image;
+pub mod supervisor;
 pub mod update_random_stats;
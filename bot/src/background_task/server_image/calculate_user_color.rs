@@ -3,11 +3,9 @@ use std::io::Cursor;
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::config::DbConfig;
 use crate::database::prelude::UserColor;
 use crate::database::user_color::{ActiveModel, Column, Model};
 use crate::event_handler::{add_user_data_to_db, BotData};
-use crate::get_url;
 use crate::new_member::change_to_x128_url;
 use base64::engine::general_purpose;
 use base64::Engine;
@@ -347,7 +345,8 @@ pub async fn get_member(ctx_clone: SerenityContext, guild: GuildId) -> Vec<Membe
 }
 
 pub async fn get_specific_user_color(
-	user_blacklist_server_image: Arc<RwLock<Vec<String>>>, user: User, db_config: DbConfig,
+	user_blacklist_server_image: Arc<RwLock<Vec<String>>>, user: User,
+	connection: Arc<DatabaseConnection>,
 ) {
 	if user_blacklist_server_image
 		.read()
@@ -366,13 +365,9 @@ pub async fn get_specific_user_color(
 
 	let id = user.id.to_string();
 
-	let connection = sea_orm::Database::connect(get_url(db_config.clone()))
-		.await
-		.unwrap();
-
 	let user_color = UserColor::find()
 		.filter(Column::UserId.eq(id.clone()))
-		.one(&connection)
+		.one(&*connection)
 		.await
 		.unwrap_or(None)
 		.unwrap_or(Model {
@@ -406,7 +401,7 @@ pub async fn get_specific_user_color(
 			.update_column(Column::Images)
 			.to_owned(),
 	)
-	.exec(&connection)
+	.exec(&*connection)
 	.await
 	.unwrap();
 }
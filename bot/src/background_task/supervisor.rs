@@ -0,0 +1,251 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Instant};
+use tracing::{debug, error, info};
+
+use crate::constant::{TASK_RESTART_BASE_DELAY, TASK_RESTART_MAX_DELAY};
+use crate::metrics::{TaskLabels, METRICS};
+
+/// State of a background task, shown by `/bot tasks`.
+#[derive(Debug, Clone, Default)]
+
+pub struct TaskStatus {
+	/// Seconds between two runs.
+	pub interval: u64,
+	pub running: bool,
+	pub last_run: Option<DateTime<Utc>>,
+	pub last_duration: Option<Duration>,
+	/// Error of the last run, `None` when it succeeded.
+	pub last_error: Option<String>,
+	/// Number of runs that failed or panicked in a row.
+	pub failures: u32,
+	/// Number of times the task was restarted after a failure since the bot started.
+	pub restarts: u32,
+}
+
+/// Runs the background tasks and keeps their state. Each run of a task is spawned on its own,
+/// a run that fails or panics is logged and the task is restarted with a backoff.
+#[derive(Default)]
+
+pub struct TaskSupervisor {
+	tasks: RwLock<BTreeMap<&'static str, TaskStatus>>,
+}
+
+impl TaskSupervisor {
+	/// Runs `task` after `delay` then every `interval`. A failed run is retried after
+	/// `restart_delay` instead of the interval.
+
+	pub fn spawn<F, Fut>(
+		self: &Arc<Self>, name: &'static str, delay: Duration, interval: Duration, task: F,
+	) where
+		F: Fn() -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = Result<()>> + Send + 'static,
+	{
+		let supervisor = self.clone();
+
+		tokio::spawn(async move {
+			supervisor.tasks.write().await.insert(
+				name,
+				TaskStatus {
+					interval: interval.as_secs(),
+					..Default::default()
+				},
+			);
+
+			info!("Launching the {} task!", name);
+
+			sleep(delay).await;
+
+			loop {
+				let start = Instant::now();
+
+				let next_run = match supervisor.run_once(name, task()).await {
+					Ok(()) => interval.saturating_sub(start.elapsed()),
+					Err(failures) => restart_delay(failures),
+				};
+
+				sleep(next_run).await;
+			}
+		});
+	}
+
+	/// Runs the task once and records the run. Returns the number of failures in a row when
+	/// the run failed.
+
+	async fn run_once(
+		&self, name: &'static str, run: impl Future<Output = Result<()>> + Send + 'static,
+	) -> Result<(), u32> {
+		self.update(name, |status| {
+			status.running = true;
+
+			status.last_run = Some(Utc::now());
+		})
+		.await;
+
+		let start = Instant::now();
+
+		let result = match tokio::spawn(run).await {
+			Ok(result) => result,
+			Err(e) if e.is_panic() => Err(anyhow!("panicked: {}", panic_message(e.into_panic()))),
+			Err(e) => Err(anyhow!(e)),
+		};
+
+		let duration = start.elapsed();
+
+		debug!("The {} task ran in {:?}.", name, duration);
+
+		METRICS
+			.task_duration
+			.get_or_create(&TaskLabels { task: name })
+			.observe(duration.as_secs_f64());
+
+		if result.is_err() {
+			METRICS
+				.task_failures
+				.get_or_create(&TaskLabels { task: name })
+				.inc();
+		}
+
+		let mut failures = 0;
+
+		self.update(name, |status| {
+			status.running = false;
+
+			status.last_duration = Some(duration);
+
+			match &result {
+				Ok(()) => {
+					status.last_error = None;
+
+					status.failures = 0;
+				},
+				Err(e) => {
+					status.last_error = Some(format!("{:#}", e));
+
+					status.failures += 1;
+
+					status.restarts += 1;
+				},
+			}
+
+			failures = status.failures;
+		})
+		.await;
+
+		match result {
+			Ok(()) => Ok(()),
+			Err(e) => {
+				error!(
+					"The {} task failed, restarting it in {:?}. {:#}",
+					name,
+					restart_delay(failures),
+					e
+				);
+
+				Err(failures)
+			},
+		}
+	}
+
+	async fn update(&self, name: &'static str, update: impl FnOnce(&mut TaskStatus)) {
+		update(self.tasks.write().await.entry(name).or_default())
+	}
+
+	/// State of every task, sorted by name.
+
+	pub async fn status(&self) -> Vec<(&'static str, TaskStatus)> {
+		self.tasks
+			.read()
+			.await
+			.iter()
+			.map(|(name, status)| (*name, status.clone()))
+			.collect()
+	}
+}
+
+/// Delay before a task is restarted after `failures` failed runs in a row, doubled on each
+/// failure.
+
+fn restart_delay(failures: u32) -> Duration {
+	let delay = TASK_RESTART_BASE_DELAY.saturating_mul(1 << failures.saturating_sub(1).min(16));
+
+	Duration::from_secs(delay.min(TASK_RESTART_MAX_DELAY))
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+	match panic.downcast::<String>() {
+		Ok(message) => *message,
+		Err(panic) => match panic.downcast::<&str>() {
+			Ok(message) => message.to_string(),
+			Err(_) => "unknown panic".to_string(),
+		},
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_restart_delay() {
+		assert_eq!(
+			restart_delay(1),
+			Duration::from_secs(TASK_RESTART_BASE_DELAY)
+		);
+
+		assert_eq!(
+			restart_delay(2),
+			Duration::from_secs(TASK_RESTART_BASE_DELAY * 2)
+		);
+
+		assert_eq!(
+			restart_delay(100),
+			Duration::from_secs(TASK_RESTART_MAX_DELAY)
+		);
+	}
+
+	#[tokio::test]
+
+	async fn test_run_once() {
+		let supervisor = TaskSupervisor::default();
+
+		assert_eq!(
+			supervisor
+				.run_once("test", async { panic!("no shard") })
+				.await,
+			Err(1)
+		);
+
+		assert_eq!(
+			supervisor
+				.run_once("test", async { Err(anyhow!("timeout")) })
+				.await,
+			Err(2)
+		);
+
+		let status = supervisor.status().await;
+
+		assert_eq!(status[0].1.last_error.as_deref(), Some("timeout"));
+
+		assert_eq!(supervisor.run_once("test", async { Ok(()) }).await, Ok(()));
+
+		let (name, status) = &supervisor.status().await[0];
+
+		assert_eq!(*name, "test");
+
+		assert!(!status.running && status.last_run.is_some());
+
+		assert_eq!((status.failures, status.restarts), (0, 2));
+
+		assert_eq!(status.last_error, None);
+	}
+}
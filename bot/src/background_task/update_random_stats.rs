@@ -3,14 +3,10 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
-use tokio::time::interval;
-use tracing::info;
 
-use crate::constant::{RANDOM_STATS_PATH, TIME_BETWEEN_RANDOM_STATS_UPDATE};
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::constant::RANDOM_STATS_PATH;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::run::anilist::site_statistic_anime::{AnimeStat, AnimeStatVariables};
 use crate::structure::run::anilist::site_statistic_manga::{MangaStat, MangaStatVariables};
 
@@ -35,42 +31,17 @@ impl Default for RandomStat {
 	}
 }
 
-/// Launches a background task to update the random statistics at regular intervals.
-///
-/// # Arguments
-///
-/// * `anilist_cache` - A cache for storing Anilist API responses.
-
-pub async fn update_random_stats_launcher(anilist_cache: Arc<RwLock<Cache<String, String>>>) {
-	// Log the start of the random stats update task.
-	info!("Starting random stats update");
-
-	// Create an interval that ticks every `TIME_BETWEEN_ACTIVITY_CHECK` seconds.
-	let mut interval = interval(Duration::from_secs(TIME_BETWEEN_RANDOM_STATS_UPDATE));
-
-	// Run the update task indefinitely.
-	loop {
-		// Wait for the next tick of the interval.
-		interval.tick().await;
-
-		// Update the random statistics and ignore the result.
-		let _ = update_random_stats(anilist_cache.clone()).await;
-	}
-}
-
 /// Updates the random statistics by fetching the latest statistics from the Anilist API and saving them to a JSON file.
 ///
 /// # Arguments
 ///
-/// * `anilist_cache` - A cache for storing Anilist API responses.
+/// * `anilist_client` - The AniList client, its cache is skipped so the page counts are current.
 ///
 /// # Returns
 ///
 /// Returns the updated `RandomStat` on success, or an error on failure.
 
-pub async fn update_random_stats(
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<RandomStat> {
+pub async fn update_random_stats(anilist_client: Arc<AnilistClient>) -> Result<RandomStat> {
 	// Try to load random stats from a JSON file.
 	let mut random_stats: RandomStat = match std::fs::read_to_string(RANDOM_STATS_PATH) {
 		Ok(stats) => serde_json::from_str(&stats)?,
@@ -78,7 +49,7 @@ pub async fn update_random_stats(
 	};
 
 	// Update the random statistics.
-	random_stats = update_random(random_stats, anilist_cache).await?;
+	random_stats = update_random(random_stats, anilist_client).await?;
 
 	// Write the updated random statistics to a JSON file.
 	let random_stats_json = serde_json::to_string(&random_stats)?;
@@ -94,20 +65,20 @@ pub async fn update_random_stats(
 /// # Arguments
 ///
 /// * `random_stats` - The current random statistics.
-/// * `anilist_cache` - A cache for storing Anilist API responses.
+/// * `anilist_client` - The AniList client, its cache is skipped so the page counts are current.
 ///
 /// # Returns
 ///
 /// A `Result` containing the updated random statistics or an error.
 
 async fn update_random(
-	mut random_stats: RandomStat, anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	mut random_stats: RandomStat, anilist_client: Arc<AnilistClient>,
 ) -> Result<RandomStat> {
 	// Keep updating pages until there are no more pages to update.
 	let mut has_more_pages = true;
 
 	while has_more_pages {
-		has_more_pages = update_page(&mut random_stats, anilist_cache.clone(), true, true).await;
+		has_more_pages = update_page(&mut random_stats, anilist_client.clone(), true, true).await;
 
 		// sleep 1s
 		tokio::time::sleep(Duration::from_secs(1)).await;
@@ -116,7 +87,7 @@ async fn update_random(
 	has_more_pages = true;
 
 	while has_more_pages {
-		has_more_pages = update_page(&mut random_stats, anilist_cache.clone(), false, false).await;
+		has_more_pages = update_page(&mut random_stats, anilist_client.clone(), false, false).await;
 
 		// sleep 1s
 		tokio::time::sleep(Duration::from_secs(1)).await;
@@ -126,8 +97,8 @@ async fn update_random(
 }
 
 async fn update_page(
-	random_stats: &mut RandomStat, anilist_cache: Arc<RwLock<Cache<String, String>>>,
-	update_anime: bool, update_manga: bool,
+	random_stats: &mut RandomStat, anilist_client: Arc<AnilistClient>, update_anime: bool,
+	update_manga: bool,
 ) -> bool {
 	let data = if update_anime {
 		let var = AnimeStatVariables {
@@ -137,7 +108,7 @@ async fn update_page(
 		let operation = AnimeStat::build(var);
 
 		let data: Result<GraphQlResponse<AnimeStat>> =
-			make_request_anilist(operation, false, anilist_cache.clone()).await;
+			make_request_anilist(operation, true, anilist_client.clone()).await;
 
 		data
 	} else if update_manga {
@@ -148,7 +119,7 @@ async fn update_page(
 		let operation = MangaStat::build(var);
 
 		let data: Result<GraphQlResponse<AnimeStat>> =
-			make_request_anilist(operation, false, anilist_cache.clone()).await;
+			make_request_anilist(operation, true, anilist_client.clone()).await;
 
 		data
 	} else {
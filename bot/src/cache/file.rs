@@ -0,0 +1,211 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::cache::CacheBackend;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Cache stored on the local disk so it survives a restart without an external service.
+///
+/// Every entry is a json file in `<path>/<namespace>/`, named after the xxh3 hash of its key.
+/// The hash does not change between releases, the files of a previous build are read back.
+/// Expired entries are removed when they are read and by the `cache_sweep` task.
+
+pub struct FileCache {
+	path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+
+struct FileEntry {
+	key: String,
+	expires_at: i64,
+	value: String,
+}
+
+impl FileCache {
+	pub async fn new(path: &str) -> Result<Self> {
+		tokio::fs::create_dir_all(path)
+			.await
+			.context(format!("Failed to create the cache directory {}", path))?;
+
+		Ok(Self {
+			path: PathBuf::from(path),
+		})
+	}
+
+	fn entry_path(&self, namespace: &str, key: &str) -> PathBuf {
+		self.path
+			.join(namespace)
+			.join(format!("{:016x}.json", xxh3_64(key.as_bytes())))
+	}
+}
+
+#[async_trait]
+
+impl CacheBackend for FileCache {
+	async fn get(&self, namespace: &str, key: &str) -> Result<Option<String>> {
+		let path = self.entry_path(namespace, key);
+
+		let content = match tokio::fs::read_to_string(&path).await {
+			Ok(content) => content,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(e).context(format!("Failed to read {}", path.display())),
+		};
+
+		let entry: FileEntry = serde_json::from_str(&content)
+			.context(format!("Failed to parse {}", path.display()))?;
+
+		if entry.expires_at <= Utc::now().timestamp() {
+			tokio::fs::remove_file(&path).await.ok();
+
+			return Ok(None);
+		}
+
+		// Two keys can share a hash, the key is stored to tell them apart.
+		if entry.key != key {
+			return Ok(None);
+		}
+
+		Ok(Some(entry.value))
+	}
+
+	async fn set(&self, namespace: &str, key: &str, value: String, ttl: Duration) -> Result<()> {
+		let path = self.entry_path(namespace, key);
+
+		tokio::fs::create_dir_all(self.path.join(namespace))
+			.await
+			.context("Failed to create the cache namespace directory")?;
+
+		let entry = FileEntry {
+			key: key.to_string(),
+			expires_at: Utc::now().timestamp() + ttl.as_secs() as i64,
+			value,
+		};
+
+		// Written to a temporary file first so a reader never sees a partial entry.
+		let temp_path = path.with_extension("tmp");
+
+		tokio::fs::write(&temp_path, serde_json::to_string(&entry)?)
+			.await
+			.context(format!("Failed to write {}", temp_path.display()))?;
+
+		tokio::fs::rename(&temp_path, &path)
+			.await
+			.context(format!("Failed to write {}", path.display()))?;
+
+		Ok(())
+	}
+
+	async fn remove_expired(&self) -> Result<usize> {
+		let now = Utc::now().timestamp();
+
+		let mut removed = 0;
+
+		let mut namespaces = tokio::fs::read_dir(&self.path)
+			.await
+			.context(format!("Failed to list {}", self.path.display()))?;
+
+		while let Some(namespace) = namespaces.next_entry().await? {
+			if !namespace.file_type().await?.is_dir() {
+				continue;
+			}
+
+			let mut entries = tokio::fs::read_dir(namespace.path()).await?;
+
+			while let Some(entry) = entries.next_entry().await? {
+				let path = entry.path();
+
+				if !path
+					.extension()
+					.is_some_and(|extension| extension == "json")
+				{
+					continue;
+				}
+
+				// An entry that can't be read is removed too, it would never be a hit.
+				let expired = match tokio::fs::read_to_string(&path).await {
+					Ok(content) => !serde_json::from_str::<FileEntry>(&content)
+						.is_ok_and(|entry| entry.expires_at > now),
+					Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+					Err(_) => true,
+				};
+
+				if expired && tokio::fs::remove_file(&path).await.is_ok() {
+					removed += 1;
+				}
+			}
+		}
+
+		Ok(removed)
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+
+	async fn test_file_cache() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let cache = FileCache::new(dir.path().to_str().unwrap()).await.unwrap();
+
+		cache
+			.set(
+				"anilist",
+				"key",
+				"value".to_string(),
+				Duration::from_secs(60),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(
+			cache.get("anilist", "key").await.unwrap(),
+			Some("value".to_string())
+		);
+
+		assert_eq!(cache.get("vndb", "key").await.unwrap(), None);
+
+		cache
+			.set("vndb", "key", "value".to_string(), Duration::ZERO)
+			.await
+			.unwrap();
+
+		assert_eq!(cache.get("vndb", "key").await.unwrap(), None);
+
+		cache
+			.set("vndb", "other", "value".to_string(), Duration::ZERO)
+			.await
+			.unwrap();
+
+		assert_eq!(cache.remove_expired().await.unwrap(), 1);
+
+		// A new instance on the same directory reads the entries back.
+		let cache = FileCache::new(dir.path().to_str().unwrap()).await.unwrap();
+
+		assert_eq!(
+			cache.get("anilist", "key").await.unwrap(),
+			Some("value".to_string())
+		);
+	}
+
+	#[tokio::test]
+
+	async fn test_entry_path_is_stable() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let cache = FileCache::new(dir.path().to_str().unwrap()).await.unwrap();
+
+		// The name of an entry must not change between builds, it is how a restart finds it.
+		assert!(cache
+			.entry_path("anilist", "key")
+			.ends_with("anilist/bbea0d63a05165e3.json"));
+	}
+}
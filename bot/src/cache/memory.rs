@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use crate::cache::CacheBackend;
+use anyhow::Result;
+use async_trait::async_trait;
+use moka::future::Cache;
+use moka::Expiry;
+
+/// In memory cache, lost on restart.
+
+pub struct MemoryCache {
+	cache: Cache<String, MemoryEntry>,
+}
+
+#[derive(Clone)]
+
+struct MemoryEntry {
+	value: String,
+	ttl: Duration,
+}
+
+// Expires every entry after its own time to live.
+struct EntryExpiry;
+
+impl Expiry<String, MemoryEntry> for EntryExpiry {
+	fn expire_after_create(
+		&self, _key: &String, value: &MemoryEntry, _created_at: Instant,
+	) -> Option<Duration> {
+		Some(value.ttl)
+	}
+
+	fn expire_after_update(
+		&self, _key: &String, value: &MemoryEntry, _updated_at: Instant,
+		_duration_until_expiry: Option<Duration>,
+	) -> Option<Duration> {
+		Some(value.ttl)
+	}
+}
+
+impl MemoryCache {
+	pub fn new(max_capacity: u64) -> Self {
+		let cache = Cache::builder()
+			.max_capacity(max_capacity)
+			.expire_after(EntryExpiry)
+			.build();
+
+		Self { cache }
+	}
+}
+
+#[async_trait]
+
+impl CacheBackend for MemoryCache {
+	async fn get(&self, namespace: &str, key: &str) -> Result<Option<String>> {
+		let entry = self.cache.get(&format!("{}:{}", namespace, key)).await;
+
+		Ok(entry.map(|entry| entry.value))
+	}
+
+	async fn set(&self, namespace: &str, key: &str, value: String, ttl: Duration) -> Result<()> {
+		self.cache
+			.insert(format!("{}:{}", namespace, key), MemoryEntry { value, ttl })
+			.await;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+
+	async fn test_memory_cache() {
+		let cache = MemoryCache::new(100);
+
+		cache
+			.set(
+				"anilist",
+				"key",
+				"value".to_string(),
+				Duration::from_secs(60),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(
+			cache.get("anilist", "key").await.unwrap(),
+			Some("value".to_string())
+		);
+
+		assert_eq!(cache.get("vndb", "key").await.unwrap(), None);
+
+		cache
+			.set(
+				"vndb",
+				"key",
+				"value".to_string(),
+				Duration::from_millis(50),
+			)
+			.await
+			.unwrap();
+
+		tokio::time::sleep(Duration::from_millis(100)).await;
+
+		assert_eq!(cache.get("vndb", "key").await.unwrap(), None);
+	}
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cache::file::FileCache;
+use crate::cache::memory::MemoryCache;
+use crate::cache::redis::RedisCache;
+use crate::config::CacheConfig;
+use crate::metrics::METRICS;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+pub mod file;
+pub mod memory;
+pub mod redis;
+
+/// Storage used to cache the responses of the external APIs.
+///
+/// Every entry belongs to a namespace (`anilist`, `vndb`, ...) so the backends can share one
+/// store without key collision, and has its own time to live.
+
+#[async_trait]
+
+pub trait CacheBackend: Send + Sync {
+	async fn get(&self, namespace: &str, key: &str) -> Result<Option<String>>;
+
+	async fn set(&self, namespace: &str, key: &str, value: String, ttl: Duration) -> Result<()>;
+
+	/// Removes the expired entries and returns their number. The backends that expire their
+	/// entries by themselves keep this default.
+
+	async fn remove_expired(&self) -> Result<usize> {
+		Ok(0)
+	}
+}
+
+/// A namespace of a cache backend with the time to live set in the config.
+#[derive(Clone)]
+
+pub struct NamespacedCache {
+	backend: Arc<dyn CacheBackend>,
+	namespace: &'static str,
+	ttl: Duration,
+}
+
+impl NamespacedCache {
+	pub fn new(backend: Arc<dyn CacheBackend>, namespace: &'static str, ttl: Duration) -> Self {
+		Self {
+			backend,
+			namespace,
+			ttl,
+		}
+	}
+
+	/// Returns the cached value, a backend error is logged and treated as a miss so an
+	/// unavailable cache never fails a request.
+
+	pub async fn get(&self, key: &str) -> Option<String> {
+		let value = match self.backend.get(self.namespace, key).await {
+			Ok(value) => value,
+			Err(e) => {
+				warn!("Failed to read the {} cache. {:#}", self.namespace, e);
+
+				None
+			},
+		};
+
+		METRICS.record_cache_request(self.namespace, value.is_some());
+
+		value
+	}
+
+	pub async fn set(&self, key: &str, value: String) {
+		if let Err(e) = self.backend.set(self.namespace, key, value, self.ttl).await {
+			warn!("Failed to write the {} cache. {:#}", self.namespace, e);
+		}
+	}
+}
+
+/// Creates the backend selected by `backend` in the `[cache]` config section.
+
+pub async fn create_cache_backend(config: &CacheConfig) -> Result<Arc<dyn CacheBackend>> {
+	info!("Using the {} cache backend.", config.backend);
+
+	let backend: Arc<dyn CacheBackend> = match config.backend.as_str() {
+		"memory" => Arc::new(MemoryCache::new(config.max_capacity)),
+		"redis" => Arc::new(RedisCache::new(&config.redis_url).await?),
+		"file" => Arc::new(FileCache::new(&config.file_path).await?),
+		backend => {
+			return Err(anyhow!(
+				"Unknown cache backend {}, expected memory, redis or file",
+				backend
+			))
+		},
+	};
+
+	Ok(backend)
+}
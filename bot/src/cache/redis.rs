@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use crate::cache::CacheBackend;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+
+/// Cache stored in a redis server, the expiration is handled by redis.
+
+pub struct RedisCache {
+	connection: ConnectionManager,
+}
+
+impl RedisCache {
+	pub async fn new(url: &str) -> Result<Self> {
+		let client = redis::Client::open(url).context(format!("Invalid redis url {}", url))?;
+
+		// The connection manager reconnects by itself when the connection is lost.
+		let connection = ConnectionManager::new(client)
+			.await
+			.context(format!("Failed to connect to redis at {}", url))?;
+
+		Ok(Self { connection })
+	}
+}
+
+#[async_trait]
+
+impl CacheBackend for RedisCache {
+	async fn get(&self, namespace: &str, key: &str) -> Result<Option<String>> {
+		let mut connection = self.connection.clone();
+
+		let value: Option<String> = connection
+			.get(format!("kasuki:{}:{}", namespace, key))
+			.await
+			.context("Failed to get the value from redis")?;
+
+		Ok(value)
+	}
+
+	async fn set(&self, namespace: &str, key: &str, value: String, ttl: Duration) -> Result<()> {
+		let mut connection = self.connection.clone();
+
+		let _: () = connection
+			.set_ex(
+				format!("kasuki:{}:{}", namespace, key),
+				value,
+				ttl.as_secs().max(1),
+			)
+			.await
+			.context("Failed to set the value in redis")?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	// Needs a local redis server, run with `cargo test -- --ignored`.
+	// The server can be changed with the KASUKI_TEST_REDIS_URL environment variable.
+	#[tokio::test]
+	#[ignore]
+
+	async fn test_redis_cache() {
+		let url = std::env::var("KASUKI_TEST_REDIS_URL")
+			.unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+
+		let cache = RedisCache::new(&url).await.unwrap();
+
+		cache
+			.set("test", "key", "value".to_string(), Duration::from_secs(60))
+			.await
+			.unwrap();
+
+		assert_eq!(
+			cache.get("test", "key").await.unwrap(),
+			Some("value".to_string())
+		);
+
+		assert_eq!(cache.get("test", "missing").await.unwrap(), None);
+
+		cache
+			.set(
+				"test",
+				"expired",
+				"value".to_string(),
+				Duration::from_secs(1),
+			)
+			.await
+			.unwrap();
+
+		tokio::time::sleep(Duration::from_millis(1_500)).await;
+
+		assert_eq!(cache.get("test", "expired").await.unwrap(), None);
+	}
+}
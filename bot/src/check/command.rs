@@ -0,0 +1,411 @@
+use std::collections::HashSet;
+
+use crate::register::function::register_command::get_commands;
+use crate::register::function::register_guild_specific_command::get_commands as get_guild_commands;
+use crate::register::function::register_message_command::get_message_command;
+use crate::register::function::register_subcommand::get_subcommands;
+use crate::register::function::register_subcommand_group::get_subcommands_group;
+use crate::register::function::register_user_command::get_user_command;
+use crate::register::structure::common::{Arg, Localised};
+use crate::register::structure::subcommand::Command as SubCommandCommand;
+use crate::register::structure::subcommand_group::SubCommand;
+
+// Limits from https://discord.com/developers/docs/interactions/application-commands
+const MAX_NAME_LENGTH: usize = 32;
+
+const MAX_DESCRIPTION_LENGTH: usize = 100;
+
+const MAX_CHOICE_LENGTH: usize = 100;
+
+const MAX_OPTIONS: usize = 25;
+
+const MAX_CHOICES: usize = 25;
+
+/// Locales accepted by Discord for the localized names and descriptions.
+
+const DISCORD_LOCALES: [&str; 32] = [
+	"id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu", "nl",
+	"no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th",
+	"zh-CN", "ja", "zh-TW", "ko",
+];
+
+/// Loads every command definition with the structs used for the registration and checks
+/// them against the rules of Discord.
+
+pub fn check_commands() -> Vec<String> {
+	let mut problems = Vec::new();
+
+	match get_commands("./json/command") {
+		Ok(commands) => {
+			for command in commands {
+				let location = format!("/{}", command.name);
+
+				check_name(&mut problems, &location, &command.name);
+
+				check_description(&mut problems, &location, &command.desc);
+
+				check_localised(&mut problems, &location, &command.localised);
+
+				check_args(&mut problems, &location, &command.args);
+			}
+		},
+		Err(e) => problems.push(format!("json/command: {:#}", e)),
+	}
+
+	match get_guild_commands("./json/guild_command") {
+		Ok(commands) => {
+			for command in commands {
+				let location = format!("/{} (guild {})", command.name, command.guild_id);
+
+				check_name(&mut problems, &location, &command.name);
+
+				check_description(&mut problems, &location, &command.desc);
+
+				check_localised(&mut problems, &location, &command.localised);
+
+				check_args(&mut problems, &location, &command.args);
+			}
+		},
+		Err(e) => problems.push(format!("json/guild_command: {:#}", e)),
+	}
+
+	match get_subcommands("./json/subcommand") {
+		Ok(commands) => {
+			for command in commands {
+				let location = format!("/{}", command.name);
+
+				check_name(&mut problems, &location, &command.name);
+
+				check_description(&mut problems, &location, &command.desc);
+
+				check_localised(&mut problems, &location, &command.localised);
+
+				check_subcommands(&mut problems, &location, &command.command, 0);
+			}
+		},
+		Err(e) => problems.push(format!("json/subcommand: {:#}", e)),
+	}
+
+	match get_subcommands_group("./json/subcommand_group") {
+		Ok(commands) => {
+			for command in commands {
+				let location = format!("/{}", command.name);
+
+				check_name(&mut problems, &location, &command.name);
+
+				check_description(&mut problems, &location, &command.desc);
+
+				check_localised(&mut problems, &location, &command.localised);
+
+				let groups = command.subcommands.as_ref().map_or(0, Vec::len);
+
+				check_subcommands(&mut problems, &location, &command.command, groups);
+
+				check_groups(&mut problems, &location, &command.subcommands);
+			}
+		},
+		Err(e) => problems.push(format!("json/subcommand_group: {:#}", e)),
+	}
+
+	match get_user_command("./json/user_command") {
+		Ok(commands) => {
+			for command in commands {
+				let localised = command.localised.iter().flatten();
+
+				check_context_menu(
+					&mut problems,
+					&command.name,
+					localised.map(|l| (l.code.as_str(), l.name.as_str())),
+				);
+			}
+		},
+		Err(e) => problems.push(format!("json/user_command: {:#}", e)),
+	}
+
+	match get_message_command("./json/message_command") {
+		Ok(commands) => {
+			for command in commands {
+				let localised = command.localised.iter().flatten();
+
+				check_context_menu(
+					&mut problems,
+					&command.name,
+					localised.map(|l| (l.code.as_str(), l.name.as_str())),
+				);
+			}
+		},
+		Err(e) => problems.push(format!("json/message_command: {:#}", e)),
+	}
+
+	problems
+}
+
+/// User and message commands are shown in the context menu, their name can contain spaces
+/// and capital letters and they have no description.
+
+fn check_context_menu<'a>(
+	problems: &mut Vec<String>, name: &str, localised: impl Iterator<Item = (&'a str, &'a str)>,
+) {
+	let location = format!("context menu {}", name);
+
+	check_length(problems, &location, "name", name, MAX_NAME_LENGTH);
+
+	for (code, name) in localised {
+		let location = format!("{} ({})", location, code);
+
+		check_locale(problems, &location, code);
+
+		check_length(problems, &location, "name", name, MAX_NAME_LENGTH);
+	}
+}
+
+fn check_groups(problems: &mut Vec<String>, location: &str, groups: &Option<Vec<SubCommand>>) {
+	for group in groups.iter().flatten() {
+		let location = format!("{} {}", location, group.name);
+
+		check_name(problems, &location, &group.name);
+
+		check_description(problems, &location, &group.desc);
+
+		check_localised(problems, &location, &group.localised);
+
+		check_subcommands(problems, &location, &group.command, 0);
+	}
+}
+
+/// `siblings` is the number of other options next to the subcommands, like the subcommand
+/// groups of a command.
+
+fn check_subcommands(
+	problems: &mut Vec<String>, location: &str, commands: &Option<Vec<SubCommandCommand>>,
+	siblings: usize,
+) {
+	let commands = commands.as_deref().unwrap_or_default();
+
+	if commands.len() + siblings > MAX_OPTIONS {
+		problems.push(format!(
+			"{}: {} subcommands, the maximum is {}",
+			location,
+			commands.len() + siblings,
+			MAX_OPTIONS
+		));
+	}
+
+	check_duplicates(problems, location, commands.iter().map(|c| c.name.as_str()));
+
+	for command in commands {
+		let location = format!("{} {}", location, command.name);
+
+		check_name(problems, &location, &command.name);
+
+		check_description(problems, &location, &command.desc);
+
+		check_localised(problems, &location, &command.localised);
+
+		check_args(problems, &location, &command.args);
+	}
+}
+
+fn check_args(problems: &mut Vec<String>, location: &str, args: &Option<Vec<Arg>>) {
+	let args = args.as_deref().unwrap_or_default();
+
+	if args.len() > MAX_OPTIONS {
+		problems.push(format!(
+			"{}: {} options, the maximum is {}",
+			location,
+			args.len(),
+			MAX_OPTIONS
+		));
+	}
+
+	check_duplicates(problems, location, args.iter().map(|arg| arg.name.as_str()));
+
+	let mut optional_found = false;
+
+	for arg in args {
+		let location = format!("{} [{}]", location, arg.name);
+
+		check_name(problems, &location, &arg.name);
+
+		check_description(problems, &location, &arg.desc);
+
+		check_localised(problems, &location, &arg.localised);
+
+		if arg.required && optional_found {
+			problems.push(format!(
+				"{}: required options must be placed before the optional ones",
+				location
+			));
+		}
+
+		optional_found |= !arg.required;
+
+		let choices = arg.choices.as_deref().unwrap_or_default();
+
+		if choices.len() > MAX_CHOICES {
+			problems.push(format!(
+				"{}: {} choices, the maximum is {}",
+				location,
+				choices.len(),
+				MAX_CHOICES
+			));
+		}
+
+		for choice in choices {
+			let location = format!("{} choice {}", location, choice.option_choice);
+
+			check_length(
+				problems,
+				&location,
+				"choice",
+				&choice.option_choice,
+				MAX_CHOICE_LENGTH,
+			);
+
+			for localised in choice.option_choice_localised.iter().flatten() {
+				let location = format!("{} ({})", location, localised.code);
+
+				check_locale(problems, &location, &localised.code);
+
+				check_length(
+					problems,
+					&location,
+					"choice",
+					&localised.name,
+					MAX_CHOICE_LENGTH,
+				);
+			}
+		}
+	}
+}
+
+fn check_localised(problems: &mut Vec<String>, location: &str, localised: &Option<Vec<Localised>>) {
+	for localised in localised.iter().flatten() {
+		let location = format!("{} ({})", location, localised.code);
+
+		check_locale(problems, &location, &localised.code);
+
+		check_name(problems, &location, &localised.name);
+
+		check_description(problems, &location, &localised.desc);
+	}
+}
+
+fn check_locale(problems: &mut Vec<String>, location: &str, code: &str) {
+	if !DISCORD_LOCALES.contains(&code) {
+		problems.push(format!(
+			"{}: \"{}\" is not a Discord locale",
+			location, code
+		));
+	}
+}
+
+/// Names of chat input commands and options must be lowercase and only contain letters,
+/// numbers, `-` and `_`.
+
+fn check_name(problems: &mut Vec<String>, location: &str, name: &str) {
+	check_length(problems, location, "name", name, MAX_NAME_LENGTH);
+
+	if !name
+		.chars()
+		.all(|c| c == '-' || c == '_' || c.is_alphanumeric())
+	{
+		problems.push(format!(
+			"{}: the name \"{}\" can only contain letters, numbers, - and _",
+			location, name
+		));
+	}
+
+	if name.to_lowercase() != name {
+		problems.push(format!(
+			"{}: the name \"{}\" must be lowercase",
+			location, name
+		));
+	}
+}
+
+fn check_description(problems: &mut Vec<String>, location: &str, description: &str) {
+	check_length(
+		problems,
+		location,
+		"description",
+		description,
+		MAX_DESCRIPTION_LENGTH,
+	);
+}
+
+fn check_length(problems: &mut Vec<String>, location: &str, field: &str, value: &str, max: usize) {
+	let length = value.chars().count();
+
+	if length == 0 || length > max {
+		problems.push(format!(
+			"{}: the {} must be between 1 and {} characters, it has {}",
+			location, field, max, length
+		));
+	}
+}
+
+fn check_duplicates<'a>(
+	problems: &mut Vec<String>, location: &str, names: impl Iterator<Item = &'a str>,
+) {
+	let mut seen = HashSet::new();
+
+	for name in names {
+		if !seen.insert(name) {
+			problems.push(format!("{}: \"{}\" is defined twice", location, name));
+		}
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use crate::register::structure::common::RemoteCommandOptionType;
+
+	fn arg(name: &str, required: bool) -> Arg {
+		Arg {
+			name: name.to_string(),
+			desc: "description".to_string(),
+			arg_type: RemoteCommandOptionType::String,
+			required,
+			autocomplete: false,
+			choices: None,
+			localised: None,
+		}
+	}
+
+	#[test]
+
+	fn test_commands_are_valid() {
+		let problems = check_commands();
+
+		assert!(problems.is_empty(), "{}", problems.join("\n"));
+	}
+
+	#[test]
+
+	fn test_check_args() {
+		let mut problems = Vec::new();
+
+		check_args(
+			&mut problems,
+			"/test",
+			&Some(vec![arg("name", true), arg("type", false)]),
+		);
+
+		assert!(problems.is_empty());
+
+		check_args(
+			&mut problems,
+			"/test",
+			&Some(vec![
+				arg("type", false),
+				arg("Name", true),
+				arg("type", false),
+			]),
+		);
+
+		assert_eq!(problems.len(), 3);
+	}
+}
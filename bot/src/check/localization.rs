@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::helper::read_file::read_file_as_string;
+use crate::structure::message::localised::{Localised, LOCALISED};
+use serde_json::{Map, Value};
+
+/// Language codes used in the `json/message` files, see `normalize_lang`.
+
+const LANGUAGES: [&str; 4] = ["en", "fr", "jp", "de"];
+
+type Parse = fn(Value) -> serde_json::Result<Localised>;
+
+/// Checks the language codes of every file under `path` and that every language can be
+/// deserialized into the struct used by the bot.
+
+pub fn check_localization(path: &str) -> Vec<String> {
+	let mut problems = Vec::new();
+
+	let mut files = Vec::new();
+
+	if let Err(e) = find_json_files(Path::new(path), &mut files) {
+		problems.push(format!("{}: failed to list the files. {:#}", path, e));
+
+		return problems;
+	}
+
+	let known: HashSet<&str> = LOCALISED.iter().map(|localised| localised.path).collect();
+
+	for file in &files {
+		if !known.contains(file.as_str()) {
+			problems.push(format!(
+				"{}: no localised struct is checked for this file",
+				file
+			));
+		}
+	}
+
+	for localised in LOCALISED {
+		if !files.iter().any(|path| path == localised.path) {
+			problems.push(format!("{}: the file does not exist", localised.path));
+
+			continue;
+		}
+
+		problems.extend(check_file(localised.path, localised.parse));
+	}
+
+	problems
+}
+
+fn check_file(file: &str, parse: Parse) -> Vec<String> {
+	let languages: Map<String, Value> = match read_file_as_string(file)
+		.and_then(|content| serde_json::from_str(&content).map_err(Into::into))
+	{
+		Ok(languages) => languages,
+		Err(e) => return vec![format!("{}: {:#}", file, e)],
+	};
+
+	let mut problems = Vec::new();
+
+	for lang in languages.keys() {
+		if !LANGUAGES.contains(&lang.as_str()) {
+			let hint = match lang.as_str() {
+				"ja" => ", use \"jp\"".to_string(),
+				lang if lang.contains('-') => {
+					format!(", use \"{}\"", lang.split('-').next().unwrap_or(lang))
+				},
+				_ => String::new(),
+			};
+
+			problems.push(format!(
+				"{}: unknown language code \"{}\"{}",
+				file, lang, hint
+			));
+		}
+	}
+
+	let english = match languages.get("en").and_then(Value::as_object) {
+		Some(english) => english,
+		None => {
+			problems.push(format!("{}: missing the language en", file));
+
+			return problems;
+		},
+	};
+
+	for lang in LANGUAGES {
+		match languages.get(lang) {
+			Some(Value::Object(language)) => {
+				for problem in missing_keys(parse, language, english) {
+					problems.push(format!("{}: {}: {}", file, lang, problem));
+				}
+			},
+			Some(_) => problems.push(format!("{}: {}: expected an object", file, lang)),
+			None => problems.push(format!("{}: missing the language {}", file, lang)),
+		}
+	}
+
+	problems
+}
+
+/// Deserializes `language` with `parse` and returns every key that is missing or invalid.
+
+fn missing_keys(
+	parse: Parse, language: &Map<String, Value>, english: &Map<String, Value>,
+) -> Vec<String> {
+	let mut language = language.clone();
+
+	let mut problems = Vec::new();
+
+	loop {
+		let error = match parse(Value::Object(language.clone())) {
+			Ok(_) => return problems,
+			Err(e) => e.to_string(),
+		};
+
+		// serde stops at the first missing field, it is filled from English to find the next one.
+		let key = error
+			.strip_prefix("missing field `")
+			.and_then(|rest| rest.split('`').next())
+			.filter(|key| !language.contains_key(*key))
+			.map(str::to_string);
+
+		match key.and_then(|key| english.get(&key).map(|value| (key, value.clone()))) {
+			Some((key, value)) => {
+				problems.push(format!("missing the key {}", key));
+
+				language.insert(key, value);
+			},
+			None => {
+				problems.push(error);
+
+				return problems;
+			},
+		}
+	}
+}
+
+fn find_json_files(path: &Path, files: &mut Vec<String>) -> anyhow::Result<()> {
+	for entry in std::fs::read_dir(path)? {
+		let path = entry?.path();
+
+		if path.is_dir() {
+			find_json_files(&path, files)?;
+		} else if path
+			.extension()
+			.is_some_and(|extension| extension == "json")
+		{
+			files.push(path.to_string_lossy().replace('\\', "/"));
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use crate::constant::LOCALIZATION_PATH;
+	use serde_json::json;
+
+	#[test]
+
+	fn test_localization_matches_the_structs() {
+		let problems = check_localization(LOCALIZATION_PATH);
+
+		assert!(problems.is_empty(), "{}", problems.join("\n"));
+	}
+
+	#[test]
+
+	fn test_missing_keys() {
+		let english = json!({
+			"title": "Error", "user_input": "", "not_found": "", "upstream_api": "",
+			"permission": "", "premium_limit": "", "internal": "", "code": "", "reference": ""
+		});
+
+		let language = json!({"title": "Erreur", "user_input": "", "not_found": ""});
+
+		let error = LOCALISED
+			.iter()
+			.find(|localised| localised.path == "json/message/error/error.json")
+			.unwrap();
+
+		let problems = missing_keys(
+			error.parse,
+			language.as_object().unwrap(),
+			english.as_object().unwrap(),
+		);
+
+		assert_eq!(problems.len(), 6);
+
+		assert_eq!(problems[0], "missing the key upstream_api");
+	}
+}
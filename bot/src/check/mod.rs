@@ -0,0 +1,33 @@
+mod command;
+mod localization;
+
+use crate::constant::LOCALIZATION_PATH;
+
+/// Exit code of `kasuki check` when a file is invalid. 1 is taken by an invalid config, the
+/// CI can tell a broken json file from a broken environment.
+
+pub const CHECK_FAILED_EXIT_CODE: i32 = 11;
+
+/// `kasuki check`: validates the json files read by the bot without a config or a connection,
+/// so broken translations and command definitions fail the CI instead of the bot.
+/// Prints every problem found and returns the exit code of the process.
+
+pub fn run_check() -> i32 {
+	let mut problems = command::check_commands();
+
+	problems.extend(localization::check_localization(LOCALIZATION_PATH));
+
+	if problems.is_empty() {
+		println!("Every command and localization file is valid.");
+
+		return 0;
+	}
+
+	for problem in &problems {
+		eprintln!("{}", problem);
+	}
+
+	eprintln!("{} problems found.", problems.len());
+
+	CHECK_FAILED_EXIT_CODE
+}
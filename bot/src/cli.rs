@@ -0,0 +1,273 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::background_task::update_random_stats::update_random_stats;
+use crate::cache::{create_cache_backend, NamespacedCache};
+use crate::command::anilist_user::anime::get_anime_by_id;
+use crate::command::command_dispatch::check_kill_switch_status;
+use crate::config::Config;
+use crate::constant::{COMMAND_USE_PATH, DEFAULT_CONFIG_PATH};
+use crate::database::prelude::ActivityData;
+use crate::helper::command_usage::{export_legacy_command_usage, import_legacy_command_usage};
+use crate::helper::make_graphql_cached::AnilistClient;
+use crate::register::registration_dispatcher::command_registration;
+use crate::{connect_db, init_db, run_migrations};
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::EntityTrait;
+use serenity::http::Http;
+use serenity::secrets::Token;
+use tracing::{error, info, warn};
+
+/// Kasuki discord bot. Without a command the bot is started.
+#[derive(Parser)]
+#[command(version, about)]
+
+pub struct Cli {
+	/// Path of the config file, its fields can be overridden by the KASUKI_* environment
+	/// variables, for example KASUKI_BOT_DISCORD_TOKEN.
+	#[arg(long, global = true, env = "KASUKI_CONFIG", default_value = DEFAULT_CONFIG_PATH)]
+	pub config: String,
+	#[command(subcommand)]
+	pub command: Option<CliCommand>,
+}
+
+#[derive(Subcommand)]
+
+pub enum CliCommand {
+	/// Start the bot, the default.
+	Run,
+	/// Validate the command and localization json files, does not need a config. Exits with 11
+	/// when a file is invalid.
+	Check,
+	/// Apply, revert or list the database migrations.
+	Migrate {
+		#[command(subcommand)]
+		action: MigrateAction,
+	},
+	/// Create, update or delete the commands registered on Discord to match the json files.
+	#[command(alias = "register")]
+	RegisterCommands {
+		/// Only print the planned changes.
+		#[arg(long)]
+		dry_run: bool,
+	},
+	/// Write the command usage of the database to a file in the format of command_use.json.
+	ExportUsage {
+		#[arg(default_value = COMMAND_USE_PATH)]
+		path: String,
+	},
+	/// Import a command_use.json file into the database, the file is renamed once imported.
+	ImportUsage {
+		#[arg(default_value = COMMAND_USE_PATH)]
+		path: String,
+	},
+	/// Validate the config, including the AI settings when the AI module is enabled.
+	CheckConfig,
+	/// Fill the AniList cache with the data of the tracked anime and the random stats.
+	CacheWarm,
+}
+
+#[derive(Subcommand)]
+
+pub enum MigrateAction {
+	/// Apply every pending migration.
+	Up,
+	/// Revert the last applied migrations.
+	Down {
+		#[arg(long, default_value_t = 1)]
+		steps: u32,
+	},
+	/// List the applied and pending migrations.
+	Status,
+}
+
+/// Runs a command other than `run` and `check` and returns the exit code of the process.
+
+pub async fn run_operator_command(command: CliCommand, config: Arc<Config>) -> i32 {
+	let (result, error_code) = match command {
+		CliCommand::Run | CliCommand::Check => unreachable!("run and check are handled by main"),
+		CliCommand::Migrate { action } => (migrate(action, config).await, 4),
+		CliCommand::RegisterCommands { dry_run } => (register_commands(config, dry_run).await, 10),
+		CliCommand::ExportUsage { path } => (export_usage(config, &path).await, 12),
+		CliCommand::ImportUsage { path } => (import_usage(config, &path).await, 12),
+		CliCommand::CheckConfig => (check_config(config).await, 1),
+		CliCommand::CacheWarm => (cache_warm(config).await, 12),
+	};
+
+	match result {
+		Ok(()) => 0,
+		Err(e) => {
+			error!("{:#}", e);
+
+			error_code
+		},
+	}
+}
+
+async fn migrate(action: MigrateAction, config: Arc<Config>) -> Result<()> {
+	let connection = connect_db(config).await?;
+
+	match action {
+		MigrateAction::Up => run_migrations(&connection).await,
+		MigrateAction::Down { steps } => {
+			Migrator::down(&connection, Some(steps))
+				.await
+				.context("Failed to revert the migrations")?;
+
+			info!("Reverted {} migration(s).", steps);
+
+			Ok(())
+		},
+		MigrateAction::Status => {
+			let applied = Migrator::get_applied_migrations(&connection)
+				.await
+				.context("Failed to get the applied migrations")?;
+
+			let pending = Migrator::get_pending_migrations(&connection)
+				.await
+				.context("Failed to get the pending migrations")?;
+
+			for migration in applied {
+				println!("applied  {}", migration.name());
+			}
+
+			for migration in pending {
+				println!("pending  {}", migration.name());
+			}
+
+			Ok(())
+		},
+	}
+}
+
+/// Registers the commands without starting the gateway client.
+
+async fn register_commands(config: Arc<Config>, dry_run: bool) -> Result<()> {
+	let token = Token::from_str(&config.bot.discord_token).context("Failed to get the token")?;
+
+	let http = Arc::new(Http::new(token));
+
+	// The global commands are registered for the application of the token.
+	let application = http
+		.get_current_application_info()
+		.await
+		.context("Failed to get the application info")?;
+
+	http.set_application_id(application.id);
+
+	let changes = command_registration(&http, config.bot.remove_old_commands, dry_run).await?;
+
+	if changes.is_empty() {
+		println!("Every command is up to date.");
+	}
+
+	for change in changes {
+		println!("{}", change);
+	}
+
+	Ok(())
+}
+
+async fn export_usage(config: Arc<Config>, path: &str) -> Result<()> {
+	let connection = init_db(config).await?;
+
+	let exported = export_legacy_command_usage(&connection, path).await?;
+
+	println!("Exported {} command use to {}.", exported, path);
+
+	Ok(())
+}
+
+async fn import_usage(config: Arc<Config>, path: &str) -> Result<()> {
+	let connection = init_db(config).await?;
+
+	let imported = import_legacy_command_usage(&connection, path).await?;
+
+	println!("Imported {} command use from {}.", imported, path);
+
+	Ok(())
+}
+
+async fn check_config(config: Arc<Config>) -> Result<()> {
+	let mut problems = Vec::new();
+
+	// The AI module is enabled unless the global kill switch disabled it. The database is
+	// only reached when its config is valid, `get_url` exits otherwise.
+	let ai_enabled = if config.db.validate().is_empty() {
+		match connect_db(config.clone()).await {
+			Ok(connection) => check_kill_switch_status("AI", Arc::new(connection))
+				.await
+				.unwrap_or(true),
+			Err(e) => {
+				problems.push(format!("{:#}", e));
+
+				true
+			},
+		}
+	} else {
+		true
+	};
+
+	problems.extend(config.validate(ai_enabled));
+
+	if problems.is_empty() {
+		println!("The config is valid.");
+
+		return Ok(());
+	}
+
+	for problem in &problems {
+		eprintln!("{}", problem);
+	}
+
+	Err(anyhow!("{} problems found in the config", problems.len()))
+}
+
+async fn cache_warm(config: Arc<Config>) -> Result<()> {
+	if config.cache.backend == "memory" {
+		return Err(anyhow!(
+			"The memory cache is lost when the process exits, use the redis or file backend"
+		));
+	}
+
+	let connection = init_db(config.clone()).await?;
+
+	let cache_backend = create_cache_backend(&config.cache).await?;
+
+	let anilist_cache = NamespacedCache::new(
+		cache_backend,
+		"anilist",
+		Duration::from_secs(config.cache.ttl.anilist),
+	);
+
+	let anilist_client = Arc::new(AnilistClient::new(reqwest::Client::new(), anilist_cache));
+
+	update_random_stats(anilist_client.clone())
+		.await
+		.context("Failed to update the random stats")?;
+
+	let anime_ids: HashSet<i32> = ActivityData::find()
+		.all(&connection)
+		.await
+		.context("Failed to get the activities")?
+		.into_iter()
+		.map(|activity| activity.anime_id)
+		.collect();
+
+	let mut warmed = 0;
+
+	for anime_id in anime_ids {
+		match get_anime_by_id(anime_id, anilist_client.clone(), true).await {
+			Ok(_) => warmed += 1,
+			Err(e) => warn!("Failed to get the anime {}. {:#}", anime_id, e),
+		}
+	}
+
+	println!("Cached the random stats and {} anime.", warmed);
+
+	Ok(())
+}
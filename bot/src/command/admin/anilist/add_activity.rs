@@ -2,16 +2,17 @@ use anyhow::{anyhow, Result};
 use std::io::{Cursor, Read};
 use std::sync::Arc;
 
+use crate::background_task::activity::scheduler::ScheduledActivity;
 use crate::command::command_trait::Embed;
 use crate::command::command_trait::{Command, EmbedType, SlashCommand};
-use crate::config::DbConfig;
+use crate::constant::ACTIVITY_KIND_ANIME;
 use crate::database::activity_data;
 use crate::database::activity_data::Column;
 use crate::database::prelude::ActivityData;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::get_option::subcommand_group::get_option_map_string_subcommand_group;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::helper::trimer::trim_webhook;
 use crate::structure::message::admin::anilist::add_activity::load_localization_add_activity;
 use crate::structure::run::anilist::minimal_anime::{
@@ -25,18 +26,17 @@ use chrono::Utc;
 use cynic::{GraphQlResponse, QueryBuilder};
 use image::imageops::FilterType;
 use image::{guess_format, GenericImageView, ImageFormat};
-use moka::future::Cache;
 use prost::bytes::Bytes;
 use reqwest::get;
 use sea_orm::ActiveValue::Set;
 use sea_orm::ColumnTrait;
+use sea_orm::DatabaseConnection;
 use sea_orm::EntityTrait;
 use sea_orm::QueryFilter;
 use serde_json::json;
 use serenity::all::{
 	ChannelId, CommandInteraction, Context as SerenityContext, CreateAttachment, EditWebhook,
 };
-use tokio::sync::RwLock;
 use tracing::trace;
 
 pub struct AddActivityCommand {
@@ -60,9 +60,9 @@ impl SlashCommand for AddActivityCommand {
 
 		let ctx = self.ctx.clone();
 		let bot_data = ctx.data::<BotData>().clone();
-		let anilist_cache = bot_data.anilist_cache.clone();
+		let anilist_client = bot_data.anilist_client.clone();
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
 		let map = get_option_map_string_subcommand_group(&command_interaction);
 
@@ -71,7 +71,7 @@ impl SlashCommand for AddActivityCommand {
 			.cloned()
 			.unwrap_or(String::new());
 
-		let media = get_minimal_anime_media(anime.to_string(), anilist_cache).await?;
+		let media = get_minimal_anime_media(anime.to_string(), anilist_client).await?;
 
 		let guild_id = match command_interaction.guild_id {
 			Some(id) => id.to_string(),
@@ -80,12 +80,17 @@ impl SlashCommand for AddActivityCommand {
 
 		trace!(?guild_id);
 
-		let add_activity_localised =
-			load_localization_add_activity(guild_id.clone(), config.db.clone()).await?;
+		let add_activity_localised = load_localization_add_activity(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			db_connection.clone(),
+		)
+		.await?;
 
 		let anime_id = media.id;
 
-		let exist = check_if_activity_exist(anime_id, guild_id.clone(), config.db.clone()).await;
+		let exist =
+			check_if_activity_exist(anime_id, guild_id.clone(), db_connection.clone()).await;
 
 		self.defer().await?;
 
@@ -142,10 +147,13 @@ impl SlashCommand for AddActivityCommand {
 
 			let image = format!("data:image/jpeg;base64,{}", base64);
 
-			let next_airing = media.next_airing_episode.clone().ok_or(anyhow!(format!(
-				"No next episode found for {} on anilist",
-				anime_name
-			)))?;
+			let next_airing = media
+				.next_airing_episode
+				.clone()
+				.ok_or(KasukiError::NotFound(format!(
+					"No next episode found for {} on anilist",
+					anime_name
+				)))?;
 
 			let webhook = get_webhook(
 				&ctx,
@@ -167,16 +175,24 @@ impl SlashCommand for AddActivityCommand {
 			ActivityData::insert(activity_data::ActiveModel {
 				anime_id: Set(media.id),
 				timestamp: Set(chrono),
-				server_id: Set(guild_id),
+				server_id: Set(guild_id.clone()),
 				webhook: Set(webhook),
 				episode: Set(next_airing.episode),
 				name: Set(trimmed_anime_name),
 				delay: Set(delay),
 				image: Set(image),
+				media_kind: Set(ACTIVITY_KIND_ANIME.to_string()),
+				external_id: Set(None),
+				sent_episode: Set(None),
+				last_chapter: Set(None),
 			})
 			.exec(&*connection)
 			.await?;
 
+			bot_data
+				.activity_scheduler
+				.schedule(ScheduledActivity::new(media.id, guild_id, chrono, delay));
+
 			self.send_embed(
 				Vec::new(),
 				None,
@@ -197,7 +213,7 @@ impl SlashCommand for AddActivityCommand {
 	}
 }
 
-async fn resize_image(image_bytes: &Bytes) -> Result<Cursor<Vec<u8>>> {
+pub async fn resize_image(image_bytes: &Bytes) -> Result<Cursor<Vec<u8>>> {
 	let image = image::load_from_memory_with_format(image_bytes, guess_format(image_bytes)?)?;
 
 	let (width, height) = image.dimensions();
@@ -225,16 +241,13 @@ fn calculate_crop_params(width: u32, height: u32) -> (u32, u32, u32) {
 	(crop_x, crop_y, square_size)
 }
 
-async fn check_if_activity_exist(anime_id: i32, server_id: String, config: DbConfig) -> bool {
-	let conn = match sea_orm::Database::connect(get_url(config.clone())).await {
-		Ok(conn) => conn,
-		Err(_) => return false,
-	};
-
+pub async fn check_if_activity_exist(
+	anime_id: i32, server_id: String, db_connection: Arc<DatabaseConnection>,
+) -> bool {
 	let row = match ActivityData::find()
 		.filter(Column::ServerId.eq(server_id))
 		.filter(Column::AnimeId.eq(anime_id))
-		.one(&conn)
+		.one(&*db_connection)
 		.await
 	{
 		Ok(row) => row,
@@ -263,7 +276,7 @@ pub fn get_name(title: MediaTitle) -> String {
 	title
 }
 
-async fn get_webhook(
+pub async fn get_webhook(
 	ctx: &SerenityContext, channel_id: ChannelId, image: String, base64: String, anime_name: String,
 ) -> Result<String> {
 	trace!(?image);
@@ -341,9 +354,7 @@ async fn get_webhook(
 	Ok(webhook_url)
 }
 
-pub async fn get_minimal_anime_by_id(
-	id: i32, cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<Media> {
+pub async fn get_minimal_anime_by_id(id: i32, anilist_client: Arc<AnilistClient>) -> Result<Media> {
 	trace!(?id);
 
 	let query = MinimalAnimeIdVariables { id: Some(id) };
@@ -351,19 +362,19 @@ pub async fn get_minimal_anime_by_id(
 	let operation = MinimalAnimeId::build(query);
 
 	let response: GraphQlResponse<MinimalAnimeId> =
-		make_request_anilist(operation, false, cache).await?;
+		make_request_anilist(operation, true, anilist_client).await?;
 
 	let media = response
 		.data
 		.ok_or(anyhow!("Error with request"))?
 		.media
-		.ok_or(anyhow!("No media found"))?;
+		.ok_or(KasukiError::NotFound("No media found".to_string()))?;
 
 	Ok(media)
 }
 
 async fn get_minimal_anime_by_search(
-	query: &str, cache: Arc<RwLock<Cache<String, String>>>,
+	query: &str, anilist_client: Arc<AnilistClient>,
 ) -> Result<Media> {
 	trace!(?query);
 
@@ -374,24 +385,24 @@ async fn get_minimal_anime_by_search(
 	let operation = MinimalAnimeSearch::build(search_query);
 
 	let response: GraphQlResponse<MinimalAnimeSearch> =
-		make_request_anilist(operation, false, cache).await?;
+		make_request_anilist(operation, true, anilist_client).await?;
 
 	let media = response
 		.data
 		.ok_or(anyhow!("Error with request"))?
 		.media
-		.ok_or(anyhow!("No media found"))?;
+		.ok_or(KasukiError::NotFound("No media found".to_string()))?;
 
 	Ok(media)
 }
 
 pub async fn get_minimal_anime_media(
-	anime: String, cache: Arc<RwLock<Cache<String, String>>>,
+	anime: String, anilist_client: Arc<AnilistClient>,
 ) -> Result<Media> {
 	let media = if let Ok(id) = anime.parse::<i32>() {
-		get_minimal_anime_by_id(id, cache).await?
+		get_minimal_anime_by_id(id, anilist_client).await?
 	} else {
-		get_minimal_anime_by_search(&anime, cache).await?
+		get_minimal_anime_by_search(&anime, anilist_client).await?
 	};
 
 	trace!(?media);
@@ -0,0 +1,260 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+
+use crate::command::admin::anilist::add_activity::{
+	check_if_activity_exist, get_name, get_webhook, resize_image,
+};
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
+use crate::constant::ACTIVITY_KIND_MANGA;
+use crate::database::activity_data;
+use crate::database::prelude::ActivityData;
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+use crate::helper::get_option::subcommand_group::get_option_map_string_subcommand_group;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
+use crate::helper::trimer::trim_webhook;
+use crate::structure::message::admin::anilist::add_manga_activity::load_localization_add_manga_activity;
+use crate::structure::run::anilist::minimal_anime::{
+	MangaMedia, MinimalMangaId, MinimalMangaIdVariables, MinimalMangaSearch,
+	MinimalMangaSearchVariables,
+};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use chrono::Utc;
+use cynic::{GraphQlResponse, QueryBuilder};
+use reqwest::get;
+use sea_orm::ActiveValue::Set;
+use sea_orm::EntityTrait;
+use serenity::all::{CommandInteraction, Context as SerenityContext};
+use tracing::trace;
+
+pub struct AddMangaActivityCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for AddMangaActivityCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for AddMangaActivityCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let command_interaction = self.command_interaction.clone();
+
+		let ctx = self.ctx.clone();
+		let bot_data = ctx.data::<BotData>().clone();
+		let anilist_client = bot_data.anilist_client.clone();
+
+		let db_connection = bot_data.db_connection.clone();
+
+		let map = get_option_map_string_subcommand_group(&command_interaction);
+
+		let manga = map
+			.get(&String::from("manga_name"))
+			.cloned()
+			.unwrap_or(String::new());
+
+		let media = get_minimal_manga_media(manga, anilist_client).await?;
+
+		let guild_id = match command_interaction.guild_id {
+			Some(id) => id.to_string(),
+			None => String::from("1"),
+		};
+
+		let add_activity_localised = load_localization_add_manga_activity(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			db_connection.clone(),
+		)
+		.await?;
+
+		let exist =
+			check_if_activity_exist(media.id, guild_id.clone(), db_connection.clone()).await;
+
+		self.defer().await?;
+
+		let url = format!("https://anilist.co/manga/{}", media.id);
+
+		let title = media
+			.title
+			.ok_or(anyhow!("No title for the media".to_string()))?;
+
+		// MangaUpdates lists the series under their romaji title.
+		let search_title = title
+			.romaji
+			.clone()
+			.or(title.english.clone())
+			.unwrap_or_default();
+
+		let manga_name = get_name(title);
+
+		if exist {
+			self.send_embed(
+				Vec::new(),
+				None,
+				add_activity_localised.fail.clone(),
+				add_activity_localised
+					.fail_desc
+					.replace("$manga$", manga_name.as_str()),
+				None,
+				Some(url),
+				EmbedType::Followup,
+				None,
+				Vec::new(),
+			)
+			.await?;
+
+			return Ok(());
+		}
+
+		let series = bot_data
+			.chapter_provider
+			.find_series(&search_title)
+			.await?
+			.ok_or(KasukiError::NotFound(format!(
+				"No chapter releases found for {}",
+				manga_name
+			)))?;
+
+		// The chapters already released are not sent.
+		let last_chapter = bot_data
+			.chapter_provider
+			.latest_releases(&series.id)
+			.await?
+			.iter()
+			.filter_map(|release| release.number())
+			.max_by(f64::total_cmp)
+			.unwrap_or(0.0);
+
+		trace!(?series, last_chapter);
+
+		let trimmed_manga_name = if manga_name.len() >= 50 {
+			trim_webhook(manga_name.clone(), 50 - manga_name.len() as i32)
+		} else {
+			manga_name.clone()
+		};
+
+		let cover = media
+			.cover_image
+			.and_then(|cover_image| cover_image.extra_large)
+			.ok_or(anyhow!("No cover image for this media"))?;
+
+		let bytes = get(cover).await?.bytes().await?;
+
+		let buf = resize_image(&bytes).await?;
+
+		let base64 = STANDARD.encode(buf.into_inner());
+
+		let image = format!("data:image/jpeg;base64,{}", base64);
+
+		let webhook = get_webhook(
+			&ctx,
+			command_interaction.channel_id,
+			image.clone(),
+			base64,
+			trimmed_manga_name.clone(),
+		)
+		.await?;
+
+		ActivityData::insert(activity_data::ActiveModel {
+			anime_id: Set(media.id),
+			timestamp: Set(Utc::now().naive_utc()),
+			server_id: Set(guild_id),
+			webhook: Set(webhook),
+			episode: Set(last_chapter.floor() as i32),
+			name: Set(trimmed_manga_name),
+			delay: Set(0),
+			image: Set(image),
+			media_kind: Set(ACTIVITY_KIND_MANGA.to_string()),
+			external_id: Set(Some(series.id)),
+			sent_episode: Set(None),
+			last_chapter: Set(Some(last_chapter.to_string())),
+		})
+		.exec(&*db_connection)
+		.await?;
+
+		self.send_embed(
+			Vec::new(),
+			None,
+			add_activity_localised.success.clone(),
+			// The series is shown since the search can match another one than the manga.
+			format!(
+				"{}\n{}",
+				add_activity_localised
+					.success_desc
+					.replace("$manga$", manga_name.as_str()),
+				add_activity_localised
+					.series
+					.replace("$series$", series.title.as_str())
+			),
+			None,
+			Some(url),
+			EmbedType::Followup,
+			None,
+			Vec::new(),
+		)
+		.await?;
+
+		Ok(())
+	}
+}
+
+async fn get_minimal_manga_by_id(
+	id: i32, anilist_client: Arc<AnilistClient>,
+) -> Result<MangaMedia> {
+	let query = MinimalMangaIdVariables { id: Some(id) };
+
+	let operation = MinimalMangaId::build(query);
+
+	let response: GraphQlResponse<MinimalMangaId> =
+		make_request_anilist(operation, true, anilist_client).await?;
+
+	let media = response
+		.data
+		.ok_or(anyhow!("Error with request"))?
+		.media
+		.ok_or(KasukiError::NotFound("No media found".to_string()))?;
+
+	Ok(media)
+}
+
+async fn get_minimal_manga_by_search(
+	query: &str, anilist_client: Arc<AnilistClient>,
+) -> Result<MangaMedia> {
+	let search_query = MinimalMangaSearchVariables {
+		search: Some(query),
+	};
+
+	let operation = MinimalMangaSearch::build(search_query);
+
+	let response: GraphQlResponse<MinimalMangaSearch> =
+		make_request_anilist(operation, true, anilist_client).await?;
+
+	let media = response
+		.data
+		.ok_or(anyhow!("Error with request"))?
+		.media
+		.ok_or(KasukiError::NotFound("No media found".to_string()))?;
+
+	Ok(media)
+}
+
+pub async fn get_minimal_manga_media(
+	manga: String, anilist_client: Arc<AnilistClient>,
+) -> Result<MangaMedia> {
+	let media = if let Ok(id) = manga.parse::<i32>() {
+		get_minimal_manga_by_id(id, anilist_client).await?
+	} else {
+		get_minimal_manga_by_search(&manga, anilist_client).await?
+	};
+
+	trace!(?media);
+
+	Ok(media)
+}
@@ -1,15 +1,20 @@
-use crate::command::admin::anilist::add_activity::{get_minimal_anime_media, get_name};
 use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
-use crate::config::DbConfig;
+use crate::database::activity_data::{Column, Model};
 use crate::database::prelude::ActivityData;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::get_option::subcommand_group::get_option_map_string_subcommand_group;
 use crate::structure::message::admin::anilist::delete_activity::load_localization_delete_activity;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use sea_orm::ColumnTrait;
+use sea_orm::DatabaseConnection;
 use sea_orm::{EntityTrait, ModelTrait, QueryFilter};
 use serenity::all::{CommandInteraction, Context as SerenityContext};
+use std::sync::Arc;
+
+/// Length the activity names are trimmed to when they are added, the webhook name limit.
+
+const WEBHOOK_NAME_LENGTH: usize = 50;
 
 pub struct DeleteActivityCommand {
 	pub ctx: SerenityContext,
@@ -30,11 +35,10 @@ impl SlashCommand for DeleteActivityCommand {
 	async fn run_slash(&self) -> Result<()> {
 		let ctx = self.get_ctx();
 		let bot_data = ctx.data::<BotData>().clone();
-		let anilist_cache = bot_data.anilist_cache.clone();
 
 		let command_interaction = self.command_interaction.clone();
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
 		let map = get_option_map_string_subcommand_group(&command_interaction);
 
@@ -43,6 +47,8 @@ impl SlashCommand for DeleteActivityCommand {
 			.cloned()
 			.unwrap_or(String::new());
 
+		let media_kind = map.get(&String::from("type")).cloned();
+
 		let guild_id = match command_interaction.guild_id {
 			Some(id) => id.to_string(),
 			None => String::from("1"),
@@ -50,22 +56,29 @@ impl SlashCommand for DeleteActivityCommand {
 
 		self.defer().await?;
 
-		let delete_activity_localised_text =
-			load_localization_delete_activity(guild_id.clone(), config.db.clone()).await?;
-
-		let media = get_minimal_anime_media(anime.to_string(), anilist_cache).await?;
-
-		let anime_id = media.id;
+		let delete_activity_localised_text = load_localization_delete_activity(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			db_connection.clone(),
+		)
+		.await?;
 
-		remove_activity(guild_id.as_str(), &anime_id, config.db.clone()).await?;
+		let activity = find_activity(
+			&guild_id,
+			&anime,
+			media_kind.as_deref(),
+			db_connection.clone(),
+		)
+		.await?;
 
-		let title = media
-			.title
-			.ok_or(anyhow!(format!("Anime with id {} not found", anime_id)))?;
+		let anime_name = activity.name.clone();
 
-		let anime_name = get_name(title);
+		let url = format!(
+			"https://anilist.co/{}/{}",
+			activity.media_kind, activity.anime_id
+		);
 
-		let url = format!("https://anilist.co/anime/{}", media.id);
+		activity.delete(&*db_connection).await?;
 
 		self.send_embed(
 			Vec::new(),
@@ -86,17 +99,69 @@ impl SlashCommand for DeleteActivityCommand {
 	}
 }
 
-async fn remove_activity(guild_id: &str, anime_id: &i32, db_config: DbConfig) -> Result<()> {
-	let connection = sea_orm::Database::connect(get_url(db_config.clone())).await?;
+/// The activity of the guild chosen in the autocomplete, by id, or found by its name among the
+/// activities of the guild. `media_kind` tells an anime from a manga of the same name.
+
+async fn find_activity(
+	guild_id: &str, anime: &str, media_kind: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<Model> {
+	let mut query = ActivityData::find().filter(Column::ServerId.eq(guild_id));
+
+	if let Some(media_kind) = media_kind {
+		query = query.filter(Column::MediaKind.eq(media_kind));
+	}
+
+	if let Ok(id) = anime.parse::<i32>() {
+		return query
+			.filter(Column::AnimeId.eq(id))
+			.one(&*db_connection)
+			.await?
+			.ok_or(KasukiError::NotFound(format!("No activity found for {}", id)).into());
+	}
 
-	let activity = ActivityData::find()
-		.filter(crate::database::activity_data::Column::ServerId.eq(guild_id))
-		.filter(crate::database::activity_data::Column::AnimeId.eq(anime_id.to_string()))
-		.one(&connection)
+	let mut activities: Vec<Model> = query
+		.all(&*db_connection)
 		.await?
-		.ok_or(anyhow!(format!("Anime with id {} not found", anime_id)))?;
+		.into_iter()
+		.filter(|activity| matches_name(&activity.name, anime))
+		.collect();
+
+	if activities.len() > 1 {
+		return Err(KasukiError::UserInput(format!(
+			"An anime and a manga activity are named {}, choose one in the list or give the type",
+			anime
+		))
+		.into());
+	}
+
+	activities
+		.pop()
+		.ok_or(KasukiError::NotFound(format!("No activity found for {}", anime)).into())
+}
+
+/// The stored names are trimmed to the length of a webhook name, a longer name only has to
+/// start with it.
+
+fn matches_name(stored: &str, name: &str) -> bool {
+	let (stored, name) = (stored.to_lowercase(), name.trim().to_lowercase());
+
+	stored == name || (stored.len() >= WEBHOOK_NAME_LENGTH && name.starts_with(&stored))
+}
 
-	activity.delete(&connection).await?;
+#[cfg(test)]
 
-	Ok(())
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_matches_name() {
+		assert!(matches_name("One Piece", "one piece "));
+
+		assert!(!matches_name("One Piece", "One Piece Film"));
+
+		let trimmed = "a".repeat(WEBHOOK_NAME_LENGTH);
+
+		assert!(matches_name(&trimmed, &format!("{}bc", trimmed)));
+	}
 }
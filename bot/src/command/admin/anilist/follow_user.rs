@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Result};
+
+use crate::background_task::activity::user_activity::fetch_activities;
+use crate::command::admin::anilist::add_activity::{get_webhook, resize_image};
+use crate::command::anilist_user::user::get_user;
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
+use crate::database::followed_user;
+use crate::database::prelude::{FollowedUser, RegisteredUser};
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+use crate::helper::get_option::subcommand_group::get_option_map_user_subcommand_group;
+use crate::structure::message::admin::anilist::follow_user::load_localization_follow_user;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use chrono::Utc;
+use reqwest::get;
+use sea_orm::ActiveValue::Set;
+use sea_orm::EntityTrait;
+use serenity::all::{CommandInteraction, Context as SerenityContext};
+use tracing::trace;
+
+pub struct FollowUserCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for FollowUserCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for FollowUserCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let command_interaction = self.command_interaction.clone();
+
+		let ctx = self.ctx.clone();
+		let bot_data = ctx.data::<BotData>().clone();
+		let anilist_client = bot_data.anilist_client.clone();
+
+		let db_connection = bot_data.db_connection.clone();
+
+		let map = get_option_map_user_subcommand_group(&command_interaction);
+
+		let user_id = map
+			.get(&String::from("user"))
+			.ok_or(KasukiError::UserInput("No user given".to_string()))?;
+
+		let registered = RegisteredUser::find_by_id(user_id.to_string())
+			.one(&*db_connection)
+			.await?
+			.ok_or(KasukiError::NotFound(format!(
+				"<@{}> has not linked an AniList account with /register",
+				user_id
+			)))?;
+
+		let guild_id = match command_interaction.guild_id {
+			Some(id) => id.to_string(),
+			None => String::from("1"),
+		};
+
+		let follow_user_localised = load_localization_follow_user(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			db_connection.clone(),
+		)
+		.await?;
+
+		self.defer().await?;
+
+		let user = get_user(&registered.anilist_id.to_string(), anilist_client.clone()).await?;
+
+		let url = format!("https://anilist.co/user/{}", user.name);
+
+		let exist = FollowedUser::find_by_id((guild_id.clone(), user.id))
+			.one(&*db_connection)
+			.await?
+			.is_some();
+
+		if exist {
+			self.send_embed(
+				Vec::new(),
+				None,
+				follow_user_localised.fail.clone(),
+				follow_user_localised
+					.fail_desc
+					.replace("$user$", user.name.as_str()),
+				None,
+				Some(url),
+				EmbedType::Followup,
+				None,
+				Vec::new(),
+			)
+			.await?;
+
+			return Ok(());
+		}
+
+		// The activities made before the follow are not sent.
+		let last_activity_id = fetch_activities(&[user.id], i32::MAX, anilist_client)
+			.await?
+			.iter()
+			.map(|activity| activity.id)
+			.max()
+			.unwrap_or_default();
+
+		trace!(anilist_id = user.id, last_activity_id);
+
+		let avatar = user
+			.avatar
+			.clone()
+			.and_then(|avatar| avatar.large)
+			.ok_or(anyhow!("No avatar for this user"))?;
+
+		let bytes = get(avatar).await?.bytes().await?;
+
+		let buf = resize_image(&bytes).await?;
+
+		let base64 = STANDARD.encode(buf.into_inner());
+
+		let image = format!("data:image/jpeg;base64,{}", base64);
+
+		let webhook = get_webhook(
+			&ctx,
+			command_interaction.channel_id,
+			image,
+			base64,
+			user.name.clone(),
+		)
+		.await?;
+
+		FollowedUser::insert(followed_user::ActiveModel {
+			guild_id: Set(guild_id),
+			anilist_id: Set(user.id),
+			webhook: Set(webhook),
+			last_activity_id: Set(last_activity_id),
+			created_at: Set(Utc::now().naive_utc()),
+		})
+		.exec(&*db_connection)
+		.await?;
+
+		self.send_embed(
+			Vec::new(),
+			None,
+			follow_user_localised.success.clone(),
+			follow_user_localised
+				.success_desc
+				.replace("$user$", user.name.as_str()),
+			None,
+			Some(url),
+			EmbedType::Followup,
+			None,
+			Vec::new(),
+		)
+		.await?;
+
+		Ok(())
+	}
+}
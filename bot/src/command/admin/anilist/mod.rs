@@ -1,2 +1,5 @@
 pub mod add_activity;
+pub mod add_manga_activity;
 pub mod delete_activity;
+pub mod follow_user;
+pub mod unfollow_user;
@@ -0,0 +1,85 @@
+use anyhow::Result;
+
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
+use crate::database::prelude::{FollowedUser, RegisteredUser};
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+use crate::helper::get_option::subcommand_group::get_option_map_user_subcommand_group;
+use crate::structure::message::admin::anilist::unfollow_user::load_localization_unfollow_user;
+use sea_orm::{EntityTrait, ModelTrait};
+use serenity::all::{CommandInteraction, Context as SerenityContext};
+
+pub struct UnfollowUserCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for UnfollowUserCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for UnfollowUserCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let command_interaction = self.command_interaction.clone();
+
+		let bot_data = self.ctx.data::<BotData>().clone();
+
+		let db_connection = bot_data.db_connection.clone();
+
+		let map = get_option_map_user_subcommand_group(&command_interaction);
+
+		let user_id = map
+			.get(&String::from("user"))
+			.ok_or(KasukiError::UserInput("No user given".to_string()))?;
+
+		let guild_id = match command_interaction.guild_id {
+			Some(id) => id.to_string(),
+			None => String::from("1"),
+		};
+
+		let unfollow_user_localised = load_localization_unfollow_user(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			db_connection.clone(),
+		)
+		.await?;
+
+		let not_followed =
+			|| KasukiError::NotFound(format!("<@{}> is not followed in this server", user_id));
+
+		let registered = RegisteredUser::find_by_id(user_id.to_string())
+			.one(&*db_connection)
+			.await?
+			.ok_or_else(not_followed)?;
+
+		let follow = FollowedUser::find_by_id((guild_id, registered.anilist_id))
+			.one(&*db_connection)
+			.await?
+			.ok_or_else(not_followed)?;
+
+		follow.delete(&*db_connection).await?;
+
+		self.send_embed(
+			Vec::new(),
+			None,
+			unfollow_user_localised.success.clone(),
+			unfollow_user_localised
+				.success_desc
+				.replace("$user$", &format!("<@{}>", user_id)),
+			None,
+			None,
+			EmbedType::First,
+			None,
+			Vec::new(),
+		)
+		.await?;
+
+		Ok(())
+	}
+}
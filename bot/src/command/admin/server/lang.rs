@@ -50,7 +50,12 @@ impl SlashCommand for LangCommand {
 		.exec(&*connection)
 		.await?;
 
-		let lang_localised = load_localization_lang(guild_id, bot_data.config.db.clone()).await?;
+		let lang_localised = load_localization_lang(
+			guild_id,
+			Some(command_interaction.locale.as_str()),
+			connection,
+		)
+		.await?;
 
 		self.send_embed(
 			Vec::new(),
@@ -1,14 +1,18 @@
 use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
-use crate::database::module_activation::Model;
+use crate::database::module_activation::{ActiveModel, Column, Model};
 use crate::database::prelude::ModuleActivation;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::get_option::subcommand_group::{
 	get_option_map_boolean_subcommand_group, get_option_map_string_subcommand_group,
 };
 use crate::structure::message::admin::server::module::load_localization_module_activation;
 use anyhow::{anyhow, Result};
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::ActiveValue::Set;
 use sea_orm::ColumnTrait;
-use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, QueryFilter};
+use sea_orm::{EntityTrait, QueryFilter};
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 
 pub struct ModuleCommand {
@@ -44,9 +48,12 @@ impl SlashCommand for ModuleCommand {
 			.get(&String::from("name"))
 			.ok_or(anyhow!("No option for name"))?;
 
-		let module_localised =
-			load_localization_module_activation(guild_id.clone(), bot_data.config.db.clone())
-				.await?;
+		let module_localised = load_localization_module_activation(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			connection.clone(),
+		)
+		.await?;
 
 		let map = get_option_map_boolean_subcommand_group(command_interaction);
 
@@ -55,7 +62,7 @@ impl SlashCommand for ModuleCommand {
 			.ok_or(anyhow!("No option for state"))?;
 
 		let mut row = ModuleActivation::find()
-			.filter(crate::database::module_activation::Column::GuildId.eq(guild_id.clone()))
+			.filter(Column::GuildId.eq(guild_id.clone()))
 			.one(&*connection)
 			.await?
 			.unwrap_or(Model {
@@ -77,13 +84,39 @@ impl SlashCommand for ModuleCommand {
 			"ANIME" => row.anime_module = state,
 			"VN" => row.vn_module = state,
 			_ => {
-				return Err(anyhow!("The module specified does not exist"));
+				return Err(KasukiError::UserInput(
+					"The module specified does not exist".to_string(),
+				)
+				.into());
 			},
 		}
 
-		let active_model = row.into_active_model();
-
-		active_model.update(&*connection).await?;
+		// the guild may not have a row yet, so it is inserted or updated
+		ModuleActivation::insert(ActiveModel {
+			guild_id: Set(row.guild_id),
+			ai_module: Set(row.ai_module),
+			anilist_module: Set(row.anilist_module),
+			game_module: Set(row.game_module),
+			new_members_module: Set(row.new_members_module),
+			anime_module: Set(row.anime_module),
+			vn_module: Set(row.vn_module),
+			updated_at: Set(Utc::now().naive_utc()),
+		})
+		.on_conflict(
+			OnConflict::column(Column::GuildId)
+				.update_columns([
+					Column::AiModule,
+					Column::AnilistModule,
+					Column::GameModule,
+					Column::NewMembersModule,
+					Column::AnimeModule,
+					Column::VnModule,
+					Column::UpdatedAt,
+				])
+				.to_owned(),
+		)
+		.exec(&*connection)
+		.await?;
 
 		let desc = if state {
 			&module_localised.on
@@ -109,9 +109,12 @@ impl SlashCommand for NewMemberSettingCommand {
 		// save the hashmap to the file
 		fs::write(NEW_MEMBER_PATH, serde_json::to_string(&hashmap)?)?;
 
-		let localised =
-			load_localization_new_member_setting(guild_id.clone(), bot_data.config.db.clone())
-				.await?;
+		let localised = load_localization_new_member_setting(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+		)
+		.await?;
 
 		self.send_embed(
 			Vec::new(),
@@ -1,8 +1,6 @@
 use std::sync::Arc;
 
-use crate::command::command_trait::{
-	Command, Embed, EmbedType, PremiumCommand, PremiumCommandType, SlashCommand,
-};
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
 use crate::config::Config;
 use crate::constant::DEFAULT_STRING;
 use crate::event_handler::BotData;
@@ -25,7 +23,6 @@ use uuid::Uuid;
 pub struct ImageCommand {
 	pub ctx: SerenityContext,
 	pub command_interaction: CommandInteraction,
-	pub command_name: String,
 }
 
 impl Command for ImageCommand {
@@ -42,19 +39,6 @@ impl SlashCommand for ImageCommand {
 	async fn run_slash(&self) -> Result<()> {
 		let ctx = &self.ctx;
 		let bot_data = ctx.data::<BotData>().clone();
-		if self
-			.check_hourly_limit(
-				self.command_name.clone(),
-				&bot_data.clone(),
-				PremiumCommandType::AIImage,
-			)
-			.await?
-		{
-			return Err(anyhow!(
-				"You have reached your hourly limit. Please try again later.",
-			));
-		}
-
 		let command_interaction = &self.command_interaction;
 
 		let config = bot_data.config.clone();
@@ -70,7 +54,12 @@ impl SlashCommand for ImageCommand {
 			None => String::from("0"),
 		};
 
-		let image_localised = load_localization_image(guild_id.clone(), config.db.clone()).await?;
+		let image_localised = load_localization_image(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+		)
+		.await?;
 
 		self.defer().await?;
 
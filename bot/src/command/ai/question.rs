@@ -1,10 +1,8 @@
-use crate::command::command_trait::{
-	Command, Embed, EmbedType, PremiumCommand, PremiumCommandType, SlashCommand,
-};
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
 use crate::constant::DEFAULT_STRING;
 use crate::event_handler::BotData;
 use crate::helper::get_option::subcommand::get_option_map_string_subcommand;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use serde_json::{json, Value};
 use serenity::all::{CommandInteraction, Context as SerenityContext};
@@ -12,7 +10,6 @@ use tracing::trace;
 pub struct QuestionCommand {
 	pub ctx: SerenityContext,
 	pub command_interaction: CommandInteraction,
-	pub command_name: String,
 }
 
 impl Command for QuestionCommand {
@@ -32,19 +29,6 @@ impl SlashCommand for QuestionCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let config = bot_data.config.clone();
 
-		if self
-			.check_hourly_limit(
-				self.command_name.clone(),
-				&bot_data,
-				PremiumCommandType::AIQuestion,
-			)
-			.await?
-		{
-			return Err(anyhow!(
-				"You have reached your hourly limit. Please try again later.",
-			));
-		}
-
 		let map = get_option_map_string_subcommand(command_interaction);
 
 		let prompt = map.get(&String::from("prompt")).unwrap_or(DEFAULT_STRING);
@@ -1,7 +1,6 @@
-use crate::command::command_trait::{
-	Command, Embed, EmbedType, PremiumCommand, PremiumCommandType, SlashCommand,
-};
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
 use crate::constant::DEFAULT_STRING;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::get_option::subcommand::{
 	get_option_map_attachment_subcommand, get_option_map_string_subcommand,
@@ -17,7 +16,6 @@ use uuid::Uuid;
 pub struct TranscriptCommand {
 	pub ctx: SerenityContext,
 	pub command_interaction: CommandInteraction,
-	pub command_name: String,
 }
 
 impl Command for TranscriptCommand {
@@ -37,19 +35,6 @@ impl SlashCommand for TranscriptCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let config = bot_data.config.clone();
 
-		if self
-			.check_hourly_limit(
-				self.command_name.clone(),
-				&bot_data,
-				PremiumCommandType::AITranscript,
-			)
-			.await?
-		{
-			return Err(anyhow!(
-				"You have reached your hourly limit. Please try again later.",
-			));
-		}
-
 		let map = get_option_map_string_subcommand(command_interaction);
 
 		self.defer().await?;
@@ -82,11 +67,15 @@ impl SlashCommand for TranscriptCommand {
 			None => String::from("0"),
 		};
 
-		let transcript_localised =
-			load_localization_transcript(guild_id, config.db.clone()).await?;
+		let transcript_localised = load_localization_transcript(
+			guild_id,
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+		)
+		.await?;
 
 		if !content_type.starts_with("audio/") && !content_type.starts_with("video/") {
-			return Err(anyhow!("Unsupported file type"));
+			return Err(KasukiError::UserInput("Unsupported file type".to_string()).into());
 		}
 
 		let allowed_extensions = ["mp3", "mp4", "mpeg", "mpga", "m4a", "wav", "webm", "ogg"];
@@ -102,11 +91,13 @@ impl SlashCommand for TranscriptCommand {
 		let file_extension = last_segment
 			.rsplit('.')
 			.next()
-			.ok_or(anyhow!("Failed to get the file extension"))?
+			.ok_or(KasukiError::UserInput(
+				"The file has no extension".to_string(),
+			))?
 			.to_lowercase();
 
 		if !allowed_extensions.contains(&&*file_extension) {
-			return Err(anyhow!("Unsupported file extension"));
+			return Err(KasukiError::UserInput("Unsupported file extension".to_string()).into());
 		}
 
 		let response = reqwest::get(content.to_string()).await?;
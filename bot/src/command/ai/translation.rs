@@ -1,8 +1,7 @@
 use crate::command::ai::question::question_api_url;
-use crate::command::command_trait::{
-	Command, Embed, EmbedType, PremiumCommand, PremiumCommandType, SlashCommand,
-};
+use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
 use crate::constant::DEFAULT_STRING;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::get_option::subcommand::{
 	get_option_map_attachment_subcommand, get_option_map_string_subcommand,
@@ -19,8 +18,6 @@ use uuid::Uuid;
 pub struct TranslationCommand {
 	pub ctx: SerenityContext,
 	pub command_interaction: CommandInteraction,
-
-	pub command_name: String,
 }
 
 impl Command for TranslationCommand {
@@ -40,19 +37,6 @@ impl SlashCommand for TranslationCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let config = bot_data.config.clone();
 
-		if self
-			.check_hourly_limit(
-				self.command_name.clone(),
-				&bot_data,
-				PremiumCommandType::AITranslation,
-			)
-			.await?
-		{
-			return Err(anyhow!(
-				"You have reached your hourly limit. Please try again later.",
-			));
-		}
-
 		let map = get_option_map_string_subcommand(command_interaction);
 
 		let attachment_map = get_option_map_attachment_subcommand(command_interaction);
@@ -78,11 +62,15 @@ impl SlashCommand for TranslationCommand {
 			None => String::from("0"),
 		};
 
-		let translation_localised =
-			load_localization_translation(guild_id, config.db.clone()).await?;
+		let translation_localised = load_localization_translation(
+			guild_id,
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+		)
+		.await?;
 
 		if !content_type.starts_with("audio/") && !content_type.starts_with("video/") {
-			return Err(anyhow!("Unsupported file type"));
+			return Err(KasukiError::UserInput("Unsupported file type".to_string()).into());
 		}
 
 		self.defer().await?;
@@ -100,11 +88,13 @@ impl SlashCommand for TranslationCommand {
 		let file_extension = last_segment
 			.rsplit('.')
 			.next()
-			.ok_or(anyhow!("Failed to get the file extension"))?
+			.ok_or(KasukiError::UserInput(
+				"The file has no extension".to_string(),
+			))?
 			.to_lowercase();
 
 		if !allowed_extensions.contains(&&*file_extension) {
-			return Err(anyhow!("Unsupported file extension"));
+			return Err(KasukiError::UserInput("Unsupported file extension".to_string()).into());
 		}
 
 		let response = reqwest::get(content.as_str()).await?; // save the file into a buffer
@@ -1,15 +1,14 @@
 use crate::command::command_trait::{Command, SlashCommand};
 use crate::components::anilist::list_all_activity::get_formatted_activity_list;
-use crate::config::Config;
 use crate::constant::ACTIVITY_LIST_LIMIT;
 use crate::database::activity_data::Column;
 use crate::database::prelude::ActivityData;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::anilist_server::list_all_activity::load_localization_list_activity;
 use anyhow::{anyhow, Result};
 use sea_orm::ColumnTrait;
+use sea_orm::DatabaseConnection;
 use sea_orm::EntityTrait;
 use sea_orm::QueryFilter;
 use serenity::all::CreateInteractionResponse::Defer;
@@ -42,22 +41,27 @@ impl SlashCommand for ListAllActivity {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = match command_interaction.guild_id {
 		Some(id) => id.to_string(),
 		None => String::from("0"),
 	};
 
-	let list_activity_localised_text =
-		load_localization_list_activity(guild_id, config.db.clone()).await?;
+	let list_activity_localised_text = load_localization_list_activity(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let guild_id = command_interaction
 		.guild_id
@@ -69,11 +73,9 @@ async fn send_embed(
 		.create_response(&ctx.http, builder_message)
 		.await?;
 
-	let connection = sea_orm::Database::connect(get_url(config.db.clone())).await?;
-
 	let list = ActivityData::find()
 		.filter(Column::ServerId.eq(guild_id.to_string()))
-		.all(&connection)
+		.all(&*db_connection)
 		.await?;
 
 	let len = list.len();
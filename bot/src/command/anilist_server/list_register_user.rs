@@ -1,18 +1,17 @@
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::{Config, DbConfig};
 use crate::constant::{MEMBER_LIST_LIMIT, PASS_LIMIT};
 use crate::database::prelude::RegisteredUser;
 use crate::database::registered_user::{Column, Model};
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::anilist_server::list_register_user::{
 	load_localization_list_user, ListUserLocalised,
 };
 use anyhow::{anyhow, Result};
 use sea_orm::ColumnTrait;
+use sea_orm::DatabaseConnection;
 use sea_orm::EntityTrait;
 use sea_orm::QueryFilter;
 use serenity::all::CreateInteractionResponse::Defer;
@@ -45,14 +44,15 @@ impl SlashCommand for ListRegisterUser {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction or use "0" if it does not exist
 	let guild_id = match command_interaction.guild_id {
@@ -61,7 +61,12 @@ async fn send_embed(
 	};
 
 	// Load the localized text for the list user command
-	let list_user_localised = load_localization_list_user(guild_id, config.db.clone()).await?;
+	let list_user_localised = load_localization_list_user(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Retrieve the guild from the guild ID
 	let guild_id = match command_interaction.guild_id {
@@ -79,8 +84,14 @@ async fn send_embed(
 		.await?;
 
 	// Retrieve a list of AniList users in the guild
-	let (builder_message, len, last_id): (CreateEmbed, usize, Option<UserId>) =
-		get_the_list(guild, ctx, &list_user_localised, None, config.db.clone()).await?;
+	let (builder_message, len, last_id): (CreateEmbed, usize, Option<UserId>) = get_the_list(
+		guild,
+		ctx,
+		&list_user_localised,
+		None,
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Check if the number of AniList users is greater than the limit
 	let mut response = CreateInteractionResponseFollowup::new().embed(builder_message);
@@ -108,7 +119,7 @@ struct Data {
 
 pub async fn get_the_list<'a>(
 	guild: PartialGuild, ctx: &'a SerenityContext, list_user_localised: &'a ListUserLocalised,
-	last_id: Option<UserId>, db_config: DbConfig,
+	last_id: Option<UserId>, db_connection: Arc<DatabaseConnection>,
 ) -> Result<(CreateEmbed<'a>, usize, Option<UserId>)> {
 	let mut anilist_user = Vec::new();
 
@@ -137,11 +148,9 @@ pub async fn get_the_list<'a>(
 
 			let user_id = member.user.id.to_string();
 
-			let connection = sea_orm::Database::connect(get_url(db_config.clone())).await?;
-
 			let row = RegisteredUser::find()
 				.filter(Column::UserId.eq(user_id.clone()))
-				.one(&connection)
+				.one(&*db_connection)
 				.await?
 				.unwrap_or(Model {
 					user_id: user_id.clone(),
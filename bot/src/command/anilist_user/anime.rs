@@ -1,21 +1,20 @@
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::run::anilist::media;
 use crate::structure::run::anilist::media::{
 	Media, MediaFormat, MediaQuerryId, MediaQuerryIdVariables, MediaQuerrySearch,
 	MediaQuerrySearchVariables, MediaType,
 };
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
+use sea_orm::DatabaseConnection;
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 
 pub struct AnimeCommand {
 	pub ctx: SerenityContext,
@@ -39,16 +38,16 @@ impl SlashCommand for AnimeCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the name or ID of the anime from the command interaction options
 	let map = get_option_map_string(command_interaction);
@@ -58,42 +57,13 @@ async fn send_embed(
 		.cloned()
 		.unwrap_or(String::new());
 
-	let format_in = Some(vec![
-		Some(MediaFormat::Tv),
-		Some(MediaFormat::TvShort),
-		Some(MediaFormat::Movie),
-		Some(MediaFormat::Special),
-		Some(MediaFormat::Ova),
-		Some(MediaFormat::Ona),
-		Some(MediaFormat::Music),
-	]);
-
 	// If the value is an integer, treat it as an ID and retrieve the anime with that ID
 	// If the value is not an integer, treat it as a name and retrieve the anime with that name
-	let data: Media = if value.parse::<i32>().is_ok() {
-		let id = value.parse::<i32>().unwrap();
-
-		let var = MediaQuerryIdVariables {
-			format_in,
-			id: Some(id),
-			media_type: Some(MediaType::Anime),
-		};
-
-		let operation = MediaQuerryId::build(var);
-
-		let data: GraphQlResponse<MediaQuerryId> =
-			make_request_anilist(operation, false, anilist_cache).await?;
-
-		match data.data {
-			Some(data) => match data.media {
-				Some(media) => media,
-				None => return Err(anyhow!("Anime not found")),
-			},
-			None => return Err(anyhow!("Anime not found")),
-		}
+	let data: Media = if let Ok(id) = value.parse::<i32>() {
+		get_anime_by_id(id, anilist_client, false).await?
 	} else {
 		let var = MediaQuerrySearchVariables {
-			format_in,
+			format_in: anime_formats(),
 			search: Some(&*value),
 			media_type: Some(MediaType::Anime),
 		};
@@ -101,19 +71,59 @@ async fn send_embed(
 		let operation = MediaQuerrySearch::build(var);
 
 		let data: GraphQlResponse<MediaQuerrySearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		match data.data {
 			Some(data) => match data.media {
 				Some(media) => media,
-				None => return Err(anyhow!("Anime not found")),
+				None => return Err(KasukiError::NotFound("Anime not found".to_string()).into()),
 			},
-			None => return Err(anyhow!("Anime not found")),
+			None => return Err(KasukiError::NotFound("Anime not found".to_string()).into()),
 		}
 	};
 
 	// Send an embed with the anime information as a response to the command interaction
-	media::send_embed(ctx, command_interaction, data, config.db.clone()).await?;
+	media::send_embed(ctx, command_interaction, data, db_connection.clone()).await?;
 
 	Ok(())
 }
+
+/// Formats searched by the anime command, the other ones are searched by the other commands.
+
+fn anime_formats() -> Option<Vec<Option<MediaFormat>>> {
+	Some(vec![
+		Some(MediaFormat::Tv),
+		Some(MediaFormat::TvShort),
+		Some(MediaFormat::Movie),
+		Some(MediaFormat::Special),
+		Some(MediaFormat::Ova),
+		Some(MediaFormat::Ona),
+		Some(MediaFormat::Music),
+	])
+}
+
+/// Gets an anime with the query of the anime command. With `always_update` the cache is
+/// skipped and refreshed with the response.
+
+pub async fn get_anime_by_id(
+	id: i32, anilist_client: Arc<AnilistClient>, always_update: bool,
+) -> Result<Media> {
+	let var = MediaQuerryIdVariables {
+		format_in: anime_formats(),
+		id: Some(id),
+		media_type: Some(MediaType::Anime),
+	};
+
+	let operation = MediaQuerryId::build(var);
+
+	let data: GraphQlResponse<MediaQuerryId> =
+		make_request_anilist(operation, always_update, anilist_client).await?;
+
+	match data.data {
+		Some(data) => match data.media {
+			Some(media) => Ok(media),
+			None => Err(KasukiError::NotFound("Anime not found".to_string()).into()),
+		},
+		None => Err(KasukiError::NotFound("Anime not found".to_string()).into()),
+	}
+}
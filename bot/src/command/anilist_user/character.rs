@@ -1,21 +1,20 @@
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::run::anilist::character;
 use crate::structure::run::anilist::character::{
 	Character, CharacterQuerryId, CharacterQuerryIdVariables, CharacterQuerrySearch,
 	CharacterQuerrySearchVariables,
 };
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
+use sea_orm::DatabaseConnection;
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 
 pub struct CharacterCommand {
 	pub ctx: SerenityContext,
@@ -39,16 +38,16 @@ impl SlashCommand for CharacterCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the name or ID of the character from the command interaction options
 	let map = get_option_map_string(command_interaction);
@@ -61,7 +60,7 @@ async fn send_embed(
 	// If the value is an integer, treat it as an ID and retrieve the character with that ID
 	// If the value is not an integer, treat it as a name and retrieve the character with that name
 	let data: Character = if value.parse::<i32>().is_ok() {
-		get_character_by_id(value.parse::<i32>().unwrap(), anilist_cache).await?
+		get_character_by_id(value.parse::<i32>().unwrap(), anilist_client).await?
 	} else {
 		let var = CharacterQuerrySearchVariables {
 			search: Some(&*value),
@@ -70,32 +69,32 @@ async fn send_embed(
 		let operation = CharacterQuerrySearch::build(var);
 
 		let data: GraphQlResponse<CharacterQuerrySearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().character.unwrap()
 	};
 
 	// Send an embed with the character information as a response to the command interaction
-	character::send_embed(ctx, command_interaction, data, config.db.clone()).await?;
+	character::send_embed(ctx, command_interaction, data, db_connection.clone()).await?;
 
 	Ok(())
 }
 
 pub async fn get_character_by_id(
-	value: i32, anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	value: i32, anilist_client: Arc<AnilistClient>,
 ) -> Result<Character> {
 	let var = CharacterQuerryIdVariables { id: Some(value) };
 
 	let operation = CharacterQuerryId::build(var);
 
 	let data: GraphQlResponse<CharacterQuerryId> =
-		make_request_anilist(operation, false, anilist_cache).await?;
+		make_request_anilist(operation, false, anilist_client).await?;
 
 	Ok(match data.data {
 		Some(data) => match data.character {
 			Some(media) => media,
-			None => return Err(anyhow!("No character found")),
+			None => return Err(KasukiError::NotFound("No character found".to_string()).into()),
 		},
-		None => return Err(anyhow!("No data found")),
+		None => return Err(KasukiError::NotFound("No character found".to_string()).into()),
 	})
 }
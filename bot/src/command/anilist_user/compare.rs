@@ -2,21 +2,20 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use anyhow::Result;
-use moka::future::Cache;
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage,
 };
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 use tracing::trace;
 
 use crate::command::anilist_user::user::get_user;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_string;
+use crate::helper::make_graphql_cached::AnilistClient;
 use crate::structure::message::anilist_user::compare::load_localization_compare;
 use crate::structure::run::anilist::user::{
 	User, UserGenreStatistic, UserStatisticTypes, UserStatistics, UserStatistics2,
@@ -45,16 +44,16 @@ impl SlashCommand for CompareCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the usernames from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -70,9 +69,9 @@ async fn send_embed(
 		.unwrap_or(String::new());
 
 	// Fetch the user data for both users
-	let user: User = get_user(&value, anilist_cache.clone()).await?;
+	let user: User = get_user(&value, anilist_client.clone()).await?;
 
-	let user2: User = get_user(&value2, anilist_cache).await?;
+	let user2: User = get_user(&value2, anilist_client).await?;
 
 	// Get the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -81,7 +80,12 @@ async fn send_embed(
 	};
 
 	// Load the localized comparison strings
-	let compare_localised = load_localization_compare(guild_id, config.db.clone()).await?;
+	let compare_localised = load_localization_compare(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Clone the user data
 	let username = user.name.clone();
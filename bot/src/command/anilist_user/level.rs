@@ -1,26 +1,25 @@
 use std::sync::Arc;
 
-use moka::future::Cache;
 use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
 use sea_orm::EntityTrait;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage,
 };
-use tokio::sync::RwLock;
 
 use crate::command::anilist_user::user::get_user;
 use crate::command::command_trait::Command;
-use crate::config::{Config, DbConfig};
 use crate::database::prelude::RegisteredUser;
 use crate::database::registered_user::Column;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_string;
+use crate::helper::make_graphql_cached::AnilistClient;
 use crate::structure::message::anilist_user::level::load_localization_level;
 use crate::structure::run::anilist::user::{get_color, get_completed, get_user_url, User};
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use sea_orm::ColumnTrait;
 use sea_orm::QueryFilter;
 use small_fixed_array::FixedString;
@@ -47,16 +46,16 @@ impl LevelCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 pub async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the username from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -66,37 +65,35 @@ pub async fn send_embed(
 	match user {
 		Some(value) => {
 			// If a username is provided, fetch the user data and send an embed
-			let data: User = get_user(value, anilist_cache).await?;
+			let data: User = get_user(value, anilist_client).await?;
 
-			send_embed2(ctx, command_interaction, data, config.db.clone()).await
+			send_embed2(ctx, command_interaction, data, db_connection.clone()).await
 		},
 		None => {
 			// If no username is provided, retrieve the ID of the user who triggered the command
 			let user_id = &command_interaction.user.id.to_string();
 
 			// Check if the user is registered
-			let connection = sea_orm::Database::connect(get_url(config.db.clone())).await?;
-
 			let row = RegisteredUser::find()
 				.filter(Column::UserId.eq(user_id))
-				.one(&connection)
+				.one(&*db_connection)
 				.await?;
 
-			let user = row.ok_or(anyhow!(
-				"No user specified or linked to this discord account",
+			let user = row.ok_or(KasukiError::NotFound(
+				"No user specified or linked to this discord account".to_string(),
 			))?;
 
 			// Fetch the user data and send an embed
-			let data: User = get_user(user.anilist_id.to_string().as_str(), anilist_cache).await?;
+			let data: User = get_user(user.anilist_id.to_string().as_str(), anilist_client).await?;
 
-			send_embed2(ctx, command_interaction, data, config.db.clone()).await
+			send_embed2(ctx, command_interaction, data, db_connection.clone()).await
 		},
 	}
 }
 
 pub async fn send_embed2(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, user: User,
-	db_config: DbConfig,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Get the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -105,7 +102,12 @@ pub async fn send_embed2(
 	};
 
 	// Load the localized level strings
-	let level_localised = load_localization_level(guild_id, db_config).await?;
+	let level_localised = load_localization_level(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
 
 	// Clone the manga and anime statistics
 	let statistics = user.statistics.clone().unwrap();
@@ -1,10 +1,9 @@
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::run::anilist::media;
 use crate::structure::run::anilist::media::{
 	Media, MediaFormat, MediaQuerryId, MediaQuerryIdVariables, MediaQuerrySearch,
@@ -12,10 +11,9 @@ use crate::structure::run::anilist::media::{
 };
 use anyhow::Result;
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
+use sea_orm::DatabaseConnection;
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 
 pub struct MangaCommand {
 	pub ctx: SerenityContext,
@@ -39,16 +37,16 @@ impl SlashCommand for MangaCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the name or ID of the manga from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -71,7 +69,7 @@ async fn send_embed(
 		let operation = MediaQuerryId::build(var);
 
 		let data: GraphQlResponse<MediaQuerryId> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().media.unwrap()
 	} else {
@@ -84,13 +82,13 @@ async fn send_embed(
 		let operation = MediaQuerrySearch::build(var);
 
 		let data: GraphQlResponse<MediaQuerrySearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().media.unwrap()
 	};
 
 	// Send an embed containing the manga data as a response to the command interaction
-	media::send_embed(ctx, command_interaction, data, config.db.clone()).await?;
+	media::send_embed(ctx, command_interaction, data, db_connection.clone()).await?;
 
 	Ok(())
 }
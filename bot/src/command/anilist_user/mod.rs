@@ -6,7 +6,9 @@ pub mod ln;
 pub mod manga;
 pub mod random;
 pub mod register;
+pub mod schedule;
 pub mod search;
+pub mod season;
 pub mod seiyuu;
 pub mod staff;
 pub mod studio;
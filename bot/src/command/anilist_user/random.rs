@@ -1,25 +1,23 @@
 use std::sync::Arc;
 
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use rand::{rng, Rng};
+use sea_orm::DatabaseConnection;
 use serenity::all::CreateInteractionResponse::Defer;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponseFollowup,
 	CreateInteractionResponseMessage,
 };
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 use tracing::trace;
 
 use crate::background_task::update_random_stats::update_random_stats;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::convert_flavored_markdown::convert_anilist_flavored_to_discord_flavored_markdown;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::helper::trimer::trim;
 use crate::structure::message::anilist_user::random::{load_localization_random, RandomLocalised};
 use crate::structure::run::anilist::random::{
@@ -49,16 +47,16 @@ impl SlashCommand for RandomCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -67,7 +65,12 @@ async fn send_embed(
 	};
 
 	// Load the localized random strings
-	let random_localised = load_localization_random(guild_id, config.db.clone()).await?;
+	let random_localised = load_localization_random(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Retrieve the type of media (anime or manga) from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -84,7 +87,7 @@ async fn send_embed(
 		.create_response(&ctx.http, builder_message)
 		.await?;
 
-	let random_stats = update_random_stats(anilist_cache.clone()).await?;
+	let random_stats = update_random_stats(anilist_client.clone()).await?;
 
 	let last_page = if random_type.as_str() == "anime" {
 		random_stats.anime_last_page
@@ -102,7 +105,7 @@ async fn send_embed(
 		ctx,
 		command_interaction,
 		random_localised,
-		anilist_cache,
+		anilist_client,
 	)
 	.await?;
 
@@ -112,7 +115,7 @@ async fn send_embed(
 async fn embed(
 	last_page: i32, random_type: String, ctx: &SerenityContext,
 	command_interaction: &CommandInteraction, random_localised: RandomLocalised,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	let number = rng().random_range(1..=last_page);
 
@@ -130,7 +133,7 @@ async fn embed(
 	let operation = RandomPageMedia::build(var);
 
 	let data: Result<GraphQlResponse<RandomPageMedia>> =
-		make_request_anilist(operation, false, anilist_cache).await;
+		make_request_anilist(operation, false, anilist_client).await;
 
 	let data = data?;
 
@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
-use moka::future::Cache;
 use sea_orm::ActiveValue::Set;
 use sea_orm::EntityTrait;
 use serenity::all::{
@@ -9,17 +9,15 @@ use serenity::all::{
 	CreateInteractionResponseMessage,
 };
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 
 use crate::command::anilist_user::user::get_user;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::database::prelude::RegisteredUser;
 use crate::database::registered_user::{ActiveModel, Column};
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_string;
+use crate::helper::make_graphql_cached::AnilistClient;
 use crate::structure::message::anilist_user::register::load_localization_register;
 use crate::structure::run::anilist::user::{get_color, get_user_url, User};
 
@@ -45,16 +43,16 @@ impl SlashCommand for RegisterCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
-			bot_data.anilist_cache.clone(),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the username of the AniList account from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -64,7 +62,7 @@ async fn send_embed(
 		.ok_or(anyhow!("No username provided"))?;
 
 	// Fetch the user data from AniList
-	let user_data: User = get_user(value, anilist_cache).await?;
+	let user_data: User = get_user(value, anilist_client).await?;
 
 	// Retrieve the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -73,7 +71,12 @@ async fn send_embed(
 	};
 
 	// Load the localized register strings
-	let register_localised = load_localization_register(guild_id, config.db.clone()).await?;
+	let register_localised = load_localization_register(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Retrieve the user's Discord ID and username
 	let user_id = &command_interaction.user.id.to_string();
@@ -81,8 +84,6 @@ async fn send_embed(
 	let username = &command_interaction.user.name;
 
 	// Register the user's AniList account by storing the user's Discord ID and AniList ID in the database
-	let connection = sea_orm::Database::connect(get_url(config.db.clone())).await?;
-
 	RegisteredUser::insert(ActiveModel {
 		user_id: Set(user_id.to_string()),
 		anilist_id: Set(user_data.id),
@@ -93,7 +94,7 @@ async fn send_embed(
 			.update_column(Column::AnilistId)
 			.to_owned(),
 	)
-	.exec(&connection)
+	.exec(&*db_connection)
 	.await?;
 
 	// Construct the description for the embed
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use chrono::Utc;
+use sea_orm::EntityTrait;
+use serenity::all::{
+	CommandInteraction, Context as SerenityContext, CreateInteractionResponseFollowup,
+};
+
+use crate::command::command_trait::{Command, Embed, SlashCommand};
+use crate::components::anilist::schedule::{
+	day_of, get_schedule_message, ScheduleFilter, SchedulePage, ScheduleTimezone,
+};
+use crate::database::prelude::RegisteredUser;
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+use crate::helper::get_option::subcommand::{
+	get_option_map_string_subcommand, get_option_map_user_subcommand,
+};
+
+pub struct ScheduleCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for ScheduleCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for ScheduleCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let ctx = self.get_ctx();
+
+		let command_interaction = self.get_command_interaction();
+
+		let bot_data = ctx.data::<BotData>().clone();
+
+		let db_connection = bot_data.db_connection.clone();
+
+		let map = get_option_map_string_subcommand(command_interaction);
+
+		let user_map = get_option_map_user_subcommand(command_interaction);
+
+		let days = match map.get(&String::from("range")).map(String::as_str) {
+			Some("week") => 7,
+			_ => 1,
+		};
+
+		let user = user_map.get(&String::from("user")).copied();
+
+		// Giving a user implies their list.
+		let filter = match map.get(&String::from("filter")) {
+			Some(filter) => ScheduleFilter::parse(filter)
+				.ok_or(KasukiError::UserInput(format!("Unknown filter {}", filter)))?,
+			None if user.is_some() => ScheduleFilter::List,
+			None => ScheduleFilter::All,
+		};
+
+		let timezone = match map.get(&String::from("timezone")) {
+			Some(timezone) => {
+				ScheduleTimezone::parse(timezone).ok_or(KasukiError::UserInput(format!(
+					"{} is not a timezone, use one like Europe/Paris or +02:00",
+					timezone
+				)))?
+			},
+			None => ScheduleTimezone::Offset(0),
+		};
+
+		let anilist_id = match filter {
+			ScheduleFilter::List => {
+				let user_id = user.unwrap_or(command_interaction.user.id);
+
+				RegisteredUser::find_by_id(user_id.to_string())
+					.one(&*db_connection)
+					.await?
+					.ok_or(KasukiError::NotFound(format!(
+						"<@{}> has not linked an AniList account with /register",
+						user_id
+					)))?
+					.anilist_id
+			},
+			ScheduleFilter::Guild if command_interaction.guild_id.is_none() => {
+				return Err(KasukiError::UserInput(
+					"The server filter can only be used in a server".to_string(),
+				)
+				.into());
+			},
+			_ => 0,
+		};
+
+		let page = SchedulePage {
+			start_day: day_of(Utc::now().timestamp(), timezone),
+			days,
+			index: 0,
+			filter,
+			anilist_id,
+			timezone,
+		};
+
+		self.defer().await?;
+
+		let (embed, buttons) = get_schedule_message(
+			&page,
+			command_interaction.guild_id,
+			Some(command_interaction.locale.as_str()),
+			db_connection,
+			bot_data.anilist_client.clone(),
+		)
+		.await?;
+
+		let mut response = CreateInteractionResponseFollowup::new().embed(embed);
+
+		for button in buttons {
+			response = response.button(button);
+		}
+
+		command_interaction
+			.create_followup(&ctx.http, response)
+			.await?;
+
+		Ok(())
+	}
+}
@@ -11,6 +11,7 @@ use crate::command::anilist_user::staff::StaffCommand;
 use crate::command::anilist_user::studio::StudioCommand;
 use crate::command::anilist_user::user::UserCommand;
 use crate::command::command_trait::{Command, SlashCommand};
+use crate::error_management::kasuki_error::KasukiError;
 use crate::helper::get_option::command::get_option_map_string;
 
 pub struct SearchCommand {
@@ -99,7 +100,7 @@ impl SlashCommand for SearchCommand {
 				.await
 			},
 			// Return an error if the specified type is not one of the expected types
-			_ => Err(anyhow!("Type does not exist.")),
+			_ => Err(KasukiError::UserInput("Type does not exist.".to_string()).into()),
 		}
 	}
 }
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use chrono::{Datelike, Utc};
+use serenity::all::{
+	CommandInteraction, Context as SerenityContext, CreateInteractionResponseFollowup,
+};
+
+use crate::command::command_trait::{Command, Embed, SlashCommand};
+use crate::components::anilist::season::{current_season, get_season_message, SeasonPage};
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+use crate::helper::get_option::subcommand::{
+	get_option_map_integer_subcommand, get_option_map_string_subcommand,
+};
+
+pub struct SeasonCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for SeasonCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for SeasonCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let ctx = self.get_ctx();
+
+		let command_interaction = self.get_command_interaction();
+
+		let bot_data = ctx.data::<BotData>().clone();
+
+		let map = get_option_map_string_subcommand(command_interaction);
+
+		let integer_map = get_option_map_integer_subcommand(command_interaction);
+
+		let (season_year, season_now) = current_season(Utc::now().date_naive());
+
+		let season = map.get(&String::from("season")).cloned();
+
+		// The current winter takes the next year in December, a given season takes this year.
+		let year = match integer_map.get(&String::from("year")) {
+			Some(year) => i32::try_from(*year)
+				.map_err(|_| KasukiError::UserInput(format!("{} is not a valid year", year)))?,
+			None if season.is_some() => Utc::now().year(),
+			None => season_year,
+		};
+
+		let option = |name: &str, default: &str| {
+			map.get(&String::from(name))
+				.cloned()
+				.unwrap_or(default.to_string())
+		};
+
+		let page = SeasonPage {
+			year,
+			season: season.unwrap_or(season_now.to_string()),
+			format: option("format", "all"),
+			sort: option("sort", "popularity"),
+			page: 1,
+		};
+
+		// An unknown choice is refused before the defer.
+		page.variables()?;
+
+		self.defer().await?;
+
+		let (embed, components) = get_season_message(
+			&page,
+			command_interaction.guild_id,
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+			bot_data.anilist_client.clone(),
+		)
+		.await?;
+
+		let response = CreateInteractionResponseFollowup::new()
+			.embed(embed)
+			.components(components);
+
+		command_interaction
+			.create_followup(&ctx.http, response)
+			.await?;
+
+		Ok(())
+	}
+}
@@ -1,13 +1,14 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::io::Cursor;
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::message::anilist_user::seiyuu::load_localization_seiyuu;
 use crate::structure::run::anilist::seiyuu_id::{
 	Character, CharacterConnection, SeiyuuId, SeiyuuIdVariables, Staff, StaffImage,
@@ -16,7 +17,6 @@ use crate::structure::run::anilist::seiyuu_search::{SeiyuuSearch, SeiyuuSearchVa
 use cynic::{GraphQlResponse, QueryBuilder};
 use image::imageops::FilterType;
 use image::{DynamicImage, GenericImage, GenericImageView, ImageFormat};
-use moka::future::Cache;
 use prost::bytes::Bytes;
 use serenity::all::CreateInteractionResponse::Defer;
 use serenity::all::{
@@ -24,7 +24,6 @@ use serenity::all::{
 	CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
 };
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 use uuid::Uuid;
 
 pub struct SeiyuuCommand {
@@ -49,17 +48,17 @@ impl SlashCommand for SeiyuuCommand {
 
 		let command_interaction = &self.command_interaction;
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
-		let anilist_cache = bot_data.anilist_cache.clone();
+		let anilist_client = bot_data.anilist_client.clone();
 
-		send_embed(ctx, command_interaction, config, anilist_cache).await
+		send_embed(ctx, command_interaction, db_connection, anilist_client).await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	let map = get_option_map_string(command_interaction);
 
@@ -82,7 +81,7 @@ async fn send_embed(
 		let operation = SeiyuuId::build(var);
 
 		let data: GraphQlResponse<SeiyuuId> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().page.unwrap().staff.unwrap()[0]
 			.clone()
@@ -96,20 +95,24 @@ async fn send_embed(
 		let operation = SeiyuuSearch::build(var);
 
 		let data: GraphQlResponse<SeiyuuSearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		let data = match data.data {
 			Some(data) => match data.page {
 				Some(page) => match page.staff {
 					Some(staff) => match staff[0].clone() {
 						Some(staff) => staff,
-						None => return Err(anyhow!("No staff found")),
+						None => {
+							return Err(KasukiError::NotFound("No staff found".to_string()).into())
+						},
+					},
+					None => {
+						return Err(KasukiError::NotFound("No staff list found".to_string()).into())
 					},
-					None => return Err(anyhow!("No staff list found")),
 				},
-				None => return Err(anyhow!("No page found")),
+				None => return Err(KasukiError::NotFound("No page found".to_string()).into()),
 			},
-			None => return Err(anyhow!("No data found")),
+			None => return Err(KasukiError::NotFound("No data found".to_string()).into()),
 		};
 
 		Staff::from(data)
@@ -120,7 +123,12 @@ async fn send_embed(
 		None => String::from("0"),
 	};
 
-	let seiyuu_localised = load_localization_seiyuu(guild_id, config.db.clone()).await?;
+	let seiyuu_localised = load_localization_seiyuu(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let builder_message = Defer(CreateInteractionResponseMessage::new());
 
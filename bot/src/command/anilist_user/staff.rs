@@ -4,7 +4,7 @@ use crate::command::command_trait::{Command, Embed, EmbedType, SlashCommand};
 use crate::event_handler::BotData;
 use crate::helper::convert_flavored_markdown::convert_anilist_flavored_to_discord_flavored_markdown;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::message::anilist_user::staff::load_localization_staff;
 use crate::structure::run::anilist::staff::{
 	FuzzyDate, Staff, StaffQuerryId, StaffQuerryIdVariables, StaffQuerrySearch,
@@ -12,10 +12,8 @@ use crate::structure::run::anilist::staff::{
 };
 use anyhow::{anyhow, Result};
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 
 pub struct StaffCommand {
 	pub ctx: SerenityContext,
@@ -38,10 +36,10 @@ impl SlashCommand for StaffCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let command_interaction = &self.command_interaction;
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
-		let anilist_cache = bot_data.anilist_cache.clone();
-		let staff = get_staff(command_interaction, anilist_cache).await?;
+		let anilist_client = bot_data.anilist_client.clone();
+		let staff = get_staff(command_interaction, anilist_client).await?;
 
 		let va = staff
 			.characters
@@ -89,7 +87,12 @@ impl SlashCommand for StaffCommand {
 			Some(id) => id.to_string(),
 			None => String::from("0"),
 		};
-		let staff_localised = load_localization_staff(guild_id, config.db.clone()).await?;
+		let staff_localised = load_localization_staff(
+			guild_id,
+			Some(command_interaction.locale.as_str()),
+			db_connection,
+		)
+		.await?;
 
 		let mut fields = vec![
 			(staff_localised.media, media, true),
@@ -148,7 +151,7 @@ impl SlashCommand for StaffCommand {
 }
 
 async fn get_staff(
-	command_interaction: &CommandInteraction, anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	command_interaction: &CommandInteraction, anilist_client: Arc<AnilistClient>,
 ) -> Result<Staff> {
 	let map = get_option_map_string(command_interaction);
 
@@ -164,7 +167,7 @@ async fn get_staff(
 		let operation = StaffQuerryId::build(var);
 
 		let data: GraphQlResponse<StaffQuerryId> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().staff.unwrap()
 	} else {
@@ -175,7 +178,7 @@ async fn get_staff(
 		let operation = StaffQuerrySearch::build(var);
 
 		let data: GraphQlResponse<StaffQuerrySearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().staff.unwrap()
 	};
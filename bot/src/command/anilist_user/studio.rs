@@ -1,26 +1,24 @@
 use crate::command::command_trait::Command;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 use crate::command::command_trait::SlashCommand;
-use crate::config::Config;
 use crate::constant::DEFAULT_STRING;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::message::anilist_user::studio::load_localization_studio;
 use crate::structure::run::anilist::studio::{
 	StudioQuerryId, StudioQuerryIdVariables, StudioQuerrySearch, StudioQuerrySearchVariables,
 };
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage,
 };
 use small_fixed_array::FixedString;
-use tokio::sync::RwLock;
 
 pub struct StudioCommand {
 	pub ctx: SerenityContext,
@@ -43,17 +41,17 @@ impl SlashCommand for StudioCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let command_interaction = &self.command_interaction;
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
-		let anilist_cache = bot_data.anilist_cache.clone();
+		let anilist_client = bot_data.anilist_client.clone();
 
-		send_embed(ctx, command_interaction, config, anilist_cache).await
+		send_embed(ctx, command_interaction, db_connection, anilist_client).await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the name or ID of the studio from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -71,7 +69,7 @@ async fn send_embed(
 		let operation = StudioQuerryId::build(var);
 
 		let data: GraphQlResponse<StudioQuerryId> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().studio.unwrap()
 	} else {
@@ -82,7 +80,7 @@ async fn send_embed(
 		let operation = StudioQuerrySearch::build(var);
 
 		let data: GraphQlResponse<StudioQuerrySearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, false, anilist_client).await?;
 
 		data.data.unwrap().studio.unwrap()
 	};
@@ -94,7 +92,12 @@ async fn send_embed(
 	};
 
 	// Load the localized studio strings
-	let studio_localised = load_localization_studio(guild_id, config.db.clone()).await?;
+	let studio_localised = load_localization_studio(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Initialize a string to store the content of the response
 	let mut content = String::new();
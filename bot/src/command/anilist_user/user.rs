@@ -1,25 +1,23 @@
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::database::prelude::RegisteredUser;
 use crate::database::registered_user::Column;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::get_option::command::get_option_map_string;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 use crate::structure::run::anilist::user;
 use crate::structure::run::anilist::user::{
 	User, UserQueryId, UserQueryIdVariables, UserQuerySearch, UserQuerySearchVariables,
 };
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use sea_orm::ColumnTrait;
+use sea_orm::DatabaseConnection;
 use sea_orm::EntityTrait;
 use sea_orm::QueryFilter;
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 use small_fixed_array::FixedString;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 pub struct UserCommand {
 	pub ctx: SerenityContext,
@@ -42,17 +40,17 @@ impl SlashCommand for UserCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let command_interaction = &self.command_interaction;
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
-		let anilist_cache = bot_data.anilist_cache.clone();
+		let anilist_client = bot_data.anilist_client.clone();
 
-		send_embed(ctx, command_interaction, config, anilist_cache).await
+		send_embed(ctx, command_interaction, db_connection, anilist_client).await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
 ) -> Result<()> {
 	// Retrieve the username from the command interaction
 	let map = get_option_map_string(command_interaction);
@@ -61,33 +59,30 @@ async fn send_embed(
 
 	// If the username is provided, fetch the user's data from AniList and send it as a response
 	if let Some(value) = user {
-		let data: User = get_user(value, anilist_cache.clone()).await?;
+		let data: User = get_user(value, anilist_client.clone()).await?;
 
-		return user::send_embed(ctx, command_interaction, data, config.db.clone()).await;
+		return user::send_embed(ctx, command_interaction, data, db_connection.clone()).await;
 	}
 
 	// If the username is not provided, fetch the data of the user who triggered the command interaction
 	let user_id = &command_interaction.user.id.to_string();
 
-	let connection = sea_orm::Database::connect(get_url(config.db.clone())).await?;
-
 	let row = RegisteredUser::find()
 		.filter(Column::UserId.eq(user_id))
-		.one(&connection)
+		.one(&*db_connection)
 		.await?;
 
-	let user = row.ok_or(anyhow!("No user found"))?;
+	let user = row.ok_or(KasukiError::NotFound("No user found".to_string()))?;
 
 	// Fetch the user's data from AniList and send it as a response
-	let data = get_user(user.anilist_id.to_string().as_str(), anilist_cache).await?;
+	let data = get_user(user.anilist_id.to_string().as_str(), anilist_client).await?;
 
-	user::send_embed(ctx, command_interaction, data, config.db.clone()).await
+	user::send_embed(ctx, command_interaction, data, db_connection.clone()).await
 }
 
-pub async fn get_user(
-	value: &str, anilist_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<User> {
+pub async fn get_user(value: &str, anilist_client: Arc<AnilistClient>) -> Result<User> {
 	// If the value is a valid user ID, fetch the user's data by ID
+	// The statistics change with every list update, so the cache is skipped.
 	let user = if value.parse::<i32>().is_ok() {
 		let id = value.parse::<i32>()?;
 
@@ -96,7 +91,7 @@ pub async fn get_user(
 		let operation = UserQueryId::build(var);
 
 		let data: GraphQlResponse<UserQueryId> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, true, anilist_client).await?;
 
 		data.data.unwrap().user.unwrap()
 	} else {
@@ -108,7 +103,7 @@ pub async fn get_user(
 		let operation = UserQuerySearch::build(var);
 
 		let data: GraphQlResponse<UserQuerySearch> =
-			make_request_anilist(operation, false, anilist_cache).await?;
+			make_request_anilist(operation, true, anilist_client).await?;
 
 		data.data.unwrap().user.unwrap()
 	};
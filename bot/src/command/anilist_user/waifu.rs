@@ -28,17 +28,17 @@ impl SlashCommand for WaifuCommand {
 		let bot_data = ctx.data::<BotData>().clone();
 		let command_interaction = &self.command_interaction;
 
-		let config = bot_data.config.clone();
+		let db_connection = bot_data.db_connection.clone();
 
-		let anilist_cache = bot_data.anilist_cache.clone();
+		let anilist_client = bot_data.anilist_client.clone();
 
 		// Execute the corresponding search function based on the specified type
 		// Fetch the data of the character with ID 156323 from AniList
 		let value = 156323;
 
-		let data = get_character_by_id(value, anilist_cache).await?;
+		let data = get_character_by_id(value, anilist_client).await?;
 
 		// Send the character's data as a response to the command interaction
-		send_embed(ctx, command_interaction, data, config.db.clone()).await
+		send_embed(ctx, command_interaction, data, db_connection).await
 	}
 }
@@ -1,11 +1,12 @@
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::subcommand::get_option_map_string_subcommand;
 use crate::structure::message::anime::random_image::load_localization_random_image;
 use anyhow::{anyhow, Result};
 use image::EncodableLayout;
+use sea_orm::DatabaseConnection;
 use serenity::all::CreateInteractionResponse::Defer;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateAttachment,
@@ -36,14 +37,15 @@ impl SlashCommand for AnimeRandomImageCommand {
 		send(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the type of image to fetch from the command interaction
 	let map = get_option_map_string_subcommand(command_interaction);
@@ -59,8 +61,12 @@ async fn send(
 	};
 
 	// Load the localized random image strings
-	let random_image_localised =
-		load_localization_random_image(guild_id, config.db.clone()).await?;
+	let random_image_localised = load_localization_random_image(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Create a deferred response to the command interaction
 	let builder_message = Defer(CreateInteractionResponseMessage::new());
@@ -96,7 +102,7 @@ pub async fn send_embed(
 	// Retrieve the URL of the image from the JSON
 	let image_url = json["url"]
 		.as_str()
-		.ok_or(anyhow!("No image found"))?
+		.ok_or(KasukiError::NotFound("No image found".to_string()))?
 		.to_string();
 
 	// Fetch the image from the image URL
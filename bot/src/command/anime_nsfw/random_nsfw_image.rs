@@ -1,9 +1,9 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 use crate::command::anime::random_image::send_embed;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::get_option::subcommand::get_option_map_string_subcommand;
 use crate::structure::message::anime_nsfw::random_image_nsfw::load_localization_random_image_nsfw;
@@ -34,14 +34,15 @@ impl SlashCommand for AnimeRandomNsfwImageCommand {
 		send(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the type of image to fetch from the command interaction
 	let map = get_option_map_string_subcommand(command_interaction);
@@ -57,8 +58,12 @@ async fn send(
 	};
 
 	// Load the localized random NSFW image strings
-	let random_image_nsfw_localised =
-		load_localization_random_image_nsfw(guild_id, config.db.clone()).await?;
+	let random_image_nsfw_localised = load_localization_random_image_nsfw(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Create a deferred response to the command interaction
 	let builder_message = Defer(CreateInteractionResponseMessage::new());
@@ -1,10 +1,10 @@
 use crate::audio::receiver::Receiver;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::audio::join::load_localization_join_localised;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::{CommandInteraction, Context as SerenityContext, CreateEmbed};
 use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
 use songbird::CoreEvent;
@@ -33,14 +33,15 @@ impl SlashCommand for AudioJoinCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = command_interaction.guild_id.ok_or(anyhow!("No guild id"))?;
 
@@ -52,8 +53,12 @@ async fn send_embed(
 
 	let cache = ctx.cache.clone();
 
-	let localised =
-		load_localization_join_localised(guild_id.to_string(), config.db.clone()).await?;
+	let localised = load_localization_join_localised(
+		guild_id.to_string(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	if manager.get(guild_id).is_none() {
 		let channel_id;
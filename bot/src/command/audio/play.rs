@@ -1,12 +1,12 @@
 use crate::audio::receiver::{Receiver, TrackEndNotifier, TrackErrorNotifier};
 use crate::audio::rusty_ytdl::RustyYoutubeSearch;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::subcommand::get_option_map_string_subcommand;
 use crate::structure::message::audio::play::load_localization_play_localised;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::{CommandInteraction, CreateInteractionResponseFollowup};
 use serenity::builder::CreateInteractionResponse::Defer;
 use serenity::builder::CreateInteractionResponseMessage;
@@ -39,14 +39,15 @@ impl SlashCommand for AudioPlayCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let map = get_option_map_string_subcommand(command_interaction);
 
@@ -59,8 +60,12 @@ async fn send_embed(
 
 	let cache = ctx.cache.clone();
 
-	let localised =
-		load_localization_play_localised(guild_id.to_string(), config.db.clone()).await?;
+	let localised = load_localization_play_localised(
+		guild_id.to_string(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let bot_data = ctx.data::<BotData>().clone();
 
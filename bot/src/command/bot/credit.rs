@@ -1,8 +1,8 @@
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::bot::credit::load_localization_credit;
@@ -33,14 +33,15 @@ impl SlashCommand for CreditCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction or use "0" if it does not exist
 	let guild_id = match command_interaction.guild_id {
@@ -49,7 +50,12 @@ async fn send_embed(
 	};
 
 	// Load the localized strings for the credits
-	let credit_localised = load_localization_credit(guild_id, config.db.clone()).await?;
+	let credit_localised = load_localization_credit(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Construct a description by concatenating the descriptions of all credits
 	let mut desc: String = "".to_string();
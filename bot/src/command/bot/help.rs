@@ -0,0 +1,102 @@
+use anyhow::Result;
+
+use crate::command::command_dispatch::check_if_module_is_on;
+use crate::command::command_trait::{Command, SlashCommand};
+use crate::command::registry::{InteractionKind, COMMANDS};
+use crate::event_handler::BotData;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::structure::message::bot::help::load_localization_help;
+use serenity::all::{
+	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
+	CreateInteractionResponseMessage,
+};
+
+pub struct HelpCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for HelpCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for HelpCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let ctx = self.get_ctx();
+		let bot_data = ctx.data::<BotData>().clone();
+		let command_interaction = self.get_command_interaction();
+
+		let guild_id = match command_interaction.guild_id {
+			Some(id) => id.to_string(),
+			None => String::from("0"),
+		};
+
+		let help_localised = load_localization_help(
+			guild_id.clone(),
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+		)
+		.await?;
+
+		// the global slash commands grouped by module, in the registry order, without the ones
+		// reserved to the owner
+		let mut modules: Vec<(Option<&str>, Vec<String>)> = Vec::new();
+
+		for command in COMMANDS
+			.iter()
+			.filter(|command| command.kind == InteractionKind::Slash && !command.owner_only)
+		{
+			match modules
+				.iter_mut()
+				.find(|(module, _)| *module == command.module)
+			{
+				Some((_, names)) => names.push(command.display_name()),
+				None => modules.push((command.module, vec![command.display_name()])),
+			}
+		}
+
+		let mut fields = Vec::new();
+
+		for (module, names) in modules {
+			let title = match module {
+				Some(module) => {
+					let is_on = check_if_module_is_on(
+						guild_id.clone(),
+						module,
+						bot_data.db_connection.clone(),
+					)
+					.await?;
+
+					if is_on {
+						module.to_string()
+					} else {
+						format!("{} ({})", module, help_localised.disabled)
+					}
+				},
+				None => help_localised.general.clone(),
+			};
+
+			fields.push((title, names.join(", "), false));
+		}
+
+		let builder_embed = get_default_embed(None)
+			.title(help_localised.title)
+			.fields(fields);
+
+		let builder_message = CreateInteractionResponseMessage::new().embed(builder_embed);
+
+		let builder = CreateInteractionResponse::Message(builder_message);
+
+		command_interaction
+			.create_response(&ctx.http, builder)
+			.await?;
+
+		Ok(())
+	}
+}
@@ -1,12 +1,11 @@
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::constant::{APP_VERSION, LIBRARY};
 use crate::database::prelude::UserColor;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::bot::info::load_localization_info;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use sea_orm::EntityTrait;
 use serenity::all::{
 	ButtonStyle, CommandInteraction, Context as SerenityContext, CreateActionRow, CreateButton,
@@ -37,14 +36,15 @@ impl SlashCommand for InfoCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -53,16 +53,19 @@ async fn send_embed(
 	};
 
 	// Load the localized information strings
-	let info_localised = load_localization_info(guild_id, config.db.clone()).await?;
+	let info_localised = load_localization_info(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Retrieve various details about the bot and the server
 	let shard_count = ctx.cache.shard_count();
 
 	let shard = ctx.shard_id.to_string();
 
-	let connection = sea_orm::Database::connect(get_url(config.db.clone())).await?;
-
-	let user_count = UserColor::find().all(&connection).await?.len();
+	let user_count = UserColor::find().all(&*db_connection).await?.len();
 
 	let bot = ctx.http.get_current_application_info().await?;
 
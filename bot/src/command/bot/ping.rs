@@ -1,9 +1,9 @@
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::bot::ping::load_localization_ping;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage,
@@ -31,14 +31,15 @@ impl SlashCommand for PingCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -47,7 +48,12 @@ async fn send_embed(
 	};
 
 	// Load the localized ping strings
-	let ping_localised = load_localization_ping(guild_id, config.db.clone()).await?;
+	let ping_localised = load_localization_ping(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let guard = ctx.data::<BotData>().shard_manager.clone();
 	let shard_manager = match guard.read().await.clone() {
@@ -0,0 +1,101 @@
+use crate::command::command_trait::{Command, SlashCommand};
+use crate::event_handler::BotData;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::structure::message::bot::tasks::load_localization_tasks;
+use anyhow::Result;
+use serenity::all::{
+	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
+	CreateInteractionResponseMessage,
+};
+
+/// Max length of the error shown for a task, an embed is limited to 6000 characters.
+const MAX_ERROR_LENGTH: usize = 300;
+
+pub struct TasksCommand {
+	pub ctx: SerenityContext,
+	pub command_interaction: CommandInteraction,
+}
+
+impl Command for TasksCommand {
+	fn get_ctx(&self) -> &SerenityContext {
+		&self.ctx
+	}
+
+	fn get_command_interaction(&self) -> &CommandInteraction {
+		&self.command_interaction
+	}
+}
+
+impl SlashCommand for TasksCommand {
+	async fn run_slash(&self) -> Result<()> {
+		let ctx = self.get_ctx();
+		let bot_data = ctx.data::<BotData>().clone();
+		let command_interaction = self.get_command_interaction();
+
+		let guild_id = match command_interaction.guild_id {
+			Some(id) => id.to_string(),
+			None => String::from("0"),
+		};
+
+		let tasks_localised = load_localization_tasks(
+			guild_id,
+			Some(command_interaction.locale.as_str()),
+			bot_data.db_connection.clone(),
+		)
+		.await?;
+
+		let mut fields = Vec::new();
+
+		for (name, status) in bot_data.tasks.status().await {
+			let state = if status.running {
+				&tasks_localised.running
+			} else {
+				&tasks_localised.idle
+			};
+
+			let last_run = match (status.last_run, status.last_duration) {
+				(Some(last_run), Some(duration)) => tasks_localised
+					.last_run
+					.replace("$time$", &format!("<t:{}:R>", last_run.timestamp()))
+					.replace("$duration$", &format!("{:.2?}", duration)),
+				_ => tasks_localised.never.clone(),
+			};
+
+			let mut value = format!(
+				"{}\n{}\n{}",
+				state,
+				last_run,
+				tasks_localised
+					.interval
+					.replace("$interval$", &status.interval.to_string())
+					.replace("$restarts$", &status.restarts.to_string())
+			);
+
+			if let Some(error) = status.last_error {
+				let error: String = error.chars().take(MAX_ERROR_LENGTH).collect();
+
+				value.push('\n');
+
+				value.push_str(&tasks_localised.error.replace("$error$", &error));
+			}
+
+			fields.push((name, value, false));
+		}
+
+		let builder_embed = get_default_embed(None)
+			.title(&tasks_localised.title)
+			.fields(fields);
+
+		let builder_message = CreateInteractionResponseMessage::new()
+			.embed(builder_embed)
+			.ephemeral(true);
+
+		let builder = CreateInteractionResponse::Message(builder_message);
+
+		command_interaction
+			.create_response(&ctx.http, builder)
+			.await?;
+
+		Ok(())
+	}
+}
@@ -1,66 +1,25 @@
-use crate::command::admin::anilist::add_activity::AddActivityCommand;
-use crate::command::admin::anilist::delete_activity::DeleteActivityCommand;
-use crate::command::admin::server::lang::LangCommand;
-use crate::command::admin::server::module::{check_activation_status, ModuleCommand};
-use crate::command::admin::server::new_member_setting::NewMemberSettingCommand;
-use crate::command::ai::image::ImageCommand;
-use crate::command::ai::question::QuestionCommand;
-use crate::command::ai::transcript::TranscriptCommand;
-use crate::command::ai::translation::TranslationCommand;
-use crate::command::anilist_server::list_all_activity::ListAllActivity;
-use crate::command::anilist_server::list_register_user::ListRegisterUser;
-use crate::command::anilist_user::anime::AnimeCommand;
-use crate::command::anilist_user::character::CharacterCommand;
-use crate::command::anilist_user::compare::CompareCommand;
-use crate::command::anilist_user::level::LevelCommand;
-use crate::command::anilist_user::ln::LnCommand;
-use crate::command::anilist_user::manga::MangaCommand;
-use crate::command::anilist_user::random::RandomCommand;
-use crate::command::anilist_user::register::RegisterCommand;
-use crate::command::anilist_user::search::SearchCommand;
-use crate::command::anilist_user::seiyuu::SeiyuuCommand;
-use crate::command::anilist_user::staff::StaffCommand;
-use crate::command::anilist_user::studio::StudioCommand;
-use crate::command::anilist_user::user::UserCommand;
-use crate::command::anilist_user::waifu::WaifuCommand;
-use crate::command::anime::random_image::AnimeRandomImageCommand;
-use crate::command::anime_nsfw::random_nsfw_image::AnimeRandomNsfwImageCommand;
-use crate::command::audio::join::AudioJoinCommand;
-use crate::command::audio::play::AudioPlayCommand;
-use crate::command::bot::credit::CreditCommand;
-use crate::command::bot::info::InfoCommand;
-use crate::command::bot::ping::PingCommand;
-use crate::command::command_trait::SlashCommand;
+use crate::command::admin::server::module::check_activation_status;
+use crate::command::command_trait::check_hourly_limit;
 use crate::command::guess_kind::guess_command_kind;
-use crate::command::management::give_premium_sub::GivePremiumSubCommand;
-use crate::command::management::kill_switch::KillSwitchCommand;
-use crate::command::management::remove_test_sub::RemoveTestSubCommand;
-use crate::command::server::generate_image_pfp_server::GenerateImagePfPCommand;
-use crate::command::server::generate_image_pfp_server_global::GenerateGlobalImagePfPCommand;
-use crate::command::server::guild::GuildCommand;
-use crate::command::steam::steam_game_info::SteamGameInfoCommand;
-use crate::command::user::avatar::AvatarCommand;
-use crate::command::user::banner::BannerCommand;
-use crate::command::user::command_usage::CommandUsageCommand;
-use crate::command::user::profile::ProfileCommand;
-use crate::command::vn::character::VnCharacterCommand;
-use crate::command::vn::game::VnGameCommand;
-use crate::command::vn::producer::VnProducerCommand;
-use crate::command::vn::staff::VnStaffCommand;
-use crate::command::vn::stats::VnStatsCommand;
-use crate::command::vn::user::VnUserCommand;
-use crate::config::DbConfig;
+use crate::command::registry::{get_command, CommandEntry, InteractionKind};
 use crate::database;
 use crate::database::module_activation::Model;
-use crate::database::prelude::ModuleActivation;
+use crate::database::prelude::{KillSwitch, ModuleActivation};
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
-use crate::get_url;
-use anyhow::Result;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::metrics::METRICS;
+use crate::structure::message::module_disabled::load_localization_module_disabled;
+use anyhow::{anyhow, Result};
 use sea_orm::ColumnTrait;
-use sea_orm::{EntityTrait, QueryFilter};
-use serenity::all::{CommandInteraction, Context as SerenityContext};
-use std::error::Error;
-use tracing::trace;
+use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter};
+use serenity::all::{
+	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
+	CreateInteractionResponseMessage, UserId,
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, trace};
 
 pub async fn dispatch_command(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction,
@@ -72,443 +31,164 @@ pub async fn dispatch_command(
 
 	trace!("Running command: {}", full_command_name);
 
-	match name.as_str() {
-		"user_avatar" => {
-			AvatarCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"user_banner" => {
-			BannerCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"user_profile" => {
-			ProfileCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"user_command_usage" => {
-			CommandUsageCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"admin_general_lang" => {
-			LangCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"admin_general_module" => {
-			ModuleCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"admin_general_new_member_setting" => {
-			NewMemberSettingCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"admin_anilist_add_activity" => {
-			AddActivityCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"admin_anilist_delete_activity" => {
-			DeleteActivityCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"steam_game" => {
-			SteamGameInfoCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"ai_image" => {
-			ImageCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-				command_name: full_command_name.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"ai_question" => {
-			QuestionCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-				command_name: full_command_name.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"ai_transcript" => {
-			TranscriptCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-				command_name: full_command_name.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"ai_translation" => {
-			TranslationCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-				command_name: full_command_name.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"list_user" => {
-			ListRegisterUser {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"list_activity" => {
-			ListAllActivity {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"anime" => {
-			AnimeCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"character" => {
-			CharacterCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"compare" => {
-			CompareCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"level" => {
-			LevelCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"ln" => {
-			LnCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"manga" => {
-			MangaCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"anilist_user" => {
-			UserCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"waifu" => {
-			WaifuCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"random" => {
-			RandomCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"register" => {
-			RegisterCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"staff" => {
-			StaffCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"studio" => {
-			StudioCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"search" => {
-			SearchCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"seiyuu" => {
-			SeiyuuCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"random_anime_random_image" => {
-			AnimeRandomImageCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"random_hanime_random_himage" => {
-			AnimeRandomNsfwImageCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"audio_join" => {
-			AudioJoinCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"audio_play" => {
-			AudioPlayCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"bot_credit" => {
-			CreditCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"bot_info" => {
-			InfoCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"bot_ping" => {
-			PingCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"kill_switch" => {
-			KillSwitchCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"give_premium_sub" => {
-			GivePremiumSubCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"remove_test_sub" => {
-			RemoveTestSubCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"server_guild" => {
-			GuildCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"server_guild_image" => {
-			GenerateImagePfPCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"server_guild_image_g" => {
-			GenerateGlobalImagePfPCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-
-		"vn_game" => {
-			VnGameCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"vn_character" => {
-			VnCharacterCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"vn_staff" => {
-			VnStaffCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"vn_user" => {
-			VnUserCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"vn_producer" => {
-			VnProducerCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		"vn_stats" => {
-			VnStatsCommand {
-				ctx: ctx.clone(),
-				command_interaction: command_interaction.clone(),
-			}
-			.run_slash()
-			.await?
-		},
-		_ => {
-			Err(anyhow::anyhow!("Command not found"))?;
-		},
-	};
+	let command =
+		get_command(InteractionKind::Slash, name.as_str()).ok_or(anyhow!("Command not found"))?;
 
-	bot_data
-		.increment_command_use_per_command(
-			full_command_name,
-			command_interaction.user.id.to_string(),
-			command_interaction.user.name.to_string(),
-		)
-		.await;
+	if !run_command(ctx, command_interaction, command, full_command_name.clone()).await? {
+		return Ok(());
+	}
+
+	if let Err(e) = bot_data
+		.record_command_usage(full_command_name, command_interaction.user.id.to_string())
+		.await
+	{
+		error!("Failed to record the command usage. {:#}", e);
+	}
 
 	Ok(())
 }
 
+/// Runs a command from the registry after checking its owner restriction, its module and its
+/// hourly limit. Returns `false` when the command was not run because its module is disabled.
+
+pub async fn run_command(
+	ctx: &SerenityContext, command_interaction: &CommandInteraction, command: &CommandEntry,
+	command_name: String,
+) -> Result<bool> {
+	let bot_data = ctx.data::<BotData>().clone();
+
+	if command.owner_only && !is_bot_owner(ctx, command_interaction.user.id).await? {
+		return Err(KasukiError::Permission(
+			"Only the owner of the bot can use this command.".to_string(),
+		)
+		.into());
+	}
+
+	if let Some(module) = command.module {
+		if !check_command_module(
+			ctx,
+			command_interaction,
+			module,
+			bot_data.db_connection.clone(),
+		)
+		.await?
+		{
+			return Ok(false);
+		}
+	}
+
+	if let Some(premium) = command.premium {
+		if check_hourly_limit(ctx, command_interaction, command_name, premium).await? {
+			return Err(KasukiError::PremiumLimit(
+				"You have reached your hourly limit. Please try again later.".to_string(),
+			)
+			.into());
+		}
+	}
+
+	let start = Instant::now();
+
+	let result = (command.run)(ctx.clone(), command_interaction.clone()).await;
+
+	METRICS.record_command(command.name(), start.elapsed());
+
+	result?;
+
+	Ok(true)
+}
+
+/// Whether the user owns the bot application or is a member of its team.
+
+pub async fn is_bot_owner(ctx: &SerenityContext, user_id: UserId) -> Result<bool> {
+	let bot_data = ctx.data::<BotData>().clone();
+
+	let cached = bot_data.bot_info.read().await.clone();
+
+	// The bot info is refreshed by a background task, it may not be set yet.
+	let bot_info = match cached {
+		Some(bot_info) => bot_info,
+		None => ctx.http.get_current_application_info().await?,
+	};
+
+	if bot_info.owner.is_some_and(|owner| owner.id == user_id) {
+		return Ok(true);
+	}
+
+	Ok(bot_info
+		.team
+		.is_some_and(|team| team.members.iter().any(|member| member.user.id == user_id)))
+}
+
+/// Checks that the module is enabled for the guild and not turned off by the kill switch.
+/// When it is not, replies with the localized module disabled message and returns `false`.
+
+async fn check_command_module(
+	ctx: &SerenityContext, command_interaction: &CommandInteraction, module: &str,
+	db_connection: Arc<DatabaseConnection>,
+) -> Result<bool> {
+	let guild_id = match command_interaction.guild_id {
+		Some(id) => id.to_string(),
+		None => String::from("0"),
+	};
+
+	let kill_switch_state = check_kill_switch_status(module, db_connection.clone()).await?;
+
+	let guild_state =
+		check_guild_module_status(guild_id.clone(), module, db_connection.clone()).await?;
+
+	if kill_switch_state && guild_state {
+		return Ok(true);
+	}
+
+	let module_disabled_localised = load_localization_module_disabled(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
+
+	let description = if kill_switch_state {
+		module_disabled_localised.disabled
+	} else {
+		module_disabled_localised.kill_switch
+	};
+
+	let builder_embed = get_default_embed(None)
+		.title(module_disabled_localised.title)
+		.description(description.replace("$module$", module));
+
+	let builder_message = CreateInteractionResponseMessage::new()
+		.embed(builder_embed)
+		.ephemeral(true);
+
+	let builder = CreateInteractionResponse::Message(builder_message);
+
+	command_interaction
+		.create_response(&ctx.http, builder)
+		.await?;
+
+	Ok(false)
+}
+
 pub async fn check_if_module_is_on(
-	guild_id: String, module: &str, db_config: DbConfig,
-) -> Result<bool, Box<dyn Error>> {
-	let connection = sea_orm::Database::connect(get_url(db_config.clone())).await?;
+	guild_id: String, module: &str, db_connection: Arc<DatabaseConnection>,
+) -> Result<bool> {
+	let state = check_guild_module_status(guild_id, module, db_connection.clone()).await?;
+
+	let state = state && check_kill_switch_status(module, db_connection).await?;
+
+	Ok(state)
+}
 
+async fn check_guild_module_status(
+	guild_id: String, module: &str, db_connection: Arc<DatabaseConnection>,
+) -> Result<bool> {
 	let row = ModuleActivation::find()
 		.filter(database::module_activation::Column::GuildId.eq(guild_id.clone()))
-		.one(&connection)
+		.one(&*db_connection)
 		.await?
 		.unwrap_or(Model {
-			guild_id: guild_id.clone(),
+			guild_id,
 			ai_module: true,
 			anilist_module: true,
 			game_module: true,
@@ -518,28 +198,24 @@ pub async fn check_if_module_is_on(
 			updated_at: Default::default(),
 		});
 
-	let state = check_activation_status(module, row).await;
-
-	let state = state && check_kill_switch_status(module, db_config, guild_id).await?;
-
-	Ok(state)
+	Ok(check_activation_status(module, row).await)
 }
 
-async fn check_kill_switch_status(
-	module: &str, db_config: DbConfig, guild_id: String,
-) -> Result<bool, Box<dyn Error>> {
-	let connection = sea_orm::Database::connect(get_url(db_config.clone())).await?;
-
-	let row = ModuleActivation::find()
-		.filter(database::kill_switch::Column::GuildId.eq(guild_id.clone()))
-		.one(&connection)
+// The kill switch is a single row with the guild id 0 that applies to every guild, every
+// module is on when it does not exist yet.
+pub async fn check_kill_switch_status(
+	module: &str, db_connection: Arc<DatabaseConnection>,
+) -> Result<bool> {
+	let row = KillSwitch::find()
+		.filter(database::kill_switch::Column::GuildId.eq("0"))
+		.one(&*db_connection)
 		.await?
-		.unwrap_or(Model {
-			guild_id,
+		.unwrap_or(database::kill_switch::Model {
+			guild_id: String::from("0"),
 			ai_module: true,
 			anilist_module: true,
 			game_module: true,
-			new_members_module: false,
+			new_members_module: true,
 			anime_module: true,
 			vn_module: true,
 			updated_at: Default::default(),
@@ -547,5 +223,16 @@ async fn check_kill_switch_status(
 
 	trace!(?row);
 
+	let row = Model {
+		guild_id: row.guild_id,
+		ai_module: row.ai_module,
+		anilist_module: row.anilist_module,
+		game_module: row.game_module,
+		new_members_module: row.new_members_module,
+		anime_module: row.anime_module,
+		vn_module: row.vn_module,
+		updated_at: row.updated_at,
+	};
+
 	Ok(check_activation_status(module, row).await)
 }
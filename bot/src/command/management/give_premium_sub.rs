@@ -1,8 +1,8 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::{get_option_map_string, get_option_map_user};
@@ -35,14 +35,15 @@ impl SlashCommand for GivePremiumSubCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let map = get_option_map_user(command_interaction);
 
@@ -80,7 +81,8 @@ async fn send_embed(
 
 	let localization = load_localization_give_premium_sub(
 		command_interaction.guild_id.unwrap().to_string(),
-		config.db.clone(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
 	)
 	.await?;
 
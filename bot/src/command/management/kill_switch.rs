@@ -1,17 +1,19 @@
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
-use crate::database::kill_switch::{ActiveModel, Column};
+use crate::database::kill_switch::{ActiveModel, Column, Model};
 use crate::database::prelude::KillSwitch;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::{get_option_map_boolean, get_option_map_string};
 use crate::structure::message::management::kill_switch::load_localization_kill_switch;
 use anyhow::{anyhow, Result};
-use sea_orm::ActiveModelTrait;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::ActiveValue::Set;
 use sea_orm::ColumnTrait;
+use sea_orm::DatabaseConnection;
+use sea_orm::EntityTrait;
 use sea_orm::QueryFilter;
-use sea_orm::{EntityTrait, IntoActiveModel};
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage,
@@ -41,14 +43,15 @@ impl SlashCommand for KillSwitchCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = match command_interaction.guild_id {
 		Some(id) => id.to_string(),
@@ -61,8 +64,12 @@ async fn send_embed(
 		.get(&FixedString::from_str_trunc("name"))
 		.ok_or(anyhow!("No option for name"))?;
 
-	let module_localised =
-		load_localization_kill_switch(guild_id.clone(), config.db.clone()).await?;
+	let module_localised = load_localization_kill_switch(
+		guild_id.clone(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let map = get_option_map_boolean(command_interaction);
 
@@ -70,13 +77,20 @@ async fn send_embed(
 		.get(&FixedString::from_str_trunc("state"))
 		.ok_or(anyhow!("No option for state"))?;
 
-	let connection = sea_orm::Database::connect(get_url(config.db.clone())).await?;
-
 	let mut row = KillSwitch::find()
 		.filter(Column::GuildId.eq("0"))
-		.one(&connection)
+		.one(&*db_connection)
 		.await?
-		.ok_or(anyhow!("KillSwitch not found"))?;
+		.unwrap_or(Model {
+			guild_id: String::from("0"),
+			ai_module: true,
+			anilist_module: true,
+			game_module: true,
+			new_members_module: true,
+			anime_module: true,
+			vn_module: true,
+			updated_at: Default::default(),
+		});
 
 	match module.as_str() {
 		"ANILIST" => row.anilist_module = state,
@@ -86,13 +100,38 @@ async fn send_embed(
 		"ANIME" => row.anime_module = state,
 		"VN" => row.vn_module = state,
 		_ => {
-			return Err(anyhow!("The module specified does not exist"));
+			return Err(
+				KasukiError::UserInput("The module specified does not exist".to_string()).into(),
+			);
 		},
 	}
 
-	let active_model: ActiveModel = row.into_active_model();
-
-	active_model.update(&connection).await?;
+	// the row may not exist yet, so it is inserted or updated
+	KillSwitch::insert(ActiveModel {
+		guild_id: Set(row.guild_id),
+		ai_module: Set(row.ai_module),
+		anilist_module: Set(row.anilist_module),
+		game_module: Set(row.game_module),
+		new_members_module: Set(row.new_members_module),
+		anime_module: Set(row.anime_module),
+		vn_module: Set(row.vn_module),
+		updated_at: Set(Utc::now().naive_utc()),
+	})
+	.on_conflict(
+		OnConflict::column(Column::GuildId)
+			.update_columns([
+				Column::AiModule,
+				Column::AnilistModule,
+				Column::GameModule,
+				Column::NewMembersModule,
+				Column::AnimeModule,
+				Column::VnModule,
+				Column::UpdatedAt,
+			])
+			.to_owned(),
+	)
+	.exec(&*db_connection)
+	.await?;
 
 	let desc = if state {
 		&module_localised.on
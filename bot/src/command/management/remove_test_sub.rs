@@ -1,10 +1,10 @@
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::command::get_option_map_user;
 use crate::structure::message::management::remove_test_sub::load_localization_remove_test_sub;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::CreateInteractionResponse::Defer;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponseFollowup,
@@ -36,14 +36,15 @@ impl SlashCommand for RemoveTestSubCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let map = get_option_map_user(command_interaction);
 
@@ -63,7 +64,8 @@ async fn send_embed(
 
 	let localization = load_localization_remove_test_sub(
 		command_interaction.guild_id.unwrap().to_string(),
-		config.db.clone(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
 	)
 	.await?;
 
@@ -1,9 +1,25 @@
 use anyhow::{anyhow, Result};
+
+use crate::command::command_dispatch::run_command;
+use crate::command::registry::{get_command, InteractionKind};
 use serenity::all::{CommandInteraction, Context as SerenityContext};
+
 pub async fn dispatch_message_command(
-	_ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
 ) -> Result<()> {
-	match command_interaction.data.name.as_str() {
-		_ => Err(anyhow!("Unknown command")),
-	}
+	let command = get_command(
+		InteractionKind::Message,
+		command_interaction.data.name.as_str(),
+	)
+	.ok_or(anyhow!("Unknown command"))?;
+
+	run_command(
+		ctx,
+		command_interaction,
+		command,
+		command_interaction.data.name.to_string(),
+	)
+	.await?;
+
+	Ok(())
 }
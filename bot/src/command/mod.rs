@@ -12,7 +12,8 @@ pub mod audio;
 pub mod bot;
 pub mod command_dispatch;
 pub mod management;
-mod message_command_dispatch;
+pub mod message_command_dispatch;
+pub mod registry;
 pub mod server;
 pub mod steam;
 pub mod user_command_dispatch;
@@ -0,0 +1,503 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::autocomplete::anilist_server::{
+	add_anime_activity, add_manga_activity, delete_activity,
+};
+use crate::autocomplete::anilist_user::{
+	anime, character, compare, ln, manga, search, staff, studio, user,
+};
+use crate::autocomplete::game::steam_game_info;
+use crate::autocomplete::management::give_premium_sub::give_premium_sub_autocomplete;
+use crate::autocomplete::vn;
+use crate::command::admin::anilist::add_activity::AddActivityCommand;
+use crate::command::admin::anilist::add_manga_activity::AddMangaActivityCommand;
+use crate::command::admin::anilist::delete_activity::DeleteActivityCommand;
+use crate::command::admin::anilist::follow_user::FollowUserCommand;
+use crate::command::admin::anilist::unfollow_user::UnfollowUserCommand;
+use crate::command::admin::server::lang::LangCommand;
+use crate::command::admin::server::module::ModuleCommand;
+use crate::command::admin::server::new_member_setting::NewMemberSettingCommand;
+use crate::command::ai::image::ImageCommand;
+use crate::command::ai::question::QuestionCommand;
+use crate::command::ai::transcript::TranscriptCommand;
+use crate::command::ai::translation::TranslationCommand;
+use crate::command::anilist_server::list_all_activity::ListAllActivity;
+use crate::command::anilist_server::list_register_user::ListRegisterUser;
+use crate::command::anilist_user::anime::AnimeCommand;
+use crate::command::anilist_user::character::CharacterCommand;
+use crate::command::anilist_user::compare::CompareCommand;
+use crate::command::anilist_user::level::LevelCommand;
+use crate::command::anilist_user::ln::LnCommand;
+use crate::command::anilist_user::manga::MangaCommand;
+use crate::command::anilist_user::random::RandomCommand;
+use crate::command::anilist_user::register::RegisterCommand;
+use crate::command::anilist_user::schedule::ScheduleCommand;
+use crate::command::anilist_user::search::SearchCommand;
+use crate::command::anilist_user::season::SeasonCommand;
+use crate::command::anilist_user::seiyuu::SeiyuuCommand;
+use crate::command::anilist_user::staff::StaffCommand;
+use crate::command::anilist_user::studio::StudioCommand;
+use crate::command::anilist_user::user::UserCommand as AnilistUserCommand;
+use crate::command::anilist_user::waifu::WaifuCommand;
+use crate::command::anime::random_image::AnimeRandomImageCommand;
+use crate::command::anime_nsfw::random_nsfw_image::AnimeRandomNsfwImageCommand;
+use crate::command::audio::join::AudioJoinCommand;
+use crate::command::audio::play::AudioPlayCommand;
+use crate::command::bot::credit::CreditCommand;
+use crate::command::bot::help::HelpCommand;
+use crate::command::bot::info::InfoCommand;
+use crate::command::bot::ping::PingCommand;
+use crate::command::bot::tasks::TasksCommand;
+use crate::command::command_trait::{PremiumCommandType, SlashCommand, UserCommand};
+use crate::command::management::give_premium_sub::GivePremiumSubCommand;
+use crate::command::management::kill_switch::KillSwitchCommand;
+use crate::command::management::remove_test_sub::RemoveTestSubCommand;
+use crate::command::server::generate_image_pfp_server::GenerateImagePfPCommand;
+use crate::command::server::generate_image_pfp_server_global::GenerateGlobalImagePfPCommand;
+use crate::command::server::guild::GuildCommand;
+use crate::command::steam::steam_game_info::SteamGameInfoCommand;
+use crate::command::user::avatar::AvatarCommand;
+use crate::command::user::banner::BannerCommand;
+use crate::command::user::command_usage::CommandUsageCommand;
+use crate::command::user::profile::ProfileCommand;
+use crate::command::vn::character::VnCharacterCommand;
+use crate::command::vn::game::VnGameCommand;
+use crate::command::vn::producer::VnProducerCommand;
+use crate::command::vn::staff::VnStaffCommand;
+use crate::command::vn::stats::VnStatsCommand;
+use crate::command::vn::user::VnUserCommand;
+use anyhow::Result;
+use serenity::all::{CommandInteraction, Context as SerenityContext};
+
+pub type CommandFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+pub type AutocompleteFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+pub type CommandRunner = fn(SerenityContext, CommandInteraction) -> CommandFuture;
+
+pub type AutocompleteRunner = fn(SerenityContext, CommandInteraction) -> AutocompleteFuture;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+
+pub enum InteractionKind {
+	/// Slash command registered globally, from `json/command`, `json/subcommand` or
+	/// `json/subcommand_group`.
+	Slash,
+	/// Slash command only registered on the bot's own guild, from `json/guild_command`.
+	GuildSlash,
+	/// Context menu command on a user, from `json/user_command`.
+	User,
+	/// Context menu command on a message, from `json/message_command`.
+	Message,
+}
+
+/// Everything the bot needs to know about a command to dispatch it.
+
+pub struct CommandEntry {
+	/// Name of the command followed by its subcommand group and subcommand, as in the json.
+	pub path: &'static [&'static str],
+	pub kind: InteractionKind,
+	/// Module the command belongs to, `None` when it can't be disabled.
+	pub module: Option<&'static str>,
+	/// Hourly limit checked before running the command.
+	pub premium: Option<PremiumCommandType>,
+	/// Only the owner of the bot, or a member of its team, can run the command.
+	pub owner_only: bool,
+	pub run: CommandRunner,
+	pub autocomplete: Option<AutocompleteRunner>,
+}
+
+impl CommandEntry {
+	const fn new(path: &'static [&'static str], kind: InteractionKind, run: CommandRunner) -> Self {
+		CommandEntry {
+			path,
+			kind,
+			module: None,
+			premium: None,
+			owner_only: false,
+			run,
+			autocomplete: None,
+		}
+	}
+
+	const fn slash(path: &'static [&'static str], run: CommandRunner) -> Self {
+		Self::new(path, InteractionKind::Slash, run)
+	}
+
+	const fn guild_slash(path: &'static [&'static str], run: CommandRunner) -> Self {
+		Self::new(path, InteractionKind::GuildSlash, run)
+	}
+
+	const fn user(path: &'static [&'static str], run: CommandRunner) -> Self {
+		Self::new(path, InteractionKind::User, run)
+	}
+
+	const fn module(mut self, module: &'static str) -> Self {
+		self.module = Some(module);
+
+		self
+	}
+
+	const fn premium(mut self, premium: PremiumCommandType) -> Self {
+		self.premium = Some(premium);
+
+		self
+	}
+
+	const fn owner_only(mut self) -> Self {
+		self.owner_only = true;
+
+		self
+	}
+
+	const fn autocomplete(mut self, autocomplete: AutocompleteRunner) -> Self {
+		self.autocomplete = Some(autocomplete);
+
+		self
+	}
+
+	/// Name given by `guess_command_kind`, the path joined with `_`.
+
+	pub fn name(&self) -> String {
+		self.path.join("_")
+	}
+
+	/// Name as typed by the user, `/vn game` for a slash command.
+
+	pub fn display_name(&self) -> String {
+		match self.kind {
+			InteractionKind::Slash | InteractionKind::GuildSlash => {
+				format!("/{}", self.path.join(" "))
+			},
+			InteractionKind::User | InteractionKind::Message => self.path.join(" "),
+		}
+	}
+}
+
+macro_rules! slash_command {
+	($command:ident) => {
+		|ctx, command_interaction| {
+			Box::pin(async move {
+				$command {
+					ctx,
+					command_interaction,
+				}
+				.run_slash()
+				.await
+			})
+		}
+	};
+}
+
+macro_rules! user_command {
+	($command:ident) => {
+		|ctx, command_interaction| {
+			Box::pin(async move {
+				$command {
+					ctx,
+					command_interaction,
+				}
+				.run_user()
+				.await
+			})
+		}
+	};
+}
+
+macro_rules! autocomplete {
+	($function:path) => {
+		|ctx, autocomplete_interaction| Box::pin($function(ctx, autocomplete_interaction))
+	};
+}
+
+pub static COMMANDS: &[CommandEntry] = &[
+	// user
+	CommandEntry::slash(&["user", "avatar"], slash_command!(AvatarCommand)),
+	CommandEntry::slash(&["user", "banner"], slash_command!(BannerCommand)),
+	CommandEntry::slash(&["user", "profile"], slash_command!(ProfileCommand)),
+	CommandEntry::slash(
+		&["user", "command_usage"],
+		slash_command!(CommandUsageCommand),
+	),
+	CommandEntry::user(&["avatar"], user_command!(AvatarCommand)),
+	CommandEntry::user(&["banner"], user_command!(BannerCommand)),
+	CommandEntry::user(&["profile"], user_command!(ProfileCommand)),
+	// admin
+	CommandEntry::slash(&["admin", "general", "lang"], slash_command!(LangCommand)),
+	CommandEntry::slash(
+		&["admin", "general", "module"],
+		slash_command!(ModuleCommand),
+	),
+	CommandEntry::slash(
+		&["admin", "general", "member_info_setting"],
+		slash_command!(NewMemberSettingCommand),
+	),
+	CommandEntry::slash(
+		&["admin", "anilist", "add_anime_activity"],
+		slash_command!(AddActivityCommand),
+	)
+	.module("ANILIST")
+	.autocomplete(autocomplete!(add_anime_activity::autocomplete)),
+	CommandEntry::slash(
+		&["admin", "anilist", "add_manga_activity"],
+		slash_command!(AddMangaActivityCommand),
+	)
+	.module("ANILIST")
+	.autocomplete(autocomplete!(add_manga_activity::autocomplete)),
+	CommandEntry::slash(
+		&["admin", "anilist", "delete_activity"],
+		slash_command!(DeleteActivityCommand),
+	)
+	.module("ANILIST")
+	.autocomplete(autocomplete!(delete_activity::autocomplete)),
+	CommandEntry::slash(
+		&["admin", "anilist", "follow_user"],
+		slash_command!(FollowUserCommand),
+	)
+	.module("ANILIST"),
+	CommandEntry::slash(
+		&["admin", "anilist", "unfollow_user"],
+		slash_command!(UnfollowUserCommand),
+	)
+	.module("ANILIST"),
+	// ai
+	CommandEntry::slash(&["ai", "image"], slash_command!(ImageCommand))
+		.module("AI")
+		.premium(PremiumCommandType::AIImage),
+	CommandEntry::slash(&["ai", "question"], slash_command!(QuestionCommand))
+		.module("AI")
+		.premium(PremiumCommandType::AIQuestion),
+	CommandEntry::slash(&["ai", "transcript"], slash_command!(TranscriptCommand))
+		.module("AI")
+		.premium(PremiumCommandType::AITranscript),
+	CommandEntry::slash(&["ai", "translation"], slash_command!(TranslationCommand))
+		.module("AI")
+		.premium(PremiumCommandType::AITranslation),
+	// anilist
+	CommandEntry::slash(&["list_user"], slash_command!(ListRegisterUser)).module("ANILIST"),
+	CommandEntry::slash(&["list_activity"], slash_command!(ListAllActivity)).module("ANILIST"),
+	CommandEntry::slash(&["anime"], slash_command!(AnimeCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(anime::autocomplete)),
+	CommandEntry::slash(&["character"], slash_command!(CharacterCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(character::autocomplete)),
+	CommandEntry::slash(&["compare"], slash_command!(CompareCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(compare::autocomplete)),
+	CommandEntry::slash(&["level"], slash_command!(LevelCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(user::autocomplete)),
+	CommandEntry::slash(&["ln"], slash_command!(LnCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(ln::autocomplete)),
+	CommandEntry::slash(&["manga"], slash_command!(MangaCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(manga::autocomplete)),
+	CommandEntry::slash(&["anilist_user"], slash_command!(AnilistUserCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(user::autocomplete)),
+	CommandEntry::slash(&["waifu"], slash_command!(WaifuCommand)).module("ANILIST"),
+	CommandEntry::slash(&["random"], slash_command!(RandomCommand)).module("ANILIST"),
+	CommandEntry::slash(&["register"], slash_command!(RegisterCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(user::autocomplete)),
+	CommandEntry::slash(&["staff"], slash_command!(StaffCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(staff::autocomplete)),
+	CommandEntry::slash(&["studio"], slash_command!(StudioCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(studio::autocomplete)),
+	CommandEntry::slash(&["search"], slash_command!(SearchCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(search::autocomplete)),
+	CommandEntry::slash(&["seiyuu"], slash_command!(SeiyuuCommand))
+		.module("ANILIST")
+		.autocomplete(autocomplete!(staff::autocomplete)),
+	CommandEntry::slash(&["anilist", "schedule"], slash_command!(ScheduleCommand))
+		.module("ANILIST"),
+	CommandEntry::slash(&["anilist", "season"], slash_command!(SeasonCommand)).module("ANILIST"),
+	// anime
+	CommandEntry::slash(
+		&["random_anime", "random_image"],
+		slash_command!(AnimeRandomImageCommand),
+	)
+	.module("ANIME"),
+	CommandEntry::slash(
+		&["random_hanime", "random_himage"],
+		slash_command!(AnimeRandomNsfwImageCommand),
+	)
+	.module("ANIME"),
+	// game
+	CommandEntry::slash(&["steam", "game"], slash_command!(SteamGameInfoCommand))
+		.module("GAME")
+		.autocomplete(autocomplete!(steam_game_info::autocomplete)),
+	// vn
+	CommandEntry::slash(&["vn", "game"], slash_command!(VnGameCommand))
+		.module("VN")
+		.autocomplete(autocomplete!(vn::game::autocomplete)),
+	CommandEntry::slash(&["vn", "character"], slash_command!(VnCharacterCommand))
+		.module("VN")
+		.autocomplete(autocomplete!(vn::character::autocomplete)),
+	CommandEntry::slash(&["vn", "staff"], slash_command!(VnStaffCommand)).module("VN"),
+	CommandEntry::slash(&["vn", "user"], slash_command!(VnUserCommand)).module("VN"),
+	CommandEntry::slash(&["vn", "producer"], slash_command!(VnProducerCommand))
+		.module("VN")
+		.autocomplete(autocomplete!(vn::producer::autocomplete)),
+	CommandEntry::slash(&["vn", "stats"], slash_command!(VnStatsCommand)).module("VN"),
+	// audio
+	CommandEntry::slash(&["audio", "join"], slash_command!(AudioJoinCommand)),
+	CommandEntry::slash(&["audio", "play"], slash_command!(AudioPlayCommand)),
+	// bot
+	CommandEntry::slash(&["bot", "credit"], slash_command!(CreditCommand)),
+	CommandEntry::slash(&["bot", "help"], slash_command!(HelpCommand)),
+	CommandEntry::slash(&["bot", "info"], slash_command!(InfoCommand)),
+	CommandEntry::slash(&["bot", "ping"], slash_command!(PingCommand)),
+	CommandEntry::slash(&["bot", "tasks"], slash_command!(TasksCommand)).owner_only(),
+	// server
+	CommandEntry::slash(&["server", "guild"], slash_command!(GuildCommand)),
+	CommandEntry::slash(
+		&["server", "guild_image"],
+		slash_command!(GenerateImagePfPCommand),
+	),
+	CommandEntry::slash(
+		&["server", "guild_image_g"],
+		slash_command!(GenerateGlobalImagePfPCommand),
+	),
+	// management
+	CommandEntry::guild_slash(&["kill_switch"], slash_command!(KillSwitchCommand)),
+	CommandEntry::guild_slash(&["give_premium_sub"], slash_command!(GivePremiumSubCommand))
+		.autocomplete(autocomplete!(give_premium_sub_autocomplete)),
+	CommandEntry::guild_slash(&["remove_test_sub"], slash_command!(RemoveTestSubCommand)),
+];
+
+/// Finds the command of the given kind by the name given by `guess_command_kind`, or by the
+/// command name for the user and message commands.
+
+pub fn get_command(kind: InteractionKind, name: &str) -> Option<&'static CommandEntry> {
+	let is_slash = |kind: InteractionKind| {
+		matches!(kind, InteractionKind::Slash | InteractionKind::GuildSlash)
+	};
+
+	COMMANDS.iter().find(|command| {
+		(command.kind == kind || (is_slash(command.kind) && is_slash(kind)))
+			&& command.name() == name
+	})
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use serde_json::Value;
+	use std::collections::BTreeSet;
+
+	// Every command declared in the json folders, with whether one of its args is autocompleted.
+	fn json_commands() -> BTreeSet<(String, InteractionKind, bool)> {
+		let read_dir = |dir: &str| -> Vec<Value> {
+			let mut files: Vec<Value> = Vec::new();
+
+			for entry in std::fs::read_dir(dir).unwrap() {
+				let path = entry.unwrap().path();
+
+				if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+					let content = std::fs::read_to_string(&path).unwrap();
+
+					files.push(serde_json::from_str(&content).unwrap());
+				}
+			}
+
+			files
+		};
+
+		let name = |value: &Value| value["name"].as_str().unwrap().to_string();
+
+		let has_autocomplete = |value: &Value| {
+			value["args"].as_array().is_some_and(|args| {
+				args.iter()
+					.any(|arg| arg["autocomplete"].as_bool().unwrap_or(false))
+			})
+		};
+
+		let mut commands = BTreeSet::new();
+
+		for command in read_dir("json/command") {
+			commands.insert((
+				name(&command),
+				InteractionKind::Slash,
+				has_autocomplete(&command),
+			));
+		}
+
+		for command in read_dir("json/guild_command") {
+			commands.insert((
+				name(&command),
+				InteractionKind::GuildSlash,
+				has_autocomplete(&command),
+			));
+		}
+
+		for command in read_dir("json/subcommand") {
+			for subcommand in command["command"].as_array().unwrap() {
+				commands.insert((
+					format!("{}_{}", name(&command), name(subcommand)),
+					InteractionKind::Slash,
+					has_autocomplete(subcommand),
+				));
+			}
+		}
+
+		for command in read_dir("json/subcommand_group") {
+			for group in command["subcommands"].as_array().into_iter().flatten() {
+				for subcommand in group["command"].as_array().unwrap() {
+					commands.insert((
+						format!("{}_{}_{}", name(&command), name(group), name(subcommand)),
+						InteractionKind::Slash,
+						has_autocomplete(subcommand),
+					));
+				}
+			}
+
+			for subcommand in command["command"].as_array().into_iter().flatten() {
+				commands.insert((
+					format!("{}_{}", name(&command), name(subcommand)),
+					InteractionKind::Slash,
+					has_autocomplete(subcommand),
+				));
+			}
+		}
+
+		for command in read_dir("json/user_command") {
+			commands.insert((name(&command), InteractionKind::User, false));
+		}
+
+		for command in read_dir("json/message_command") {
+			commands.insert((name(&command), InteractionKind::Message, false));
+		}
+
+		commands
+	}
+
+	#[test]
+
+	fn test_registry_matches_json() {
+		let registered: BTreeSet<(String, InteractionKind, bool)> = COMMANDS
+			.iter()
+			.map(|command| (command.name(), command.kind, command.autocomplete.is_some()))
+			.collect();
+
+		let declared = json_commands();
+
+		let missing: Vec<_> = declared.difference(&registered).collect();
+
+		let unknown: Vec<_> = registered.difference(&declared).collect();
+
+		assert!(
+			missing.is_empty() && unknown.is_empty(),
+			"not in the registry: {:?}, not in the json: {:?}",
+			missing,
+			unknown
+		);
+	}
+}
@@ -1,12 +1,11 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::{Config, DbConfig};
 use crate::database::prelude::ServerImage;
 use crate::database::server_image::Column;
 use crate::event_handler::BotData;
-use crate::get_url;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::server::generate_image_pfp_server::load_localization_pfp_server_image;
 use base64::engine::general_purpose::STANDARD as BASE64;
@@ -45,21 +44,22 @@ impl SlashCommand for GenerateImagePfPCommand {
 		init(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn init(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
-	send_embed(ctx, command_interaction, "local", config.db.clone()).await
+	send_embed(ctx, command_interaction, "local", db_connection.clone()).await
 }
 
 pub async fn send_embed(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, image_type: &str,
-	db_config: DbConfig,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -68,8 +68,12 @@ pub async fn send_embed(
 	};
 
 	// Load the localized text for the server's profile picture image
-	let pfp_server_image_localised_text =
-		load_localization_pfp_server_image(guild_id.clone(), db_config.clone()).await?;
+	let pfp_server_image_localised_text = load_localization_pfp_server_image(
+		guild_id.clone(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Create a deferred response to the command interaction
 	let builder_message = Defer(CreateInteractionResponseMessage::new());
@@ -80,12 +84,10 @@ pub async fn send_embed(
 		.await?;
 
 	// Retrieve the server's profile picture image
-	let connection = sea_orm::Database::connect(get_url(db_config.clone())).await?;
-
 	let image = ServerImage::find()
 		.filter(Column::ServerId.eq(guild_id.clone()))
 		.filter(Column::ImageType.eq(image_type.to_string()))
-		.one(&connection)
+		.one(&*db_connection)
 		.await?
 		.ok_or(anyhow!(format!(
 			"Server image with type {} not found",
@@ -2,9 +2,9 @@ use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
 use crate::command::server::generate_image_pfp_server::send_embed;
-use crate::config::Config;
 use crate::event_handler::BotData;
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use serenity::all::{CommandInteraction, Context as SerenityContext};
 
 pub struct GenerateGlobalImagePfPCommand {
@@ -29,14 +29,15 @@ impl SlashCommand for GenerateGlobalImagePfPCommand {
 		init(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 pub async fn init(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
-	send_embed(ctx, command_interaction, "global", config.db.clone()).await
+	send_embed(ctx, command_interaction, "global", db_connection.clone()).await
 }
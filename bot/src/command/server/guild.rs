@@ -1,8 +1,9 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::server::guild::load_localization_guild;
@@ -34,14 +35,15 @@ impl SlashCommand for GuildCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the command interaction
 	let guild_id = match command_interaction.guild_id {
@@ -50,10 +52,17 @@ async fn send_embed(
 	};
 
 	// Load the localized guild information
-	let guild_localised = load_localization_guild(guild_id, config.db.clone()).await?;
+	let guild_localised = load_localization_guild(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Retrieve the guild ID from the command interaction or return an error if it does not exist
-	let guild_id = command_interaction.guild_id.ok_or(anyhow!("No guild ID"))?;
+	let guild_id = command_interaction.guild_id.ok_or(KasukiError::UserInput(
+		"This command can only be used in a server".to_string(),
+	))?;
 
 	// Retrieve the guild's information or return an error if it could not be retrieved
 	let guild = guild_id.to_partial_guild_with_counts(&ctx.http).await?;
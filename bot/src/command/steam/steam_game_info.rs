@@ -1,9 +1,9 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::convert_flavored_markdown::convert_steam_to_discord_flavored_markdown;
 use crate::helper::create_default_embed::get_default_embed;
@@ -37,7 +37,7 @@ impl SlashCommand for SteamGameInfoCommand {
 		let data = get_steam_game(
 			bot_data.apps.clone(),
 			self.command_interaction.clone(),
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await?;
 
@@ -45,7 +45,7 @@ impl SlashCommand for SteamGameInfoCommand {
 			&self.ctx,
 			&self.command_interaction,
 			data,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
@@ -53,7 +53,7 @@ impl SlashCommand for SteamGameInfoCommand {
 
 async fn get_steam_game(
 	apps: Arc<RwLock<HashMap<String, u128>>>, command_interaction: CommandInteraction,
-	config: Arc<Config>,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<SteamGameWrapper> {
 	let guild_id = command_interaction
 		.guild_id
@@ -67,10 +67,15 @@ async fn get_steam_game(
 		.ok_or(anyhow!("No option for game_name"))?;
 
 	let data: SteamGameWrapper = if value.parse::<i128>().is_ok() {
-		SteamGameWrapper::new_steam_game_by_id(value.parse().unwrap(), guild_id, config.db.clone())
-			.await?
+		SteamGameWrapper::new_steam_game_by_id(
+			value.parse().unwrap(),
+			guild_id,
+			db_connection.clone(),
+		)
+		.await?
 	} else {
-		SteamGameWrapper::new_steam_game_by_search(value, guild_id, apps, config.db.clone()).await?
+		SteamGameWrapper::new_steam_game_by_search(value, guild_id, apps, db_connection.clone())
+			.await?
 	};
 
 	Ok(data)
@@ -78,15 +83,19 @@ async fn get_steam_game(
 
 async fn send_embed(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, data: SteamGameWrapper,
-	config: Arc<Config>,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = command_interaction
 		.guild_id
 		.unwrap_or(GuildId::from(0))
 		.to_string();
 
-	let steam_game_info_localised =
-		load_localization_steam_game_info(guild_id.clone(), config.db.clone()).await?;
+	let steam_game_info_localised = load_localization_steam_game_info(
+		guild_id.clone(),
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let game = data.data;
 
@@ -1,14 +1,15 @@
 use crate::command::command_trait::{Command, SlashCommand, UserCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::subcommand::get_option_map_user_subcommand;
 use crate::structure::message::user::avatar::load_localization_avatar;
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage, User,
 };
+use std::sync::Arc;
 
 pub struct AvatarCommand {
 	pub ctx: SerenityContext,
@@ -30,7 +31,13 @@ impl SlashCommand for AvatarCommand {
 		let user = get_user_command(&self.ctx, &self.command_interaction).await?;
 		let ctx = self.get_ctx();
 		let bot_data = ctx.data::<BotData>().clone();
-		send_embed(&self.ctx, &self.command_interaction, user, &bot_data.config).await
+		send_embed(
+			&self.ctx,
+			&self.command_interaction,
+			user,
+			bot_data.db_connection.clone(),
+		)
+		.await
 	}
 }
 
@@ -39,7 +46,13 @@ impl UserCommand for AvatarCommand {
 		let user = get_user_command_user(&self.ctx, &self.command_interaction).await;
 		let ctx = self.get_ctx();
 		let bot_data = ctx.data::<BotData>().clone();
-		send_embed(&self.ctx, &self.command_interaction, user, &bot_data.config).await
+		send_embed(
+			&self.ctx,
+			&self.command_interaction,
+			user,
+			bot_data.db_connection.clone(),
+		)
+		.await
 	}
 }
 
@@ -82,7 +95,8 @@ pub async fn get_user_command(
 }
 
 pub async fn send_embed(
-	ctx: &SerenityContext, interaction: &CommandInteraction, user: User, config: &Config,
+	ctx: &SerenityContext, interaction: &CommandInteraction, user: User,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = interaction
 		.guild_id
@@ -93,7 +107,9 @@ pub async fn send_embed(
 
 	let username = user.name;
 
-	let avatar_localised = load_localization_avatar(guild_id, config.db.clone()).await?;
+	let avatar_localised =
+		load_localization_avatar(guild_id, Some(interaction.locale.as_str()), db_connection)
+			.await?;
 
 	let embed = get_default_embed(None)
 		.image(avatar_url)
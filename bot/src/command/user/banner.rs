@@ -2,11 +2,11 @@ use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand, UserCommand};
 use crate::command::user::avatar::{get_user_command, get_user_command_user};
-use crate::config::{Config, DbConfig};
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::user::banner::load_localization_banner;
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage, User,
@@ -31,7 +31,13 @@ impl SlashCommand for BannerCommand {
 		let user = get_user_command(&self.ctx, &self.command_interaction).await?;
 		let ctx = self.get_ctx();
 		let bot_data = ctx.data::<BotData>().clone();
-		send_embed(&self.ctx, &self.command_interaction, user, &bot_data.config).await
+		send_embed(
+			&self.ctx,
+			&self.command_interaction,
+			user,
+			bot_data.db_connection.clone(),
+		)
+		.await
 	}
 }
 
@@ -44,7 +50,7 @@ impl UserCommand for BannerCommand {
 			&self.ctx,
 			&self.command_interaction,
 			user.await,
-			&bot_data.config,
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
@@ -52,14 +58,19 @@ impl UserCommand for BannerCommand {
 
 pub async fn no_banner(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, username: &str,
-	db_config: DbConfig,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = match command_interaction.guild_id {
 		Some(id) => id.to_string(),
 		None => String::from("0"),
 	};
 
-	let banner_localised = load_localization_banner(guild_id, db_config).await?;
+	let banner_localised = load_localization_banner(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
 
 	let builder_embed = get_default_embed(None)
 		.description(banner_localised.no_banner.replace("$user$", username))
@@ -78,14 +89,12 @@ pub async fn no_banner(
 
 pub async fn send_embed(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, user: User,
-	config: &Arc<Config>,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
-	let db_config = config.db.clone();
-
 	let banner = match user.banner_url() {
 		Some(url) => url,
 		None => {
-			no_banner(ctx, command_interaction, &user.name, db_config).await?;
+			no_banner(ctx, command_interaction, &user.name, db_connection).await?;
 
 			return Ok(());
 		},
@@ -98,7 +107,12 @@ pub async fn send_embed(
 		None => String::from("0"),
 	};
 
-	let banner_localised = load_localization_banner(guild_id, db_config).await?;
+	let banner_localised = load_localization_banner(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
 
 	let builder_embed = get_default_embed(None)
 		.image(banner)
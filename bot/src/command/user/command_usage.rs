@@ -2,16 +2,17 @@ use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand};
 use crate::command::user::avatar::get_user_command;
-use crate::config::DbConfig;
-use crate::event_handler::{BotData, RootUsage};
+use crate::event_handler::BotData;
+use crate::helper::command_usage::{get_usage_per_command, UsageCount};
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::user::command_usage::load_localization_command_usage;
 use anyhow::Result;
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage, User,
 };
-use tokio::sync::{RwLock, RwLockReadGuard};
 
 pub struct CommandUsageCommand {
 	pub ctx: SerenityContext,
@@ -37,33 +38,34 @@ impl SlashCommand for CommandUsageCommand {
 			&self.ctx,
 			&self.command_interaction,
 			user,
-			&bot_data.config.db.clone(),
-			&bot_data.number_of_command_use_per_command,
+			bot_data.config.bot.command_usage_retention_days,
+			bot_data.db_connection.clone(),
 		)
 		.await
 	}
 }
 
 pub async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, user: User, config: &DbConfig,
-	command_usage: &Arc<RwLock<RootUsage>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction, user: User,
+	retention_days: u32, db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
-	let db_config = config.clone();
-
 	let user_id = user.id.to_string();
 
 	let username = user.name.clone();
 
-	let read_command_usage = command_usage.read().await;
-
-	let usage = get_usage_for_id(&user_id, read_command_usage);
+	let usage = get_usage_for_id(&user_id, &db_connection).await?;
 
 	let guild_id = command_interaction
 		.guild_id
 		.map(|id| id.to_string())
 		.unwrap_or("0".to_string());
 
-	let localized_command_usage = load_localization_command_usage(guild_id, db_config).await?;
+	let localized_command_usage = load_localization_command_usage(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
 
 	let embed =
 		get_default_embed(None).title(localized_command_usage.title.replace("$user$", &username));
@@ -83,12 +85,15 @@ pub async fn send_embed(
 
 		let mut inner_embed = embed.clone();
 
-		for (command, usage_count) in &usage {
+		for (command, total, daily, hourly) in &usage {
 			description.push_str(
 				localized_command_usage
 					.command_usage
 					.replace("$command$", command)
-					.replace("$usage$", &usage_count.to_string())
+					.replace("$usage$", &total.to_string())
+					.replace("$days$", &retention_days.to_string())
+					.replace("$daily$", &daily.to_string())
+					.replace("$hourly$", &hourly.to_string())
 					.as_str(),
 			);
 
@@ -120,18 +125,51 @@ pub async fn send_embed(
 	Ok(())
 }
 
-fn get_usage_for_id(
-	target_id: &str, root_usage: RwLockReadGuard<RootUsage>,
-) -> Vec<(String, u128)> {
-	let mut usage = Vec::new();
-
-	for (command, user_info) in root_usage.command_list.iter() {
-		for (id, user_usage) in user_info.user_info.iter() {
-			if id == target_id {
-				usage.push((command.clone(), user_usage.usage));
-			}
-		}
-	}
-
-	usage
+// Total, last 24 hours and last hour usage of each command used by the user, most used first.
+async fn get_usage_for_id(
+	target_id: &str, db_connection: &DatabaseConnection,
+) -> Result<Vec<(String, i64, i64, i64)>> {
+	let now = Utc::now().naive_utc();
+
+	let total = get_usage_per_command(db_connection, None, Some(target_id), None).await?;
+
+	let daily = get_usage_per_command(
+		db_connection,
+		None,
+		Some(target_id),
+		Some(now - Duration::days(1)),
+	)
+	.await?;
+
+	let hourly = get_usage_per_command(
+		db_connection,
+		None,
+		Some(target_id),
+		Some(now - Duration::hours(1)),
+	)
+	.await?;
+
+	let count_for = |counts: &[UsageCount], command: &str| {
+		counts
+			.iter()
+			.find(|count| count.command_name == command)
+			.map(|count| count.usage)
+			.unwrap_or(0)
+	};
+
+	let mut usage: Vec<(String, i64, i64, i64)> = total
+		.iter()
+		.map(|count| {
+			(
+				count.command_name.clone(),
+				count.usage,
+				count_for(&daily, &count.command_name),
+				count_for(&hourly, &count.command_name),
+			)
+		})
+		.collect();
+
+	usage.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+	Ok(usage)
 }
@@ -2,11 +2,11 @@ use std::sync::Arc;
 
 use crate::command::command_trait::{Command, SlashCommand, UserCommand};
 use crate::command::user::avatar::{get_user_command, get_user_command_user};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::user::profile::{load_localization_profile, ProfileLocalised};
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage, Member, User,
@@ -32,7 +32,13 @@ impl SlashCommand for ProfileCommand {
 		let user = get_user_command(&self.ctx, &self.command_interaction).await?;
 		let ctx = self.get_ctx();
 		let bot_data = ctx.data::<BotData>().clone();
-		send_embed(&self.ctx, &self.command_interaction, user, &bot_data.config).await
+		send_embed(
+			&self.ctx,
+			&self.command_interaction,
+			user,
+			bot_data.db_connection.clone(),
+		)
+		.await
 	}
 }
 
@@ -41,7 +47,13 @@ impl UserCommand for ProfileCommand {
 		let user = get_user_command_user(&self.ctx, &self.command_interaction).await;
 		let ctx = self.get_ctx();
 		let bot_data = ctx.data::<BotData>().clone();
-		send_embed(&self.ctx, &self.command_interaction, user, &bot_data.config).await
+		send_embed(
+			&self.ctx,
+			&self.command_interaction,
+			user,
+			bot_data.db_connection.clone(),
+		)
+		.await
 	}
 }
 
@@ -87,16 +99,19 @@ fn get_fields(profile_localised: &ProfileLocalised, user: User) -> Vec<(String,
 
 async fn send_embed(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, user: User,
-	config: &Arc<Config>,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
-	let db_config = config.db.clone();
-
 	let guild_id = command_interaction
 		.guild_id
 		.map(|id| id.to_string())
 		.unwrap_or("0".to_string());
 
-	let profile_localised = load_localization_profile(guild_id, db_config).await?;
+	let profile_localised = load_localization_profile(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
 
 	let mut fields = get_fields(&profile_localised, user.clone());
 
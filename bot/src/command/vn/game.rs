@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
+use crate::cache::NamespacedCache;
 use crate::command::command_trait::{Command, SlashCommand};
-use crate::config::Config;
 use crate::event_handler::BotData;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::helper::get_option::subcommand::get_option_map_string_subcommand;
@@ -9,12 +9,11 @@ use crate::helper::vndbapi::game::get_vn;
 use crate::structure::message::vn::game::load_localization_game;
 use anyhow::Result;
 use markdown_converter::vndb::convert_vndb_markdown;
-use moka::future::Cache;
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateInteractionResponse,
 	CreateInteractionResponseMessage,
 };
-use tokio::sync::RwLock;
 use tracing::trace;
 
 pub struct VnGameCommand {
@@ -39,7 +38,7 @@ impl SlashCommand for VnGameCommand {
 		send_embed(
 			&self.ctx,
 			&self.command_interaction,
-			bot_data.config.clone(),
+			bot_data.db_connection.clone(),
 			bot_data.vndb_cache.clone(),
 		)
 		.await
@@ -47,8 +46,8 @@ impl SlashCommand for VnGameCommand {
 }
 
 async fn send_embed(
-	ctx: &SerenityContext, command_interaction: &CommandInteraction, config: Arc<Config>,
-	vndb_cache: Arc<RwLock<Cache<String, String>>>,
+	ctx: &SerenityContext, command_interaction: &CommandInteraction,
+	db_connection: Arc<DatabaseConnection>, vndb_cache: NamespacedCache,
 ) -> Result<()> {
 	let guild_id = match command_interaction.guild_id {
 		Some(id) => id.to_string(),
@@ -64,7 +63,12 @@ async fn send_embed(
 		.cloned()
 		.unwrap_or(String::new());
 
-	let game_localised = load_localization_game(guild_id, config.db.clone()).await?;
+	let game_localised = load_localization_game(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let vn = get_vn(game.clone(), vndb_cache).await?;
 
@@ -49,8 +49,12 @@ async fn send_embed(ctx: &SerenityContext, command_interaction: &CommandInteract
 		.unwrap_or(String::new());
 	let bot_data = ctx.data::<BotData>().clone();
 
-	let producer_localised =
-		load_localization_producer(guild_id, bot_data.config.db.clone()).await?;
+	let producer_localised = load_localization_producer(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		bot_data.db_connection.clone(),
+	)
+	.await?;
 
 	let producer = get_producer(producer.clone(), bot_data.vndb_cache.clone()).await?;
 
@@ -49,7 +49,12 @@ async fn send_embed(ctx: &SerenityContext, command_interaction: &CommandInteract
 		.cloned()
 		.unwrap_or(String::new());
 
-	let staff_localised = load_localization_staff(guild_id, bot_data.config.db.clone()).await?;
+	let staff_localised = load_localization_staff(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		bot_data.db_connection.clone(),
+	)
+	.await?;
 
 	let staff = get_staff(staff.clone(), bot_data.vndb_cache.clone()).await?;
 
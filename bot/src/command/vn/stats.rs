@@ -39,8 +39,12 @@ async fn send_embed(ctx: &SerenityContext, command_interaction: &CommandInteract
 	let bot_data = ctx.data::<BotData>().clone();
 	let vndb_cache = bot_data.vndb_cache.clone();
 	let stats = get_stats(vndb_cache).await?;
-	let config = bot_data.config.clone();
-	let stats_localised = load_localization_stats(guild_id, config.db.clone()).await?;
+	let stats_localised = load_localization_stats(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		bot_data.db_connection.clone(),
+	)
+	.await?;
 	let fields = vec![
 		(stats_localised.chars.clone(), stats.chars.to_string(), true),
 		(
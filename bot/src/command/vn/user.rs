@@ -51,8 +51,12 @@ async fn send_embed(ctx: &SerenityContext, command_interaction: &CommandInteract
 
 	let user = get_user(path, vndb_cache).await?;
 
-	let config = bot_data.config.clone();
-	let user_localised: UserLocalised = load_localization_user(guild_id, config.db.clone()).await?;
+	let user_localised: UserLocalised = load_localization_user(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		bot_data.db_connection.clone(),
+	)
+	.await?;
 
 	let fields = vec![
 		(user_localised.id.clone(), user.id.clone(), true),
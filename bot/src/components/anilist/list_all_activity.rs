@@ -1,38 +1,40 @@
-use crate::config::DbConfig;
 use crate::constant::{ACTIVITY_LIST_LIMIT, COLOR};
 use crate::database::activity_data::{Column, Model};
 use crate::database::prelude::ActivityData;
-use crate::get_url;
 use crate::structure::message::anilist_server::list_all_activity::load_localization_list_activity;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serenity::all::{
 	ComponentInteraction, Context as SerenityContext, CreateButton, CreateEmbed,
 	CreateInteractionResponse, CreateInteractionResponseMessage, Timestamp,
 };
+use std::sync::Arc;
 use tracing::trace;
 
 pub async fn update(
 	ctx: &SerenityContext, component_interaction: &ComponentInteraction, page_number: &str,
-	db_config: DbConfig,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = match component_interaction.guild_id {
 		Some(id) => id.to_string(),
 		None => String::from("0"),
 	};
 
-	let list_activity_localised_text =
-		load_localization_list_activity(guild_id, db_config.clone()).await?;
+	let list_activity_localised_text = load_localization_list_activity(
+		guild_id,
+		Some(component_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	let guild_id = component_interaction
 		.guild_id
 		.ok_or(anyhow!("Guild ID not found"))?;
 
-	let connection = sea_orm::Database::connect(get_url(db_config.clone())).await?;
-
 	let list = ActivityData::find()
 		.filter(Column::ServerId.eq(guild_id.to_string()))
-		.all(&connection)
+		.all(&*db_connection)
 		.await?;
 
 	let len = list.len();
@@ -93,7 +95,11 @@ pub fn get_formatted_activity_list(list: Vec<Model>, actual_page: u64) -> Vec<St
 
 			let name = activity.name;
 
-			format!("[{}](https://anilist_user.co/anime/{})", name, anime_id)
+			// The media kind is also the path of the media on AniList.
+			format!(
+				"[{}](https://anilist.co/{}/{})",
+				name, activity.media_kind, anime_id
+			)
 		})
 		.skip((ACTIVITY_LIST_LIMIT * actual_page) as usize)
 		.take(ACTIVITY_LIST_LIMIT as usize)
@@ -1,4 +1,6 @@
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
 
 use serenity::all::{
 	ComponentInteraction, Context as SerenityContext, CreateButton, CreateEmbed, EditMessage,
@@ -6,13 +8,12 @@ use serenity::all::{
 };
 
 use crate::command::anilist_server::list_register_user::get_the_list;
-use crate::config::DbConfig;
 use crate::constant::MEMBER_LIST_LIMIT;
 use crate::structure::message::anilist_server::list_register_user::load_localization_list_user;
 
 pub async fn update(
 	ctx: &SerenityContext, component_interaction: &ComponentInteraction, user_id: &str,
-	prev_id: &str, db_config: DbConfig,
+	prev_id: &str, db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	// Retrieve the guild ID from the component interaction
 	let guild_id = match component_interaction.guild_id {
@@ -21,7 +22,12 @@ pub async fn update(
 	};
 
 	// Load the localized user list
-	let list_user_localised = load_localization_list_user(guild_id, db_config.clone()).await?;
+	let list_user_localised = load_localization_list_user(
+		guild_id,
+		Some(component_interaction.locale.as_str()),
+		db_connection.clone(),
+	)
+	.await?;
 
 	// Retrieve the guild ID from the component interaction
 	let guild_id = component_interaction
@@ -44,7 +50,7 @@ pub async fn update(
 	// Get the list of users
 	let list_user = list_user_localised.clone();
 	let (builder_message, len, last_id): (CreateEmbed, usize, Option<UserId>) =
-		get_the_list(guild, ctx, &list_user, id, db_config).await?;
+		get_the_list(guild, ctx, &list_user, id, db_connection).await?;
 
 	// Create the response message
 	let mut response = EditMessage::new().embed(builder_message);
@@ -1,2 +1,4 @@
 pub mod list_all_activity;
 pub mod list_register_user;
+pub mod schedule;
+pub mod season;
@@ -0,0 +1,711 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use cynic::{GraphQlResponse, QueryBuilder};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serenity::all::{
+	ComponentInteraction, Context as SerenityContext, CreateButton, CreateEmbed, CreateEmbedFooter,
+	CreateInteractionResponse, CreateInteractionResponseMessage, GuildId,
+};
+
+use crate::constant::{
+	ACTIVITY_KIND_ANIME, SCHEDULE_MAX_PAGES, SCHEDULE_PER_PAGE, SCHEDULE_TABLE_ROWS,
+	SCHEDULE_TITLE_LENGTH,
+};
+use crate::database::activity_data::Column;
+use crate::database::prelude::ActivityData;
+use crate::event_handler::BotData;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
+use crate::helper::render_table::render_table;
+use crate::helper::trimer::trim_title;
+use crate::structure::message::anilist_user::schedule::{
+	load_localization_schedule, ScheduleLocalised,
+};
+use crate::structure::run::anilist::airing_schedule::{
+	AiringSchedule, AiringSchedulePage, AiringScheduleVariables, CurrentList, CurrentListVariables,
+};
+
+const SECONDS_IN_DAY: i64 = 86_400;
+
+/// Which anime the schedule shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+
+pub enum ScheduleFilter {
+	All,
+	/// The anime with an activity in the guild.
+	Guild,
+	/// The CURRENT list of a registered user.
+	List,
+}
+
+impl ScheduleFilter {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ScheduleFilter::All => "all",
+			ScheduleFilter::Guild => "guild",
+			ScheduleFilter::List => "list",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<Self> {
+		match value {
+			"all" => Some(ScheduleFilter::All),
+			"guild" => Some(ScheduleFilter::Guild),
+			"list" => Some(ScheduleFilter::List),
+			_ => None,
+		}
+	}
+}
+
+/// The timezone of the schedule, an IANA zone or a fixed UTC offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+
+pub enum ScheduleTimezone {
+	Named(Tz),
+	/// UTC offset in seconds.
+	Offset(i32),
+}
+
+impl ScheduleTimezone {
+	/// Parses an IANA zone like `Europe/Paris` or an UTC offset like `+02:00`.
+
+	pub fn parse(value: &str) -> Option<Self> {
+		parse_utc_offset(value)
+			.map(ScheduleTimezone::Offset)
+			.or_else(|| value.trim().parse().ok().map(ScheduleTimezone::Named))
+	}
+
+	/// The zone name, or the offset in seconds, as kept in the custom id.
+
+	pub fn as_id(&self) -> String {
+		match self {
+			ScheduleTimezone::Named(tz) => tz.name().to_string(),
+			ScheduleTimezone::Offset(offset) => offset.to_string(),
+		}
+	}
+
+	pub fn from_id(value: &str) -> Option<Self> {
+		match value.parse() {
+			Ok(offset) => Some(ScheduleTimezone::Offset(offset)),
+			Err(_) => value.parse().ok().map(ScheduleTimezone::Named),
+		}
+	}
+
+	/// The UTC offset in seconds at `timestamp`, it changes with the daylight saving time.
+
+	pub fn offset_at(&self, timestamp: i64) -> i32 {
+		match self {
+			ScheduleTimezone::Named(tz) => DateTime::from_timestamp(timestamp, 0)
+				.map(|time| {
+					tz.offset_from_utc_datetime(&time.naive_utc())
+						.fix()
+						.local_minus_utc()
+				})
+				.unwrap_or_default(),
+			ScheduleTimezone::Offset(offset) => *offset,
+		}
+	}
+
+	/// Unix timestamp of the start of `day`, in days since the epoch in the timezone.
+
+	pub fn start_of(&self, day: i64) -> i64 {
+		let midnight = day * SECONDS_IN_DAY;
+
+		// The offset at midnight, taken at a first guess of the instant, then at the instant.
+		let guess = midnight - self.offset_at(midnight) as i64;
+
+		midnight - self.offset_at(guess) as i64
+	}
+
+	pub fn name(&self) -> String {
+		match self {
+			ScheduleTimezone::Named(tz) => tz.name().to_string(),
+			ScheduleTimezone::Offset(offset) => format_utc_offset(*offset),
+		}
+	}
+}
+
+/// A day of the schedule, kept in the custom id of the buttons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+
+pub struct SchedulePage {
+	/// First day of the range, in days since the epoch in the timezone of the schedule.
+	pub start_day: i64,
+	/// 1 for a day, the buttons then move the day, 7 for a week.
+	pub days: i64,
+	/// Day shown, from 0 to `days`.
+	pub index: i64,
+	pub filter: ScheduleFilter,
+	/// AniList id of the user for `ScheduleFilter::List`, 0 otherwise.
+	pub anilist_id: i32,
+	/// Last in the custom id, the zone names can contain `_`.
+	pub timezone: ScheduleTimezone,
+}
+
+impl SchedulePage {
+	pub fn to_custom_id(&self) -> String {
+		format!(
+			"schedule_{}_{}_{}_{}_{}_{}",
+			self.start_day,
+			self.days,
+			self.index,
+			self.filter.as_str(),
+			self.anilist_id,
+			self.timezone.as_id()
+		)
+	}
+
+	pub fn from_custom_id(custom_id: &str) -> Result<Self> {
+		let invalid = || anyhow!("Invalid schedule custom id {}", custom_id);
+
+		let parts: Vec<&str> = custom_id
+			.strip_prefix("schedule_")
+			.ok_or_else(invalid)?
+			.splitn(6, '_')
+			.collect();
+
+		let [start_day, days, index, filter, anilist_id, timezone] = parts[..] else {
+			return Err(invalid());
+		};
+
+		Ok(Self {
+			start_day: start_day.parse()?,
+			days: days.parse()?,
+			index: index.parse()?,
+			filter: ScheduleFilter::parse(filter).ok_or_else(invalid)?,
+			anilist_id: anilist_id.parse()?,
+			timezone: ScheduleTimezone::from_id(timezone).ok_or_else(invalid)?,
+		})
+	}
+
+	/// Unix timestamps of the start and the end of the day shown.
+
+	pub fn bounds(&self) -> (i64, i64) {
+		let day = self.start_day + self.index;
+
+		(self.timezone.start_of(day), self.timezone.start_of(day + 1))
+	}
+
+	pub fn previous(&self) -> Option<Self> {
+		if self.days == 1 {
+			Some(Self {
+				start_day: self.start_day - 1,
+				..self.clone()
+			})
+		} else if self.index > 0 {
+			Some(Self {
+				index: self.index - 1,
+				..self.clone()
+			})
+		} else {
+			None
+		}
+	}
+
+	pub fn next(&self) -> Option<Self> {
+		if self.days == 1 {
+			Some(Self {
+				start_day: self.start_day + 1,
+				..self.clone()
+			})
+		} else if self.index + 1 < self.days {
+			Some(Self {
+				index: self.index + 1,
+				..self.clone()
+			})
+		} else {
+			None
+		}
+	}
+}
+
+/// The day of `timestamp` in `timezone`, in days since the epoch.
+
+pub fn day_of(timestamp: i64, timezone: ScheduleTimezone) -> i64 {
+	(timestamp + timezone.offset_at(timestamp) as i64).div_euclid(SECONDS_IN_DAY)
+}
+
+/// Parses an UTC offset like `+02:00`, `UTC-5` or `+0530` into seconds.
+
+pub fn parse_utc_offset(value: &str) -> Option<i32> {
+	let value = value.trim();
+
+	let value = match value.get(..3) {
+		Some(prefix)
+			if prefix.eq_ignore_ascii_case("UTC") || prefix.eq_ignore_ascii_case("GMT") =>
+		{
+			value[3..].trim()
+		},
+		_ => value,
+	};
+
+	if value.is_empty() || value.eq_ignore_ascii_case("z") {
+		return Some(0);
+	}
+
+	let (sign, value) = match value.strip_prefix('-') {
+		Some(rest) => (-1, rest),
+		None => (1, value.strip_prefix('+').unwrap_or(value)),
+	};
+
+	let (hours, minutes) = match value.split_once(':') {
+		Some((hours, minutes)) => (hours, minutes),
+		None if value.len() == 4 => value.split_at(2),
+		None => (value, "0"),
+	};
+
+	if !(hours.chars().all(|c| c.is_ascii_digit()) && minutes.chars().all(|c| c.is_ascii_digit())) {
+		return None;
+	}
+
+	let hours: i32 = hours.parse().ok()?;
+
+	let minutes: i32 = minutes.parse().ok()?;
+
+	if hours > 14 || minutes >= 60 {
+		return None;
+	}
+
+	Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Formats an UTC offset in seconds, `UTC+05:30`.
+
+pub fn format_utc_offset(offset: i32) -> String {
+	if offset == 0 {
+		return "UTC".to_string();
+	}
+
+	let sign = if offset < 0 { '-' } else { '+' };
+
+	let offset = offset.abs();
+
+	format!("UTC{}{:02}:{:02}", sign, offset / 3600, offset % 3600 / 60)
+}
+
+pub async fn update(
+	ctx: &SerenityContext, component_interaction: &ComponentInteraction, custom_id: &str,
+	db_connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let anilist_client = ctx.data::<BotData>().anilist_client.clone();
+
+	let page = SchedulePage::from_custom_id(custom_id)?;
+
+	let (embed, buttons) = get_schedule_message(
+		&page,
+		component_interaction.guild_id,
+		Some(component_interaction.locale.as_str()),
+		db_connection,
+		anilist_client,
+	)
+	.await?;
+
+	let mut message_rep = CreateInteractionResponseMessage::new().embed(embed);
+
+	for button in buttons {
+		message_rep = message_rep.button(button);
+	}
+
+	let response = CreateInteractionResponse::UpdateMessage(message_rep);
+
+	component_interaction
+		.create_response(&ctx.http, response)
+		.await?;
+
+	Ok(())
+}
+
+/// The embed of a day of the schedule and the buttons to the previous and next days.
+
+pub async fn get_schedule_message(
+	page: &SchedulePage, guild_id: Option<GuildId>, locale: Option<&str>,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
+) -> Result<(CreateEmbed<'static>, Vec<CreateButton<'static>>)> {
+	let schedule_localised = load_localization_schedule(
+		guild_id.map_or(String::from("0"), |id| id.to_string()),
+		locale,
+		db_connection.clone(),
+	)
+	.await?;
+
+	let media_ids = match page.filter {
+		ScheduleFilter::All => None,
+		ScheduleFilter::Guild => {
+			let guild_id = guild_id.ok_or(anyhow!("Guild ID not found"))?;
+
+			let activities = ActivityData::find()
+				.filter(Column::ServerId.eq(guild_id.to_string()))
+				.filter(Column::MediaKind.eq(ACTIVITY_KIND_ANIME))
+				.all(&*db_connection)
+				.await?;
+
+			Some(
+				activities
+					.iter()
+					.map(|activity| activity.anime_id)
+					.collect(),
+			)
+		},
+		ScheduleFilter::List => {
+			Some(get_current_list(page.anilist_id, anilist_client.clone()).await?)
+		},
+	};
+
+	let (start, end) = page.bounds();
+
+	let airings = match media_ids {
+		// An empty `mediaId_in` is ignored by AniList, nothing is tracked so nothing airs.
+		Some(ids) if ids.is_empty() => Vec::new(),
+		ids => get_airings(start, end, ids, anilist_client).await?,
+	};
+
+	let embed = schedule_embed(page, &airings, &schedule_localised);
+
+	let mut buttons = Vec::new();
+
+	if let Some(previous) = page.previous() {
+		buttons.push(
+			CreateButton::new(previous.to_custom_id()).label(schedule_localised.previous.clone()),
+		);
+	}
+
+	if let Some(next) = page.next() {
+		buttons.push(CreateButton::new(next.to_custom_id()).label(schedule_localised.next.clone()));
+	}
+
+	Ok((embed, buttons))
+}
+
+/// Ids of the anime on the CURRENT list of `anilist_id`.
+
+async fn get_current_list(anilist_id: i32, anilist_client: Arc<AnilistClient>) -> Result<Vec<i32>> {
+	let operation = CurrentList::build(CurrentListVariables {
+		user_id: Some(anilist_id),
+	});
+
+	// The list changes as the user watches, a cached one could miss a new show.
+	let response: GraphQlResponse<CurrentList> =
+		make_request_anilist(operation, true, anilist_client).await?;
+
+	let collection = response
+		.data
+		.and_then(|data| data.media_list_collection)
+		.ok_or(anyhow!("No list found for the user {}", anilist_id))?;
+
+	Ok(collection
+		.lists
+		.unwrap_or_default()
+		.into_iter()
+		.flatten()
+		.flat_map(|list| list.entries.unwrap_or_default())
+		.flatten()
+		.map(|entry| entry.media_id)
+		.collect())
+}
+
+/// The episodes airing from `start` to `end`, the adult anime excluded.
+
+async fn get_airings(
+	start: i64, end: i64, media_ids: Option<Vec<i32>>, anilist_client: Arc<AnilistClient>,
+) -> Result<Vec<AiringSchedule>> {
+	let mut airings = Vec::new();
+
+	for page in 1..=SCHEDULE_MAX_PAGES {
+		let operation = AiringSchedulePage::build(AiringScheduleVariables {
+			airing_at_greater: Some(start as i32 - 1),
+			airing_at_lesser: Some(end as i32),
+			media_ids: media_ids
+				.as_ref()
+				.map(|ids| ids.iter().map(|id| Some(*id)).collect()),
+			page: Some(page),
+			per_page: Some(SCHEDULE_PER_PAGE),
+		});
+
+		let response: GraphQlResponse<AiringSchedulePage> =
+			make_request_anilist(operation, false, anilist_client.clone()).await?;
+
+		let page = response
+			.data
+			.and_then(|data| data.page)
+			.ok_or(anyhow!("No airing schedule in the response"))?;
+
+		airings.extend(
+			page.airing_schedules
+				.unwrap_or_default()
+				.into_iter()
+				.flatten()
+				.filter(|airing| {
+					airing
+						.media
+						.as_ref()
+						.is_some_and(|media| media.is_adult != Some(true))
+				}),
+		);
+
+		let has_next_page = page
+			.page_info
+			.and_then(|page_info| page_info.has_next_page)
+			.unwrap_or_default();
+
+		if !has_next_page {
+			break;
+		}
+	}
+
+	Ok(airings)
+}
+
+fn schedule_embed(
+	page: &SchedulePage, airings: &[AiringSchedule], schedule_localised: &ScheduleLocalised,
+) -> CreateEmbed<'static> {
+	let (start, end) = page.bounds();
+
+	let mut description = schedule_localised
+		.desc
+		.replace("$start$", &format!("<t:{}:F>", start))
+		.replace("$end$", &format!("<t:{}:F>", end))
+		.replace("$timezone$", &page.timezone.name());
+
+	if airings.is_empty() {
+		description.push_str(&format!("\n\n{}", schedule_localised.empty));
+	} else {
+		let rows: Vec<Vec<String>> = airings
+			.iter()
+			.take(SCHEDULE_TABLE_ROWS)
+			.map(|airing| {
+				let airing_at = airing.airing_at as i64;
+
+				let time = DateTime::from_timestamp(
+					airing_at + page.timezone.offset_at(airing_at) as i64,
+					0,
+				)
+				.map(|time| time.format("%H:%M").to_string())
+				.unwrap_or_default();
+
+				vec![
+					time,
+					airing.episode.to_string(),
+					trim_title(&title(airing), SCHEDULE_TITLE_LENGTH),
+				]
+			})
+			.collect();
+
+		let table = render_table(
+			&[
+				schedule_localised.time.as_str(),
+				schedule_localised.episode.as_str(),
+				schedule_localised.anime.as_str(),
+			],
+			&rows,
+		);
+
+		description.push_str(&format!("\n```\n{}\n```", table));
+
+		if airings.len() > SCHEDULE_TABLE_ROWS {
+			description.push_str(&format!(
+				"\n{}",
+				schedule_localised.more.replace(
+					"$count$",
+					&(airings.len() - SCHEDULE_TABLE_ROWS).to_string()
+				)
+			));
+		}
+	}
+
+	let mut embed = get_default_embed(None)
+		.title(schedule_localised.title.clone())
+		.description(description);
+
+	let now = Utc::now().timestamp();
+
+	if let Some(airing) = airings.iter().find(|airing| airing.airing_at as i64 > now) {
+		let url = airing
+			.media
+			.as_ref()
+			.and_then(|media| media.site_url.clone())
+			.unwrap_or_default();
+
+		embed = embed.field(
+			schedule_localised.next_airing.clone(),
+			schedule_localised
+				.next_airing_desc
+				.replace("$media$", &format!("[{}]({})", title(airing), url))
+				.replace("$episode$", &airing.episode.to_string())
+				.replace("$time$", &format!("<t:{}:R>", airing.airing_at)),
+			false,
+		);
+	}
+
+	if page.days > 1 {
+		embed = embed.footer(CreateEmbedFooter::new(
+			schedule_localised
+				.day
+				.replace("$index$", &(page.index + 1).to_string())
+				.replace("$days$", &page.days.to_string()),
+		));
+	}
+
+	embed
+}
+
+fn title(airing: &AiringSchedule) -> String {
+	airing
+		.media
+		.as_ref()
+		.and_then(|media| media.title.clone())
+		.and_then(|title| title.english.or(title.romaji))
+		.unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_custom_id_round_trip() {
+		let page = SchedulePage {
+			start_day: 20_378,
+			days: 7,
+			index: 3,
+			filter: ScheduleFilter::List,
+			anilist_id: 5_123_456,
+			timezone: ScheduleTimezone::Named(Tz::America__Argentina__Buenos_Aires),
+		};
+
+		let custom_id = page.to_custom_id();
+
+		assert!(custom_id.len() <= 100);
+
+		assert_eq!(SchedulePage::from_custom_id(&custom_id).unwrap(), page);
+
+		let offset = SchedulePage {
+			timezone: ScheduleTimezone::Offset(-16_200),
+			..page
+		};
+
+		assert_eq!(
+			SchedulePage::from_custom_id(&offset.to_custom_id()).unwrap(),
+			offset
+		);
+
+		assert!(SchedulePage::from_custom_id("schedule_1_2").is_err());
+
+		assert!(SchedulePage::from_custom_id("schedule_1_1_0_none_0_0").is_err());
+	}
+
+	#[test]
+
+	fn test_navigation() {
+		let week = SchedulePage {
+			start_day: 100,
+			days: 7,
+			index: 0,
+			filter: ScheduleFilter::All,
+			anilist_id: 0,
+			timezone: ScheduleTimezone::Offset(0),
+		};
+
+		assert!(week.previous().is_none());
+
+		assert_eq!(week.next().unwrap().index, 1);
+
+		let last = SchedulePage {
+			index: 6,
+			..week.clone()
+		};
+
+		assert!(last.next().is_none());
+
+		assert_eq!(last.bounds(), (106 * 86_400, 107 * 86_400));
+
+		// A single day moves the day itself.
+		let day = SchedulePage { days: 1, ..week };
+
+		assert_eq!(day.previous().unwrap().start_day, 99);
+
+		assert_eq!(day.next().unwrap().start_day, 101);
+	}
+
+	#[test]
+
+	fn test_day_of() {
+		// 2025-01-01 23:30 UTC is already the 2nd in UTC+1.
+		let timestamp = 1_735_774_200;
+
+		let paris = ScheduleTimezone::Named(Tz::Europe__Paris);
+
+		assert_eq!(day_of(timestamp, ScheduleTimezone::Offset(0)), 20_089);
+
+		assert_eq!(day_of(timestamp, ScheduleTimezone::Offset(3600)), 20_090);
+
+		assert_eq!(day_of(timestamp, paris), 20_090);
+
+		assert_eq!(day_of(0, ScheduleTimezone::Offset(-3600)), -1);
+
+		let page = SchedulePage {
+			start_day: day_of(timestamp, paris),
+			days: 1,
+			index: 0,
+			filter: ScheduleFilter::All,
+			anilist_id: 0,
+			timezone: paris,
+		};
+
+		let (start, end) = page.bounds();
+
+		assert!(start <= timestamp && timestamp < end);
+
+		// 2025-03-30 in Paris has 23 hours, the clocks go to summer time.
+		let spring = SchedulePage {
+			start_day: 20_177,
+			..page
+		};
+
+		let (start, end) = spring.bounds();
+
+		assert_eq!(start, 20_177 * 86_400 - 3600);
+
+		assert_eq!(end - start, 23 * 3600);
+	}
+
+	#[test]
+
+	fn test_parse_utc_offset() {
+		assert_eq!(parse_utc_offset("UTC"), Some(0));
+
+		assert_eq!(parse_utc_offset("+02:00"), Some(7200));
+
+		assert_eq!(parse_utc_offset("utc-5"), Some(-18_000));
+
+		assert_eq!(parse_utc_offset("GMT+0530"), Some(19_800));
+
+		assert_eq!(parse_utc_offset("9"), Some(32_400));
+
+		assert_eq!(parse_utc_offset("+15"), None);
+
+		assert_eq!(parse_utc_offset("+02:75"), None);
+
+		assert_eq!(parse_utc_offset("Europe/Paris"), None);
+
+		assert_eq!(
+			ScheduleTimezone::parse("Europe/Paris"),
+			Some(ScheduleTimezone::Named(Tz::Europe__Paris))
+		);
+
+		assert_eq!(
+			ScheduleTimezone::parse("+02:00"),
+			Some(ScheduleTimezone::Offset(7200))
+		);
+
+		assert_eq!(ScheduleTimezone::parse("Mars/Olympus"), None);
+
+		assert_eq!(format_utc_offset(19_800), "UTC+05:30");
+
+		assert_eq!(format_utc_offset(-18_000), "UTC-05:00");
+	}
+}
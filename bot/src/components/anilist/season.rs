@@ -0,0 +1,495 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, NaiveDate};
+use cynic::{GraphQlResponse, QueryBuilder};
+use sea_orm::DatabaseConnection;
+use serenity::all::{
+	ComponentInteraction, ComponentInteractionDataKind, Context as SerenityContext,
+	CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+	CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind,
+	CreateSelectMenuOption, GuildId,
+};
+
+use crate::command::anilist_user::anime::get_anime_by_id;
+use crate::constant::{SEASON_PER_PAGE, SEASON_TITLE_LENGTH};
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+use crate::helper::create_default_embed::get_default_embed;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
+use crate::helper::trimer::trim_title;
+use crate::structure::message::anilist_user::season::{load_localization_season, SeasonLocalised};
+use crate::structure::run::anilist::media::get_media_embed;
+use crate::structure::run::anilist::season::{
+	Media, MediaFormat, MediaSeason, MediaSort, Season, SeasonVariables,
+};
+
+/// Custom id of the select menu opening an anime of the list.
+
+pub const SEASON_MEDIA_ID: &str = "season_media";
+
+/// A select menu label holds at most 100 characters.
+
+const MAX_LABEL_LENGTH: usize = 100;
+
+/// An embed description holds at most 4096 characters.
+
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// A page of the season list, kept in the custom id of the buttons. The season, format and sort
+/// are the choices of the command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+
+pub struct SeasonPage {
+	pub year: i32,
+	pub season: String,
+	/// A format or `all`.
+	pub format: String,
+	pub sort: String,
+	/// Page of AniList, from 1.
+	pub page: i32,
+}
+
+impl SeasonPage {
+	pub fn to_custom_id(&self) -> String {
+		format!(
+			"season_page_{}_{}_{}_{}_{}",
+			self.year, self.season, self.format, self.sort, self.page
+		)
+	}
+
+	pub fn from_custom_id(custom_id: &str) -> Result<Self> {
+		let invalid = || anyhow!("Invalid season custom id {}", custom_id);
+
+		let parts: Vec<&str> = custom_id
+			.strip_prefix("season_page_")
+			.ok_or_else(invalid)?
+			.split('_')
+			.collect();
+
+		let [year, season, format, sort, page] = parts[..] else {
+			return Err(invalid());
+		};
+
+		Ok(Self {
+			year: year.parse()?,
+			season: season.to_string(),
+			format: format.to_string(),
+			sort: sort.to_string(),
+			page: page.parse()?,
+		})
+	}
+
+	/// The variables of the AniList query, an error for an unknown season, format or sort.
+
+	pub fn variables(&self) -> Result<SeasonVariables> {
+		let season = match self.season.as_str() {
+			"winter" => MediaSeason::Winter,
+			"spring" => MediaSeason::Spring,
+			"summer" => MediaSeason::Summer,
+			"fall" => MediaSeason::Fall,
+			season => {
+				return Err(KasukiError::UserInput(format!("Unknown season {}", season)).into())
+			},
+		};
+
+		let format = match self.format.as_str() {
+			"all" => None,
+			"tv" => Some(MediaFormat::Tv),
+			"short" => Some(MediaFormat::TvShort),
+			"movie" => Some(MediaFormat::Movie),
+			"special" => Some(MediaFormat::Special),
+			"ova" => Some(MediaFormat::Ova),
+			"ona" => Some(MediaFormat::Ona),
+			"music" => Some(MediaFormat::Music),
+			format => {
+				return Err(KasukiError::UserInput(format!("Unknown format {}", format)).into())
+			},
+		};
+
+		let sort = match self.sort.as_str() {
+			"popularity" => MediaSort::PopularityDesc,
+			"score" => MediaSort::ScoreDesc,
+			"trending" => MediaSort::TrendingDesc,
+			"title" => MediaSort::TitleRomaji,
+			"date" => MediaSort::StartDate,
+			sort => return Err(KasukiError::UserInput(format!("Unknown sort {}", sort)).into()),
+		};
+
+		Ok(SeasonVariables {
+			season: Some(season),
+			season_year: Some(self.year),
+			format_in: format.map(|format| vec![Some(format)]),
+			sort: Some(vec![Some(sort)]),
+			page: Some(self.page),
+			per_page: Some(SEASON_PER_PAGE),
+		})
+	}
+}
+
+/// The season airing on `date` and its year. The winter starts in December and takes the year
+/// of its January.
+
+pub fn current_season(date: NaiveDate) -> (i32, &'static str) {
+	match date.month() {
+		12 => (date.year() + 1, "winter"),
+		1 | 2 => (date.year(), "winter"),
+		3..=5 => (date.year(), "spring"),
+		6..=8 => (date.year(), "summer"),
+		_ => (date.year(), "fall"),
+	}
+}
+
+pub async fn update(
+	ctx: &SerenityContext, component_interaction: &ComponentInteraction, custom_id: &str,
+	db_connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let anilist_client = ctx.data::<BotData>().anilist_client.clone();
+
+	let page = SeasonPage::from_custom_id(custom_id)?;
+
+	let (embed, components) = get_season_message(
+		&page,
+		component_interaction.guild_id,
+		Some(component_interaction.locale.as_str()),
+		db_connection,
+		anilist_client,
+	)
+	.await?;
+
+	let message_rep = CreateInteractionResponseMessage::new()
+		.embed(embed)
+		.components(components);
+
+	let response = CreateInteractionResponse::UpdateMessage(message_rep);
+
+	component_interaction
+		.create_response(&ctx.http, response)
+		.await?;
+
+	Ok(())
+}
+
+/// Sends the embed of the anime picked in the select menu of the list.
+
+pub async fn show_media(
+	ctx: &SerenityContext, component_interaction: &ComponentInteraction,
+	db_connection: Arc<DatabaseConnection>,
+) -> Result<()> {
+	let anilist_client = ctx.data::<BotData>().anilist_client.clone();
+
+	let ComponentInteractionDataKind::StringSelect { values } = &component_interaction.data.kind
+	else {
+		return Err(anyhow!(
+			"The season list select menu is not a string select"
+		));
+	};
+
+	let id: i32 = values
+		.first()
+		.ok_or(anyhow!("No anime selected"))?
+		.parse()?;
+
+	let media = get_anime_by_id(id, anilist_client, false).await?;
+
+	let embed = get_media_embed(
+		ctx,
+		component_interaction.channel_id,
+		component_interaction.guild_id,
+		component_interaction.locale.as_str(),
+		media,
+		db_connection,
+	)
+	.await?;
+
+	let response =
+		CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().embed(embed));
+
+	component_interaction
+		.create_response(&ctx.http, response)
+		.await?;
+
+	Ok(())
+}
+
+/// The embed of a page of the season list, the select menu of its anime and the buttons to the
+/// previous and next pages.
+
+pub async fn get_season_message(
+	page: &SeasonPage, guild_id: Option<GuildId>, locale: Option<&str>,
+	db_connection: Arc<DatabaseConnection>, anilist_client: Arc<AnilistClient>,
+) -> Result<(CreateEmbed<'static>, Vec<CreateActionRow<'static>>)> {
+	let season_localised = load_localization_season(
+		guild_id.map_or(String::from("0"), |id| id.to_string()),
+		locale,
+		db_connection,
+	)
+	.await?;
+
+	let operation = Season::build(page.variables()?);
+
+	let response: GraphQlResponse<Season> =
+		make_request_anilist(operation, false, anilist_client).await?;
+
+	let data = response
+		.data
+		.and_then(|data| data.page)
+		.ok_or(anyhow!("No season page in the response"))?;
+
+	let has_next_page = data
+		.page_info
+		.and_then(|page_info| page_info.has_next_page)
+		.unwrap_or_default();
+
+	let media: Vec<Media> = data
+		.media
+		.unwrap_or_default()
+		.into_iter()
+		.flatten()
+		.collect();
+
+	let first = (page.page - 1) * SEASON_PER_PAGE;
+
+	let description = if media.is_empty() {
+		season_localised.empty.clone()
+	} else {
+		let entries: Vec<String> = media
+			.iter()
+			.enumerate()
+			.map(|(i, media)| entry(first + i as i32 + 1, media, &season_localised))
+			.collect();
+
+		join_entries(&entries)
+	};
+
+	let season_name = match page.season.as_str() {
+		"winter" => &season_localised.winter,
+		"spring" => &season_localised.spring,
+		"summer" => &season_localised.summer,
+		_ => &season_localised.fall,
+	};
+
+	let embed = get_default_embed(None)
+		.title(
+			season_localised
+				.title
+				.replace("$season$", season_name)
+				.replace("$year$", &page.year.to_string()),
+		)
+		.description(description)
+		.footer(CreateEmbedFooter::new(
+			season_localised
+				.page
+				.replace("$page$", &page.page.to_string()),
+		));
+
+	let mut components = Vec::new();
+
+	if !media.is_empty() {
+		let options: Vec<CreateSelectMenuOption> = media
+			.iter()
+			.map(|media| {
+				CreateSelectMenuOption::new(
+					title(media)
+						.chars()
+						.take(MAX_LABEL_LENGTH)
+						.collect::<String>(),
+					media.id.to_string(),
+				)
+			})
+			.collect();
+
+		components.push(CreateActionRow::SelectMenu(
+			CreateSelectMenu::new(
+				SEASON_MEDIA_ID,
+				CreateSelectMenuKind::String {
+					options: Cow::from(options),
+				},
+			)
+			.placeholder(season_localised.select.clone()),
+		));
+	}
+
+	let mut buttons = Vec::new();
+
+	if page.page > 1 {
+		let previous = SeasonPage {
+			page: page.page - 1,
+			..page.clone()
+		};
+
+		buttons.push(
+			CreateButton::new(previous.to_custom_id()).label(season_localised.previous.clone()),
+		);
+	}
+
+	if has_next_page {
+		let next = SeasonPage {
+			page: page.page + 1,
+			..page.clone()
+		};
+
+		buttons.push(CreateButton::new(next.to_custom_id()).label(season_localised.next.clone()));
+	}
+
+	if !buttons.is_empty() {
+		components.push(CreateActionRow::Buttons(Cow::from(buttons)));
+	}
+
+	Ok((embed, components))
+}
+
+fn entry(rank: i32, media: &Media, season_localised: &SeasonLocalised) -> String {
+	let mut details = Vec::new();
+
+	if let Some(score) = media.mean_score {
+		details.push(
+			season_localised
+				.score
+				.replace("$score$", &score.to_string()),
+		);
+	}
+
+	if let Some(episodes) = media.episodes {
+		details.push(
+			season_localised
+				.episodes
+				.replace("$episodes$", &episodes.to_string()),
+		);
+	}
+
+	let mut line = format!(
+		"{}. [{}]({})",
+		rank,
+		trim_title(&title(media), SEASON_TITLE_LENGTH),
+		media.site_url.clone().unwrap_or_default()
+	);
+
+	if !details.is_empty() {
+		line.push_str(&format!(" - {}", details.join(", ")));
+	}
+
+	line
+}
+
+/// Joins the entries, the last ones are left out if the description would be too long.
+
+fn join_entries(entries: &[String]) -> String {
+	let mut description = String::new();
+
+	let mut length = 0;
+
+	for entry in entries {
+		let entry_length = entry.chars().count() + 1;
+
+		if length + entry_length > MAX_DESCRIPTION_LENGTH {
+			break;
+		}
+
+		if !description.is_empty() {
+			description.push('\n');
+		}
+
+		description.push_str(entry);
+
+		length += entry_length;
+	}
+
+	description
+}
+
+fn title(media: &Media) -> String {
+	media
+		.title
+		.clone()
+		.and_then(|title| title.user_preferred.or(title.romaji))
+		.unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_custom_id_round_trip() {
+		let page = SeasonPage {
+			year: 2024,
+			season: "fall".to_string(),
+			format: "short".to_string(),
+			sort: "popularity".to_string(),
+			page: 3,
+		};
+
+		let custom_id = page.to_custom_id();
+
+		assert_ne!(custom_id, SEASON_MEDIA_ID);
+
+		assert_eq!(SeasonPage::from_custom_id(&custom_id).unwrap(), page);
+
+		assert!(SeasonPage::from_custom_id("season_page_2024_fall").is_err());
+
+		assert!(SeasonPage::from_custom_id(SEASON_MEDIA_ID).is_err());
+	}
+
+	#[test]
+
+	fn test_variables() {
+		let page = SeasonPage {
+			year: 2024,
+			season: "spring".to_string(),
+			format: "all".to_string(),
+			sort: "score".to_string(),
+			page: 1,
+		};
+
+		let variables = page.variables().unwrap();
+
+		assert_eq!(variables.season, Some(MediaSeason::Spring));
+
+		assert!(variables.format_in.is_none());
+
+		assert_eq!(variables.sort, Some(vec![Some(MediaSort::ScoreDesc)]));
+
+		let unknown = SeasonPage {
+			season: "monsoon".to_string(),
+			..page
+		};
+
+		assert!(unknown.variables().is_err());
+	}
+
+	#[test]
+
+	fn test_current_season() {
+		let date = |month: u32| NaiveDate::from_ymd_opt(2025, month, 15).unwrap();
+
+		assert_eq!(current_season(date(12)), (2026, "winter"));
+
+		assert_eq!(current_season(date(1)), (2025, "winter"));
+
+		assert_eq!(current_season(date(4)), (2025, "spring"));
+
+		assert_eq!(current_season(date(7)), (2025, "summer"));
+
+		assert_eq!(current_season(date(10)), (2025, "fall"));
+	}
+
+	#[test]
+
+	fn test_join_entries() {
+		let short = vec!["1. a".to_string(), "2. b".to_string()];
+
+		assert_eq!(join_entries(&short), "1. a\n2. b");
+
+		let long = vec!["x".repeat(2000); 3];
+
+		let description = join_entries(&long);
+
+		assert!(description.chars().count() <= MAX_DESCRIPTION_LENGTH);
+
+		assert_eq!(description.lines().count(), 2);
+	}
+}
@@ -1,13 +1,16 @@
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
 
 use serenity::all::{ComponentInteraction, Context as SerenityContext};
 use tracing::trace;
 
-use crate::components::anilist::{list_all_activity, list_register_user};
-use crate::config::DbConfig;
+use crate::components::anilist::season::SEASON_MEDIA_ID;
+use crate::components::anilist::{list_all_activity, list_register_user, schedule, season};
 
 pub async fn components_dispatching(
-	ctx: SerenityContext, component_interaction: ComponentInteraction, db_config: DbConfig,
+	ctx: SerenityContext, component_interaction: ComponentInteraction,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	match component_interaction.data.custom_id.as_str() {
 		s if s.starts_with("user_") => {
@@ -15,14 +18,28 @@ pub async fn components_dispatching(
 
 			let prev_id = user_id.split_at("_".len()).1;
 
-			list_register_user::update(&ctx, &component_interaction, user_id, prev_id, db_config)
-				.await?
+			list_register_user::update(
+				&ctx,
+				&component_interaction,
+				user_id,
+				prev_id,
+				db_connection,
+			)
+			.await?
 		},
 		s if s.starts_with("next_activity_") => {
 			let page_number = s.split_at("next_activity_".len()).1;
 
-			list_all_activity::update(&ctx, &component_interaction, page_number, db_config).await?
+			list_all_activity::update(&ctx, &component_interaction, page_number, db_connection)
+				.await?
+		},
+		s if s.starts_with("schedule_") => {
+			schedule::update(&ctx, &component_interaction, s, db_connection).await?
+		},
+		s if s.starts_with("season_page_") => {
+			season::update(&ctx, &component_interaction, s, db_connection).await?
 		},
+		SEASON_MEDIA_ID => season::show_media(&ctx, &component_interaction, db_connection).await?,
 		_ => trace!("does not exist."),
 	}
 
@@ -1,6 +1,18 @@
-use serde::Deserialize;
+use crate::constant::{
+	ACTIVITY_CATCH_UP_GRACE, CACHE_MAX_CAPACITY, CONFIG_ENV_PREFIX,
+	DEFAULT_COMMAND_USAGE_RETENTION_DAYS, ERROR_REPORT_DEDUP_WINDOW, ERROR_REPORT_MAX_PER_HOUR,
+	SQLITE_IN_MEMORY, TIME_BEFORE_SERVER_IMAGE, TIME_BETWEEN_BLACKLISTED_USER_UPDATE,
+	TIME_BETWEEN_BOT_INFO, TIME_BETWEEN_CACHE_SWEEP, TIME_BETWEEN_CACHE_UPDATE,
+	TIME_BETWEEN_COMMAND_USAGE_PRUNE, TIME_BETWEEN_FOLLOWED_USER_CHECK, TIME_BETWEEN_GAME_UPDATE,
+	TIME_BETWEEN_MANGA_ACTIVITY_CHECK, TIME_BETWEEN_PING_UPDATE, TIME_BETWEEN_RANDOM_STATS_UPDATE,
+	TIME_BETWEEN_SERVER_IMAGE_UPDATE, TIME_BETWEEN_USER_COLOR_UPDATE,
+};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_with::{serde_as, DisplayFromStr, PickFirst};
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct Config {
 	pub bot: BotConfig,
@@ -9,29 +21,76 @@ pub struct Config {
 	pub logging: LoggingConfig,
 	pub ai: AICfg,
 	pub grpc: GrpcCfg,
+	#[serde(default)]
+	pub cache: CacheConfig,
+	#[serde(default)]
+	pub tasks: TasksConfig,
+	#[serde(default)]
+	pub metrics: MetricsConfig,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct BotConfig {
 	pub discord_token: String,
 	pub bot_activity: String,
 	pub remove_old_commands: bool,
 	pub respect_premium: bool,
+	#[serde(default = "default_command_usage_retention_days")]
+	pub command_usage_retention_days: u32,
+	#[serde(default)]
+	pub error_reporting: ErrorReportingConfig,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// The `[bot.error_reporting]` section, where the internal errors of the commands are posted.
+/// Disabled when both the webhook and the channel are empty.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+
+pub struct ErrorReportingConfig {
+	pub webhook_url: String,
+	/// Used when `webhook_url` is empty, the bot needs to be able to send messages in it.
+	pub channel_id: String,
+	/// Seconds during which an identical error is only reported once.
+	pub dedup_window: u64,
+	/// Maximum number of reports sent in an hour.
+	pub max_per_hour: u32,
+}
+
+impl Default for ErrorReportingConfig {
+	fn default() -> Self {
+		ErrorReportingConfig {
+			webhook_url: "".to_string(),
+			channel_id: "".to_string(),
+			dedup_window: ERROR_REPORT_DEDUP_WINDOW,
+			max_per_hour: ERROR_REPORT_MAX_PER_HOUR,
+		}
+	}
+}
+
+fn default_command_usage_retention_days() -> u32 {
+	DEFAULT_COMMAND_USAGE_RETENTION_DAYS
+}
+
+fn default_grpc_bind_address() -> String {
+	"127.0.0.1".to_string()
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct DbConfig {
 	pub db_type: String,
 	pub host: Option<String>,
+	// A string when it comes from the KASUKI_DB_PORT environment variable.
+	#[serde_as(as = "Option<PickFirst<(_, DisplayFromStr)>>")]
 	pub port: Option<u16>,
 	pub user: Option<String>,
 	pub password: Option<String>,
 	pub database: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct ImageConfig {
 	pub save_image: String,
@@ -39,14 +98,24 @@ pub struct ImageConfig {
 	pub token: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct LoggingConfig {
 	pub log_level: String,
 	pub max_log_retention: u32,
+	/// text or json, used by the console and the log files.
+	#[serde(default = "default_log_format")]
+	pub log_format: String,
+	/// OTLP gRPC collector the spans are exported to, the export is disabled when empty.
+	#[serde(default)]
+	pub otlp_endpoint: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+fn default_log_format() -> String {
+	"text".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct AICfg {
 	pub ai_token: String,
@@ -56,7 +125,7 @@ pub struct AICfg {
 	pub transcription: AICfgTranscription,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct AICfgImage {
 	pub ai_image_token: Option<String>,
@@ -67,7 +136,7 @@ pub struct AICfgImage {
 	pub ai_image_style: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct AICfgQuestion {
 	pub ai_question_token: Option<String>,
@@ -75,7 +144,7 @@ pub struct AICfgQuestion {
 	pub ai_question_model: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct AICfgTranscription {
 	pub ai_transcription_token: Option<String>,
@@ -83,16 +152,128 @@ pub struct AICfgTranscription {
 	pub ai_transcription_model: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 
 pub struct GrpcCfg {
 	pub grpc_is_on: bool,
+	/// IP the server listens on, only the local machine by default.
+	#[serde(default = "default_grpc_bind_address")]
+	pub bind_address: String,
 	pub grpc_port: u16,
+	/// Token the clients send as `authorization: Bearer <token>`, required when the server is on.
+	#[serde(default)]
+	pub auth_token: String,
 	pub use_tls: bool,
 	pub tls_cert_path: String,
 	pub tls_key_path: String,
 }
 
+/// The `[cache]` section, every field is optional.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+
+pub struct CacheConfig {
+	/// memory, redis or file.
+	pub backend: String,
+	pub redis_url: String,
+	pub file_path: String,
+	/// Max number of entries of the memory backend.
+	pub max_capacity: u64,
+	pub ttl: CacheTtlConfig,
+}
+
+/// Time to live in seconds of the entries of each namespace.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+
+pub struct CacheTtlConfig {
+	pub anilist: u64,
+	pub vndb: u64,
+}
+
+/// The `[tasks]` section, seconds between two runs of each background task.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+
+pub struct TasksConfig {
+	/// Seconds after its airing an activity missed while the bot was stopped is still sent.
+	pub activity_catch_up_grace: u64,
+	pub manga_activity_check: u64,
+	pub followed_user_check: u64,
+	pub ping_update: u64,
+	pub blacklist_update: u64,
+	pub bot_info_update: u64,
+	pub game_update: u64,
+	pub random_stats_update: u64,
+	pub command_usage_prune: u64,
+	/// Removal of the expired entries of the file cache backend.
+	pub cache_sweep: u64,
+	pub user_color_update: u64,
+	pub server_image_update: u64,
+	/// Delay before the first user color update, the server image waits twice as long.
+	pub server_image_delay: u64,
+}
+
+/// The `[metrics]` section, the Prometheus endpoint is disabled by default.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+
+pub struct MetricsConfig {
+	pub enabled: bool,
+	/// Address and port of the HTTP server serving `/metrics`.
+	pub bind_address: String,
+}
+
+impl Default for MetricsConfig {
+	fn default() -> Self {
+		MetricsConfig {
+			enabled: false,
+			bind_address: "127.0.0.1:9090".to_string(),
+		}
+	}
+}
+
+impl Default for CacheConfig {
+	fn default() -> Self {
+		CacheConfig {
+			backend: "memory".to_string(),
+			redis_url: "redis://127.0.0.1/".to_string(),
+			file_path: "cache".to_string(),
+			max_capacity: CACHE_MAX_CAPACITY,
+			ttl: CacheTtlConfig::default(),
+		}
+	}
+}
+
+impl Default for CacheTtlConfig {
+	fn default() -> Self {
+		CacheTtlConfig {
+			anilist: TIME_BETWEEN_CACHE_UPDATE,
+			vndb: TIME_BETWEEN_CACHE_UPDATE,
+		}
+	}
+}
+
+impl Default for TasksConfig {
+	fn default() -> Self {
+		TasksConfig {
+			activity_catch_up_grace: ACTIVITY_CATCH_UP_GRACE,
+			manga_activity_check: TIME_BETWEEN_MANGA_ACTIVITY_CHECK,
+			followed_user_check: TIME_BETWEEN_FOLLOWED_USER_CHECK,
+			ping_update: TIME_BETWEEN_PING_UPDATE,
+			blacklist_update: TIME_BETWEEN_BLACKLISTED_USER_UPDATE,
+			bot_info_update: TIME_BETWEEN_BOT_INFO,
+			game_update: TIME_BETWEEN_GAME_UPDATE,
+			random_stats_update: TIME_BETWEEN_RANDOM_STATS_UPDATE,
+			command_usage_prune: TIME_BETWEEN_COMMAND_USAGE_PRUNE,
+			cache_sweep: TIME_BETWEEN_CACHE_SWEEP,
+			user_color_update: TIME_BETWEEN_USER_COLOR_UPDATE,
+			server_image_update: TIME_BETWEEN_SERVER_IMAGE_UPDATE,
+			server_image_delay: TIME_BEFORE_SERVER_IMAGE,
+		}
+	}
+}
+
 impl Default for Config {
 	fn default() -> Self {
 		Config {
@@ -101,6 +282,8 @@ impl Default for Config {
 				bot_activity: "".to_string(),
 				remove_old_commands: false,
 				respect_premium: false,
+				command_usage_retention_days: DEFAULT_COMMAND_USAGE_RETENTION_DAYS,
+				error_reporting: ErrorReportingConfig::default(),
 			},
 			db: DbConfig {
 				db_type: "sqlite".to_string(),
@@ -108,7 +291,7 @@ impl Default for Config {
 				port: None,
 				user: None,
 				password: None,
-				database: Some("kasuki".to_string()),
+				database: None,
 			},
 			image: ImageConfig {
 				save_image: "local".to_string(),
@@ -118,6 +301,8 @@ impl Default for Config {
 			logging: LoggingConfig {
 				log_level: "info".to_string(),
 				max_log_retention: 30,
+				log_format: default_log_format(),
+				otlp_endpoint: "".to_string(),
 			},
 			ai: AICfg {
 				ai_token: "".to_string(),
@@ -143,15 +328,56 @@ impl Default for Config {
 			},
 			grpc: GrpcCfg {
 				grpc_is_on: false,
+				bind_address: default_grpc_bind_address(),
 				grpc_port: 443,
+				auth_token: String::new(),
 				use_tls: false,
 				tls_cert_path: "cert/cert.pem".to_string(),
 				tls_key_path: "cert/key.pem".to_string(),
 			},
+			cache: CacheConfig::default(),
+			tasks: TasksConfig::default(),
+			metrics: MetricsConfig::default(),
 		}
 	}
 }
 
+impl DbConfig {
+	pub fn is_in_memory(&self) -> bool {
+		self.db_type == "sqlite" && self.database.as_deref() == Some(SQLITE_IN_MEMORY)
+	}
+
+	/// Returns every problem of the `[db]` section, `get_url` exits on them.
+
+	pub fn validate(&self) -> Vec<String> {
+		let mut problems = Vec::new();
+
+		match self.db_type.as_str() {
+			"sqlite" => {},
+			"postgresql" => {
+				let fields = [
+					("db.host", self.host.is_none()),
+					("db.port", self.port.is_none()),
+					("db.user", self.user.is_none()),
+					("db.password", self.password.is_none()),
+				];
+
+				for (field, is_missing) in fields {
+					if is_missing {
+						problems.push(format!("{} is required for postgresql", field));
+					}
+				}
+			},
+			db_type => problems.push(format!(
+				"db.db_type \"{}\" is not supported, use sqlite or postgresql",
+				db_type
+			)),
+		}
+
+		problems
+	}
+}
+
 impl Config {
 	pub fn set_default_value_on_none(&mut self) {
 		if self.ai.image.ai_image_token.is_none()
@@ -263,3 +489,414 @@ impl Config {
 		}
 	}
 }
+
+impl Config {
+	/// Reads the config at `path`, applies the `KASUKI_*` environment variables, fills the
+	/// default values and validates it. Every problem found is reported in the error.
+
+	pub fn load(path: &str) -> Result<Config> {
+		let content = std::fs::read_to_string(path).context(format!("Failed to read {}", path))?;
+
+		Self::from_toml(&content, std::env::vars())
+	}
+
+	fn from_toml(content: &str, vars: impl Iterator<Item = (String, String)>) -> Result<Config> {
+		let toml: toml::Value = toml::from_str(content).context("Failed to parse the config")?;
+
+		let mut json = serde_json::to_value(toml).context("Failed to convert the config")?;
+
+		let mut problems = apply_env_overrides(&mut json, vars);
+
+		match serde_json::from_value::<Config>(json) {
+			Ok(mut config) => {
+				config.set_default_value_on_none();
+
+				problems.extend(config.validate(false));
+
+				if problems.is_empty() {
+					return Ok(config);
+				}
+			},
+			Err(e) => problems.push(e.to_string()),
+		}
+
+		Err(anyhow!("Invalid config:\n{}", problems.join("\n")))
+	}
+
+	/// Returns every problem found in the config, to be called after
+	/// `set_default_value_on_none`. The AI settings are only required when `ai_enabled`.
+
+	pub fn validate(&self, ai_enabled: bool) -> Vec<String> {
+		let mut problems = Vec::new();
+
+		if self.bot.discord_token.is_empty() {
+			problems.push("bot.discord_token is empty".to_string());
+		}
+
+		problems.extend(self.db.validate());
+
+		let error_reporting = &self.bot.error_reporting;
+
+		if !error_reporting.webhook_url.is_empty() {
+			check_url(
+				&mut problems,
+				"bot.error_reporting.webhook_url",
+				&error_reporting.webhook_url,
+			);
+		} else if !error_reporting.channel_id.is_empty()
+			&& error_reporting.channel_id.parse::<u64>().is_err()
+		{
+			problems.push(format!(
+				"bot.error_reporting.channel_id \"{}\" is not a channel id",
+				error_reporting.channel_id
+			));
+		}
+
+		match self.image.save_image.as_str() {
+			"local" | "false" => {},
+			"remote" => {
+				if self
+					.image
+					.save_server
+					.as_deref()
+					.unwrap_or_default()
+					.is_empty()
+				{
+					problems.push(
+						"image.save_server is required to save the images remotely".to_string(),
+					);
+				}
+
+				if self.image.token.as_deref().unwrap_or_default().is_empty() {
+					problems
+						.push("image.token is required to save the images remotely".to_string());
+				}
+			},
+			save_image => problems.push(format!(
+				"image.save_image \"{}\" is not supported, use local, remote or false",
+				save_image
+			)),
+		}
+
+		match self.logging.log_format.as_str() {
+			"text" | "json" => {},
+			log_format => problems.push(format!(
+				"logging.log_format \"{}\" is not supported, use text or json",
+				log_format
+			)),
+		}
+
+		if !self.logging.otlp_endpoint.is_empty() {
+			check_url(
+				&mut problems,
+				"logging.otlp_endpoint",
+				&self.logging.otlp_endpoint,
+			);
+		}
+
+		match self.cache.backend.as_str() {
+			"memory" | "file" => {},
+			"redis" => check_url(&mut problems, "cache.redis_url", &self.cache.redis_url),
+			backend => problems.push(format!(
+				"cache.backend \"{}\" is not supported, use memory, redis or file",
+				backend
+			)),
+		}
+
+		let intervals = [
+			(
+				"tasks.manga_activity_check",
+				self.tasks.manga_activity_check,
+			),
+			("tasks.followed_user_check", self.tasks.followed_user_check),
+			("tasks.ping_update", self.tasks.ping_update),
+			("tasks.blacklist_update", self.tasks.blacklist_update),
+			("tasks.bot_info_update", self.tasks.bot_info_update),
+			("tasks.game_update", self.tasks.game_update),
+			("tasks.random_stats_update", self.tasks.random_stats_update),
+			("tasks.command_usage_prune", self.tasks.command_usage_prune),
+			("tasks.cache_sweep", self.tasks.cache_sweep),
+			("tasks.user_color_update", self.tasks.user_color_update),
+			("tasks.server_image_update", self.tasks.server_image_update),
+		];
+
+		for (field, interval) in intervals {
+			if interval == 0 {
+				problems.push(format!("{} must be greater than 0", field));
+			}
+		}
+
+		if self.metrics.enabled
+			&& self
+				.metrics
+				.bind_address
+				.parse::<std::net::SocketAddr>()
+				.is_err()
+		{
+			problems.push(format!(
+				"metrics.bind_address \"{}\" is not an address and a port like 127.0.0.1:9090",
+				self.metrics.bind_address
+			));
+		}
+
+		if self.grpc.grpc_is_on {
+			if self.grpc.auth_token.is_empty() {
+				problems.push("grpc.auth_token is required when grpc_is_on is enabled".to_string());
+			}
+
+			if self.grpc.bind_address.parse::<std::net::IpAddr>().is_err() {
+				problems.push(format!(
+					"grpc.bind_address \"{}\" is not an IP address like 127.0.0.1",
+					self.grpc.bind_address
+				));
+			}
+		}
+
+		if self.grpc.grpc_is_on && self.grpc.use_tls {
+			for (field, path) in [
+				("grpc.tls_cert_path", &self.grpc.tls_cert_path),
+				("grpc.tls_key_path", &self.grpc.tls_key_path),
+			] {
+				if path.is_empty() {
+					problems.push(format!("{} is required when use_tls is enabled", field));
+				} else if !std::path::Path::new(path).exists() {
+					problems.push(format!("{}: {} does not exist", field, path));
+				}
+			}
+		}
+
+		if ai_enabled {
+			let ai = [
+				(
+					"ai.image",
+					&self.ai.image.ai_image_token,
+					&self.ai.image.ai_image_base_url,
+				),
+				(
+					"ai.question",
+					&self.ai.question.ai_question_token,
+					&self.ai.question.ai_question_base_url,
+				),
+				(
+					"ai.transcription",
+					&self.ai.transcription.ai_transcription_token,
+					&self.ai.transcription.ai_transcription_base_url,
+				),
+			];
+
+			// The empty values were replaced by ai.ai_token and ai.ai_base_url.
+			for (section, token, base_url) in ai {
+				if token.as_deref().unwrap_or_default().is_empty() {
+					problems.push(format!(
+						"{}: no token, set it or ai.ai_token as the AI module is enabled",
+						section
+					));
+				}
+
+				check_url(
+					&mut problems,
+					&format!("{} base url", section),
+					base_url.as_deref().unwrap_or_default(),
+				);
+			}
+		}
+
+		problems
+	}
+}
+
+/// Sets the config fields named by the `KASUKI_*` environment variables, the other variables
+/// are ignored. Returns the values that can't be used.
+
+fn apply_env_overrides(
+	config: &mut Value, vars: impl Iterator<Item = (String, String)>,
+) -> Vec<String> {
+	// The fields are listed from the default config so the optional ones are included.
+	let mut fields = Vec::new();
+
+	if let Ok(default) = serde_json::to_value(Config::default()) {
+		collect_fields(&default, &mut Vec::new(), &mut fields);
+	}
+
+	let mut problems = Vec::new();
+
+	for (name, value) in vars {
+		let Some(field_name) = name.strip_prefix(CONFIG_ENV_PREFIX) else {
+			continue;
+		};
+
+		let Some((path, default)) = fields
+			.iter()
+			.find(|(path, _)| path.join("_").to_uppercase() == field_name)
+		else {
+			continue;
+		};
+
+		let value = match default {
+			Value::Bool(_) => match value.parse::<bool>() {
+				Ok(value) => Value::Bool(value),
+				Err(_) => {
+					problems.push(format!("{} must be true or false", name));
+
+					continue;
+				},
+			},
+			Value::Number(_) => match value.parse::<u64>() {
+				Ok(value) => Value::from(value),
+				Err(_) => {
+					problems.push(format!("{} must be a positive number", name));
+
+					continue;
+				},
+			},
+			_ => Value::String(value),
+		};
+
+		set_field(config, path, value);
+	}
+
+	problems
+}
+
+fn collect_fields(value: &Value, path: &mut Vec<String>, fields: &mut Vec<(Vec<String>, Value)>) {
+	match value {
+		Value::Object(map) => {
+			for (key, value) in map {
+				path.push(key.clone());
+
+				collect_fields(value, path, fields);
+
+				path.pop();
+			}
+		},
+		value => fields.push((path.clone(), value.clone())),
+	}
+}
+
+fn set_field(config: &mut Value, path: &[String], value: Value) {
+	let Some((field, tables)) = path.split_last() else {
+		return;
+	};
+
+	let mut current = config;
+
+	// The missing tables are created, a section can come only from the environment.
+	for table in tables {
+		current = match current {
+			Value::Object(map) => map
+				.entry(table.clone())
+				.or_insert_with(|| Value::Object(Map::new())),
+			_ => return,
+		};
+	}
+
+	if let Value::Object(map) = current {
+		map.insert(field.clone(), value);
+	}
+}
+
+fn check_url(problems: &mut Vec<String>, field: &str, url: &str) {
+	if url.is_empty() {
+		problems.push(format!("{} is empty", field));
+	} else if let Err(e) = reqwest::Url::parse(url) {
+		problems.push(format!("{} \"{}\" is not a valid url: {}", field, url, e));
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_validate() {
+		let mut config = Config::default();
+
+		config.set_default_value_on_none();
+
+		let problems = config.validate(true);
+
+		assert!(problems.contains(&"bot.discord_token is empty".to_string()));
+
+		assert!(problems
+			.iter()
+			.any(|problem| problem.starts_with("ai.question")));
+
+		config.bot.discord_token = "token".to_string();
+
+		config.ai.ai_token = "ai token".to_string();
+
+		config.ai.ai_base_url = "https://api.openai.com/v1/".to_string();
+
+		config.set_default_value_on_none();
+
+		assert!(config.validate(true).is_empty());
+
+		config.db.db_type = "postgresql".to_string();
+
+		assert_eq!(config.validate(false).len(), 4);
+	}
+
+	#[test]
+
+	fn test_env_overrides() {
+		let content = std::fs::read_to_string("../config.example.toml").unwrap();
+
+		let vars = [
+			("KASUKI_BOT_DISCORD_TOKEN", "token"),
+			("KASUKI_DB_PORT", "5433"),
+			("KASUKI_DB_PASSWORD", "1234"),
+			("KASUKI_GRPC_GRPC_IS_ON", "false"),
+			("KASUKI_CACHE_TTL_ANILIST", "60"),
+			("KASUKI_AI_IMAGE_AI_IMAGE_MODEL", "dall-e-2"),
+			("KASUKI_TASKS_PING_UPDATE", "60"),
+			("KASUKI_CONFIG", "config.toml"),
+			("PATH", "/usr/bin"),
+		];
+
+		let vars = vars.map(|(name, value)| (name.to_string(), value.to_string()));
+
+		let config = Config::from_toml(&content, vars.into_iter()).unwrap();
+
+		assert_eq!(config.bot.discord_token, "token");
+
+		assert_eq!(config.db.port, Some(5433));
+
+		assert_eq!(config.db.password.as_deref(), Some("1234"));
+
+		assert!(!config.grpc.grpc_is_on);
+
+		assert_eq!(config.cache.ttl.anilist, 60);
+
+		assert_eq!(config.ai.image.ai_image_model.as_deref(), Some("dall-e-2"));
+
+		assert_eq!(config.tasks.ping_update, 60);
+
+		assert_eq!(config.tasks.game_update, TIME_BETWEEN_GAME_UPDATE);
+
+		// Every problem is reported, not only the first one.
+		let vars = [
+			("KASUKI_GRPC_USE_TLS", "yes"),
+			("KASUKI_GRPC_GRPC_IS_ON", "true"),
+			("KASUKI_IMAGE_SAVE_IMAGE", "remote"),
+			("KASUKI_GRPC_TLS_CERT_PATH", ""),
+		];
+
+		let vars = vars.map(|(name, value)| (name.to_string(), value.to_string()));
+
+		let error = Config::from_toml(&content, vars.into_iter())
+			.unwrap_err()
+			.to_string();
+
+		assert!(error.contains("KASUKI_GRPC_USE_TLS must be true or false"));
+
+		assert!(error.contains("bot.discord_token is empty"));
+
+		assert!(error.contains("image.save_server is required"));
+
+		assert!(error.contains("grpc.tls_cert_path is required"));
+
+		assert!(error.contains("grpc.auth_token is required"));
+	}
+}
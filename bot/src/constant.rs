@@ -4,7 +4,6 @@ use std::env;
 use once_cell::sync::Lazy;
 use serenity::all::Colour;
 
-
 /// Delay before a new thread is spawned.
 /// Delay between ping updates.
 
@@ -38,14 +37,78 @@ pub const TIME_BETWEEN_BOT_INFO: u64 = 1_800;
 
 pub const TIME_BETWEEN_BLACKLISTED_USER_UPDATE: u64 = 3600;
 
-/// time between activity check.
+/// Seconds after its airing an activity missed while the bot was stopped is still sent.
+
+pub const ACTIVITY_CATCH_UP_GRACE: u64 = 21_600;
+
+/// Seconds before retrying an activity whose next episode could not be found.
+
+pub const ACTIVITY_RETRY_DELAY: i64 = 300;
+
+/// Max seconds the activity scheduler sleeps before checking the time again.
+
+pub const ACTIVITY_SCHEDULER_MAX_SLEEP: u64 = 60;
+
+/// time between the checks of the new manga chapters.
+
+pub const TIME_BETWEEN_MANGA_ACTIVITY_CHECK: u64 = 1800;
+
+/// time between the checks of the followed users activities.
+
+pub const TIME_BETWEEN_FOLLOWED_USER_CHECK: u64 = 300;
+
+/// Number of followed users requested in one AniList request.
+
+pub const FOLLOWED_USER_BATCH: usize = 25;
+
+/// Number of activities in a page of the followed users activities.
+
+pub const FOLLOWED_USER_PER_PAGE: i32 = 50;
+
+/// Max number of pages of activities read on each check of a batch of followed users.
+
+pub const FOLLOWED_USER_MAX_PAGES: i32 = 3;
+
+/// time between the pruning of the old command usage.
+
+pub const TIME_BETWEEN_COMMAND_USAGE_PRUNE: u64 = 3_600;
 
-pub const TIME_BETWEEN_ACTIVITY_CHECK: u64 = 1;
+/// time between two removals of the expired entries of the file cache.
+
+pub const TIME_BETWEEN_CACHE_SWEEP: u64 = 3_600;
+
+/// Delay in seconds before a failed background task is restarted, doubled on each failure in a row.
+
+pub const TASK_RESTART_BASE_DELAY: u64 = 1;
+
+/// Max delay in seconds before a failed background task is restarted.
+
+pub const TASK_RESTART_MAX_DELAY: u64 = 300;
+
+/// Seconds given to the running commands and activity sends to finish on shutdown.
+
+pub const SHUTDOWN_TIMEOUT: u64 = 30;
+
+/// Number of days the command usage is kept by default.
+
+pub const DEFAULT_COMMAND_USAGE_RETENTION_DAYS: u32 = 90;
 
 /// time between random stats update.
 
 pub const TIME_BETWEEN_RANDOM_STATS_UPDATE: u64 = 86_400;
 
+/// Number of request per minute allowed by the AniList API, shared by every command and task.
+
+pub const ANILIST_RATE_LIMIT: u32 = 90;
+
+/// Number of retry of an AniList request answered with a 429 or a 5xx.
+
+pub const ANILIST_MAX_RETRY: u32 = 3;
+
+/// Delay in milliseconds before the first retry of an AniList request, doubled on each retry.
+
+pub const ANILIST_RETRY_BASE_DELAY: u64 = 1_000;
+
 /// Max capacity for the cache.
 
 pub const CACHE_MAX_CAPACITY: u64 = 100_000;
@@ -68,7 +131,47 @@ pub const MEMBER_LIST_LIMIT: u16 = 10;
 
 pub const ACTIVITY_LIST_LIMIT: u64 = 10;
 
-/// Path to the data SQLite database.
+/// Number of airings in a page of the airing schedule.
+
+pub const SCHEDULE_PER_PAGE: i32 = 50;
+
+/// Max number of pages of airings read for one day of the airing schedule.
+
+pub const SCHEDULE_MAX_PAGES: i32 = 4;
+
+/// Max number of rows in the table of the airing schedule.
+
+pub const SCHEDULE_TABLE_ROWS: usize = 50;
+
+/// Max length of a title in the table of the airing schedule.
+
+pub const SCHEDULE_TITLE_LENGTH: usize = 30;
+
+/// Number of anime in a page of the season list, a select menu holds at most 25 options.
+
+pub const SEASON_PER_PAGE: i32 = 25;
+
+/// Max length of a title in the season list, 25 lines of it fit in an embed description.
+
+pub const SEASON_TITLE_LENGTH: usize = 60;
+
+/// Media kind of the activities sent when an episode airs.
+
+pub const ACTIVITY_KIND_ANIME: &str = "anime";
+
+/// Media kind of the activities sent when a chapter is released.
+
+pub const ACTIVITY_KIND_MANGA: &str = "manga";
+
+/// A webhook message holds at most 10 embeds, the activities send at most this many at once.
+
+pub const MAX_WEBHOOK_EMBEDS: usize = 10;
+
+/// Base url of the MangaUpdates API, used to detect the new manga chapters.
+
+pub const MANGAUPDATES_API_URL: &str = "https://api.mangaupdates.com/v1";
+
+/// Path to the command usage file used before the command_usage table, imported on startup.
 
 pub const COMMAND_USE_PATH: &str = "db/command_use.json";
 
@@ -78,6 +181,14 @@ pub const NEW_MEMBER_PATH: &str = "db/new_member.json";
 
 pub const NEW_MEMBER_IMAGE_PATH: &str = "new_member_image/";
 
+/// Path to the SQLite database when none is set in the config.
+
+pub const SQLITE_DEFAULT_PATH: &str = "db/kasuki.db";
+
+/// Database name that makes SQLite run in memory.
+
+pub const SQLITE_IN_MEMORY: &str = ":memory:";
+
 /*
 App embed color.
  */
@@ -92,6 +203,10 @@ pub const HEX_COLOR: &str = "#FAB1ED";
 
 pub const OTHER_CRATE_LEVEL: &str = "warn";
 
+/// Log level of the database queries exported to the OTLP collector.
+
+pub const SQL_QUERY_LEVEL: &str = "sqlx::query=info";
+
 /// Default string value.
 
 pub const UNKNOWN: &str = "Unknown";
@@ -104,6 +219,19 @@ pub static LANG_MAP: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
 	languages.iter().cloned().collect()
 });
 
+/// Directory of the localized messages, loaded at startup.
+
+pub const LOCALIZATION_PATH: &str = "json/message";
+
+/// Config file read when neither `--config` nor `KASUKI_CONFIG` is set.
+
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// Prefix of the environment variables overriding the config, `KASUKI_BOT_DISCORD_TOKEN`
+/// overrides `discord_token` in `[bot]`.
+
+pub const CONFIG_ENV_PREFIX: &str = "KASUKI_";
+
 /// Path to the logs.
 
 pub const LOGS_PATH: &str = "./logs";
@@ -112,6 +240,18 @@ pub const LOGS_PATH: &str = "./logs";
 
 pub const LOGS_PREFIX: &str = "kasuki_";
 
+/// Seconds during which an identical internal error is reported only once.
+
+pub const ERROR_REPORT_DEDUP_WINDOW: u64 = 3600;
+
+/// Maximum number of internal errors reported in an hour.
+
+pub const ERROR_REPORT_MAX_PER_HOUR: u32 = 20;
+
+/// Name of the service in the exported spans.
+
+pub const OTEL_SERVICE_NAME: &str = "kasuki";
+
 /// Suffix for the logs
 
 pub const LOGS_SUFFIX: &str = "log";
@@ -126,7 +266,6 @@ pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Vec of all available bot commands.
 
-
 /// Used library.
 
 pub const LIBRARY: &str = "serenity";
@@ -16,6 +16,10 @@ pub struct Model {
 	pub image: String,
 	pub delay: i32,
 	pub timestamp: DateTime,
+	pub media_kind: String,
+	pub external_id: Option<String>,
+	pub sent_episode: Option<i32>,
+	pub last_chapter: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -17,6 +17,8 @@ pub struct Model {
 pub enum Relation {
 	#[sea_orm(has_many = "super::activity_data::Entity")]
 	ActivityData,
+	#[sea_orm(has_many = "super::followed_user::Entity")]
+	FollowedUser,
 	#[sea_orm(has_one = "super::guild_lang::Entity")]
 	GuildLang,
 	#[sea_orm(has_one = "super::module_activation::Entity")]
@@ -33,6 +35,12 @@ impl Related<super::activity_data::Entity> for Entity {
 	}
 }
 
+impl Related<super::followed_user::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::FollowedUser.def()
+	}
+}
+
 impl Related<super::guild_lang::Entity> for Entity {
 	fn to() -> RelationDef {
 		Relation::GuildLang.def()
@@ -74,6 +82,8 @@ impl ActiveModelBehavior for ActiveModel {}
 pub enum RelatedEntity {
 	#[sea_orm(entity = "super::activity_data::Entity")]
 	ActivityData,
+	#[sea_orm(entity = "super::followed_user::Entity")]
+	FollowedUser,
 	#[sea_orm(entity = "super::guild_lang::Entity")]
 	GuildLang,
 	#[sea_orm(entity = "super::module_activation::Entity")]
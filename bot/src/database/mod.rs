@@ -3,6 +3,8 @@
 pub mod prelude;
 
 pub mod activity_data;
+pub mod command_usage;
+pub mod followed_user;
 pub mod guild_data;
 pub mod guild_lang;
 pub mod guild_subscription;
@@ -1,6 +1,8 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.0.0
 
 pub use super::activity_data::Entity as ActivityData;
+pub use super::command_usage::Entity as CommandUsage;
+pub use super::followed_user::Entity as FollowedUser;
 pub use super::guild_data::Entity as GuildData;
 pub use super::guild_lang::Entity as GuildLang;
 pub use super::guild_subscription::Entity as GuildSubscription;
@@ -3,49 +3,147 @@ use serenity::all::{
 	CommandInteraction, Context, CreateEmbed, CreateInteractionResponse,
 	CreateInteractionResponseFollowup, CreateInteractionResponseMessage, Timestamp,
 };
-use tracing::error;
+use tracing::{error, warn};
+use uuid::Uuid;
 
 use crate::constant::COLOR;
+use crate::error_management::error_report::report_error;
+use crate::error_management::kasuki_error::KasukiError;
 use crate::event_handler::BotData;
+use crate::metrics::{ErrorLabels, METRICS};
+use crate::structure::message::error::{load_localization_error, ErrorLocalised};
 
-const ERROR_MESSAGE: &str = "**There was an error while processing the command**\
-    \n**This error is most likely an input error** \
-    **like searching for non existent anime, requesting nsfw image to the ai, etc.**\n \
-    **but in some case it's a server or programming error**.\
-    **in this case report it to me and I will try to fix it the fastest I can** \
-    \n\n\n";
+/// Replies to a failed command with the localized message of its error.
+///
+/// Internal errors get a correlation id that is logged with the error and shown to the user,
+/// so a report can be matched with the logs without showing the internals.
 
 pub async fn command_dispatching(
-	message: String, command_interaction: &CommandInteraction, ctx: &Context,
+	error: anyhow::Error, command_interaction: &CommandInteraction, ctx: &Context,
 ) {
-	error!("{}", message.replace("\\n", "\n"));
-
-	match send_error(message.clone(), command_interaction, ctx).await {
-		Ok(_) => {},
-		Err(_) => match send_differed_error(message, command_interaction, ctx).await {
-			Ok(_) => {},
-			Err(e) => {
-				error!("{}", e);
-			},
+	let error = KasukiError::from(error);
+
+	METRICS
+		.command_errors
+		.get_or_create(&ErrorLabels { kind: error.kind() })
+		.inc();
+
+	let correlation_id = match error {
+		KasukiError::Internal(_) => {
+			let correlation_id = Uuid::new_v4().to_string();
+
+			error!(
+				%correlation_id,
+				code = error.code(),
+				"{}",
+				error.details().replace("\\n", "\n")
+			);
+
+			Some(correlation_id)
 		},
+		_ => {
+			warn!(code = error.code(), "{}", error.details());
+
+			None
+		},
+	};
+
+	let guild_id = match command_interaction.guild_id {
+		Some(id) => id.to_string(),
+		None => String::from("0"),
+	};
+
+	let db_connection = ctx.data::<BotData>().db_connection.clone();
+
+	let localised = match load_localization_error(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await
+	{
+		Ok(localised) => localised,
+		Err(e) => {
+			error!("Failed to load the error localization. {:#}", e);
+
+			default_localised()
+		},
+	};
+
+	let embed = error_embed(&error, correlation_id.clone(), &localised, ctx);
+
+	if send_error(embed.clone(), command_interaction, ctx)
+		.await
+		.is_err()
+	{
+		if let Err(e) = send_differed_error(embed, command_interaction, ctx).await {
+			error!("{}", e);
+		}
+	}
+
+	// Reported once the user got an answer, the report can wait for the rate limit of Discord.
+	if let Some(correlation_id) = correlation_id {
+		report_error(ctx, command_interaction, &error, &correlation_id).await;
 	}
 }
 
-async fn send_error(
-	e: String, command_interaction: &CommandInteraction, ctx: &Context,
-) -> Result<(), String> {
-	let error_message = format!("{}\n{}", ERROR_MESSAGE, e);
+fn error_embed(
+	error: &KasukiError, correlation_id: Option<String>, localised: &ErrorLocalised, ctx: &Context,
+) -> CreateEmbed<'static> {
+	let mut description = error.localised_message(localised).to_string();
+
+	if error.show_details() && !error.details().is_empty() {
+		// censor url and token in the error message
+		let details = censor_url_and_token(error.details().to_string(), ctx);
+
+		description = format!("{}\n\n{}", description, details);
+	}
 
-	// censor url and token in the error message
-	let error_message = censor_url_and_token(error_message, ctx);
+	description = format!(
+		"{}\n\n{}",
+		description,
+		localised.code.replace("$code$", &error.code().to_string())
+	);
+
+	if let Some(correlation_id) = correlation_id {
+		description = format!(
+			"{}\n{}",
+			description,
+			localised.reference.replace("$id$", &correlation_id)
+		);
+	}
 
-	let builder_embed = CreateEmbed::new()
+	CreateEmbed::new()
 		.timestamp(Timestamp::now())
 		.color(COLOR)
-		.description(error_message)
-		.title("There was an error while processing the command");
+		.description(description)
+		.title(localised.title.clone())
+}
+
+// Used when the json file can't be read, so the user still gets an answer.
+fn default_localised() -> ErrorLocalised {
+	ErrorLocalised {
+		title: "There was an error while processing the command".to_string(),
+		user_input: "The command could not be run with the given options.".to_string(),
+		not_found: "Nothing was found for this search.".to_string(),
+		upstream_api: "An external service did not answer correctly. Please try again later."
+			.to_string(),
+		permission: "The bot or you are missing a permission needed by this command.".to_string(),
+		premium_limit:
+			"You have reached your hourly limit for this command. Please try again later."
+				.to_string(),
+		internal: "An unexpected error happened. If it keeps happening, please report it with \
+		           the reference below."
+			.to_string(),
+		code: "Error code: $code$".to_string(),
+		reference: "Reference: `$id$`".to_string(),
+	}
+}
 
-	let builder_message = CreateInteractionResponseMessage::new().embed(builder_embed);
+async fn send_error(
+	embed: CreateEmbed<'static>, command_interaction: &CommandInteraction, ctx: &Context,
+) -> Result<(), String> {
+	let builder_message = CreateInteractionResponseMessage::new().embed(embed);
 
 	let builder = CreateInteractionResponse::Message(builder_message);
 
@@ -58,20 +156,9 @@ async fn send_error(
 }
 
 async fn send_differed_error(
-	e: String, command_interaction: &CommandInteraction, ctx: &Context,
+	embed: CreateEmbed<'static>, command_interaction: &CommandInteraction, ctx: &Context,
 ) -> Result<(), String> {
-	let error_message = format!("{}\n{}", ERROR_MESSAGE, e);
-
-	// censor url and token in the error message
-	let error_message = censor_url_and_token(error_message, ctx);
-
-	let builder_embed = CreateEmbed::new()
-		.timestamp(Timestamp::now())
-		.color(COLOR)
-		.description(error_message)
-		.title("There was an error while processing the command");
-
-	let builder = CreateInteractionResponseFollowup::new().embed(builder_embed);
+	let builder = CreateInteractionResponseFollowup::new().embed(embed);
 
 	let _ = command_interaction
 		.create_followup(&ctx.http, builder)
@@ -81,7 +168,9 @@ async fn send_differed_error(
 	Ok(())
 }
 
-fn censor_url_and_token(error_message: String, ctx: &Context) -> String {
+/// Replaces the tokens, the database credentials and the urls in `error_message`.
+
+pub fn censor_url_and_token(error_message: String, ctx: &Context) -> String {
 	let config = ctx.data::<BotData>().config.clone();
 
 	let mut error_message = error_message;
@@ -92,7 +181,11 @@ fn censor_url_and_token(error_message: String, ctx: &Context) -> String {
 
 	let db_pass = config.db.password.clone().unwrap_or_default();
 
-	let db_port = config.db.port.unwrap_or_default().to_string();
+	let db_port = config
+		.db
+		.port
+		.map(|port| port.to_string())
+		.unwrap_or_default();
 
 	let db_host = config.db.host.clone().unwrap_or_default();
 
@@ -112,15 +205,21 @@ fn censor_url_and_token(error_message: String, ctx: &Context) -> String {
 		.clone()
 		.unwrap_or_default();
 
-	error_message = error_message
-		.replace(&discord_token, "[REDACTED]")
-		.replace(&image_token, "[REDACTED]")
-		.replace(&transcript_token, "[REDACTED]")
-		.replace(&chat_token, "[REDACTED]")
-		.replace(&db_user, "[REDACTED]")
-		.replace(&db_pass, "[REDACTED]")
-		.replace(&db_port, "[REDACTED]")
-		.replace(&db_host, "[REDACTED]");
+	let secrets = [
+		discord_token,
+		image_token,
+		transcript_token,
+		chat_token,
+		db_user,
+		db_pass,
+		db_port,
+		db_host,
+	];
+
+	// An empty secret (sqlite has no user or password) would be replaced between every character.
+	for secret in secrets.iter().filter(|secret| !secret.is_empty()) {
+		error_message = error_message.replace(secret, "[REDACTED]");
+	}
 
 	// replace url with [REDACTED]
 	let url_regex = Regex::new(r"https?://(www\.)?[-a-zA-Z0-9@:%._+~#=]{1,256}\.[a-zA-Z0-9()]{1,6}\b([-a-zA-Z0-9()@:%_+.~#?&/=]*)").unwrap();
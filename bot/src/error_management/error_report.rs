@@ -0,0 +1,314 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serenity::all::{
+	ChannelId, CommandInteraction, Context, CreateEmbed, CreateEmbedFooter, CreateMessage,
+	ExecuteWebhook, ResolvedOption, ResolvedValue, Timestamp, Webhook,
+};
+use tracing::warn;
+
+use crate::command::guess_kind::guess_command_kind;
+use crate::config::ErrorReportingConfig;
+use crate::constant::COLOR;
+use crate::error_management::error_dispatch::censor_url_and_token;
+use crate::error_management::kasuki_error::KasukiError;
+use crate::event_handler::BotData;
+
+/// Maximum length of the error chain in the report, the embed description is limited to 4096.
+
+const MAX_CHAIN_LENGTH: usize = 3500;
+
+/// Maximum length of the arguments in the report, an embed field is limited to 1024.
+
+const MAX_ARGUMENTS_LENGTH: usize = 1000;
+
+/// Decides which internal errors are posted to the `[bot.error_reporting]` channel.
+#[derive(Default)]
+
+pub struct ErrorReporter {
+	state: Mutex<ReportState>,
+}
+
+#[derive(Default)]
+
+struct ReportState {
+	/// Last report of each error, with the number of identical errors not reported since.
+	reported: HashMap<String, (Instant, u32)>,
+	/// Time of the reports sent in the last hour.
+	sent: VecDeque<Instant>,
+}
+
+impl ErrorReporter {
+	/// Whether the error identified by `key` should be reported now. Returns the number of
+	/// identical errors that were not reported since the last report of this one.
+
+	fn check(&self, key: &str, now: Instant, config: &ErrorReportingConfig) -> Option<u32> {
+		let mut state = self.state.lock().ok()?;
+
+		let dedup_window = Duration::from_secs(config.dedup_window);
+
+		let hour = Duration::from_secs(3600);
+
+		// The count of the skipped errors is kept one more window, for their next report.
+		state
+			.reported
+			.retain(|_, (reported_at, _)| now.duration_since(*reported_at) < dedup_window * 2);
+
+		while state
+			.sent
+			.front()
+			.is_some_and(|sent_at| now.duration_since(*sent_at) >= hour)
+		{
+			state.sent.pop_front();
+		}
+
+		if let Some((reported_at, skipped)) = state.reported.get_mut(key) {
+			if now.duration_since(*reported_at) < dedup_window {
+				*skipped += 1;
+
+				return None;
+			}
+		}
+
+		if state.sent.len() >= config.max_per_hour as usize {
+			return None;
+		}
+
+		state.sent.push_back(now);
+
+		// The errors skipped by the rate limit are not counted, only the duplicates are.
+		let skipped = state
+			.reported
+			.insert(key.to_string(), (now, 0))
+			.map(|(_, skipped)| skipped)
+			.unwrap_or_default();
+
+		Some(skipped)
+	}
+}
+
+/// Posts an internal error of a command to the `[bot.error_reporting]` webhook or channel.
+/// Identical errors are reported once per `dedup_window` and at most `max_per_hour` reports are
+/// sent in an hour.
+
+pub async fn report_error(
+	ctx: &Context, command_interaction: &CommandInteraction, error: &KasukiError,
+	correlation_id: &str,
+) {
+	let bot_data = ctx.data::<BotData>().clone();
+
+	let config = &bot_data.config.bot.error_reporting;
+
+	if config.webhook_url.is_empty() && config.channel_id.is_empty() {
+		return;
+	}
+
+	let (_, command_name) = guess_command_kind(command_interaction);
+
+	let chain = censor_url_and_token(error.details().replace("\\n", "\n"), ctx);
+
+	let key = dedup_key(&command_name, error);
+
+	let Some(skipped) = bot_data.error_reporter.check(&key, Instant::now(), config) else {
+		return;
+	};
+
+	let guild = match command_interaction.guild_id {
+		Some(guild_id) => guild_id.to_string(),
+		None => "DM".to_string(),
+	};
+
+	let arguments = format_options(&command_interaction.data.options());
+
+	let arguments = if arguments.is_empty() {
+		"-".to_string()
+	} else {
+		censor_url_and_token(arguments.join("\n"), ctx)
+	};
+
+	let mut embed = CreateEmbed::new()
+		.timestamp(Timestamp::now())
+		.color(COLOR)
+		.title(format!("Internal error in /{}", command_name))
+		.description(format!(
+			"```\n{}\n```",
+			truncate(&escape_code_block(&chain), MAX_CHAIN_LENGTH)
+		))
+		.field("Guild", guild, true)
+		.field("Reference", correlation_id.to_string(), true)
+		.field(
+			"Arguments",
+			truncate(&arguments, MAX_ARGUMENTS_LENGTH),
+			false,
+		);
+
+	if skipped != 0 {
+		embed = embed.footer(CreateEmbedFooter::new(format!(
+			"Happened {} more times since the last report.",
+			skipped
+		)));
+	}
+
+	if let Err(e) = send_report(ctx, config, embed).await {
+		warn!("Failed to report the error {}. {:#}", correlation_id, e);
+	}
+}
+
+async fn send_report(
+	ctx: &Context, config: &ErrorReportingConfig, embed: CreateEmbed<'static>,
+) -> Result<()> {
+	if !config.webhook_url.is_empty() {
+		let webhook = Webhook::from_url(&ctx.http, &config.webhook_url).await?;
+
+		webhook
+			.execute(&ctx.http, false, ExecuteWebhook::new().embed(embed))
+			.await?;
+
+		return Ok(());
+	}
+
+	let channel_id: u64 = config
+		.channel_id
+		.parse()
+		.map_err(|_| anyhow!("Invalid channel id {}", config.channel_id))?;
+
+	ChannelId::new(channel_id)
+		.send_message(&ctx.http, CreateMessage::new().embed(embed))
+		.await?;
+
+	Ok(())
+}
+
+/// Identifies the identical errors: the command, the kind of error and the top-level context of
+/// the chain. The causes under it often hold ids or timings that change on every occurrence.
+
+fn dedup_key(command_name: &str, error: &KasukiError) -> String {
+	let context = error
+		.details()
+		.split_once(": ")
+		.map_or(error.details(), |(context, _)| context);
+
+	format!("{}:{}:{}", command_name, error.kind(), context)
+}
+
+/// Puts a zero width space after each backtick of `text`, a triple backtick would close the code
+/// block it is shown in.
+
+fn escape_code_block(text: &str) -> String {
+	text.replace('`', "`\u{200b}")
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+	if text.chars().count() <= max_length {
+		return text.to_string();
+	}
+
+	let text: String = text.chars().take(max_length).collect();
+
+	format!("{}...", text)
+}
+
+/// The options of the command, one `name: value` per line. The options of the subcommands are
+/// listed without their subcommand, it is in the command name.
+
+fn format_options(options: &[ResolvedOption]) -> Vec<String> {
+	let mut lines = Vec::new();
+
+	for option in options {
+		let value = match &option.value {
+			ResolvedValue::SubCommand(options) | ResolvedValue::SubCommandGroup(options) => {
+				lines.extend(format_options(options));
+
+				continue;
+			},
+			ResolvedValue::String(value) => value.to_string(),
+			ResolvedValue::Integer(value) => value.to_string(),
+			ResolvedValue::Number(value) => value.to_string(),
+			ResolvedValue::Boolean(value) => value.to_string(),
+			ResolvedValue::User(user, _) => user.id.to_string(),
+			ResolvedValue::Role(role) => role.id.to_string(),
+			ResolvedValue::Channel(channel) => channel.id.to_string(),
+			ResolvedValue::Attachment(attachment) => attachment.filename.to_string(),
+			value => format!("{:?}", value),
+		};
+
+		lines.push(format!("{}: {}", option.name, value));
+	}
+
+	lines
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_check() {
+		let reporter = ErrorReporter::default();
+
+		let config = ErrorReportingConfig {
+			dedup_window: 60,
+			max_per_hour: 2,
+			..Default::default()
+		};
+
+		let start = Instant::now();
+
+		assert_eq!(reporter.check("anime:timeout", start, &config), Some(0));
+
+		// Identical errors are counted but not reported until the window is over.
+		assert_eq!(reporter.check("anime:timeout", start, &config), None);
+
+		assert_eq!(reporter.check("anime:timeout", start, &config), None);
+
+		assert_eq!(
+			reporter.check("anime:timeout", start + Duration::from_secs(61), &config),
+			Some(2)
+		);
+
+		// The hourly limit is reached, a new error is not reported.
+		assert_eq!(
+			reporter.check("manga:timeout", start + Duration::from_secs(62), &config),
+			None
+		);
+
+		assert_eq!(
+			reporter.check("manga:timeout", start + Duration::from_secs(3600), &config),
+			Some(0)
+		);
+	}
+
+	#[test]
+
+	fn test_dedup_key() {
+		let first = KasukiError::Internal("Failed to query: connection 12 timed out".to_string());
+
+		let second = KasukiError::Internal("Failed to query: connection 47 timed out".to_string());
+
+		assert_eq!(dedup_key("anime", &first), "anime:internal:Failed to query");
+
+		assert_eq!(dedup_key("anime", &first), dedup_key("anime", &second));
+
+		assert_ne!(dedup_key("anime", &first), dedup_key("manga", &first));
+
+		let upstream = KasukiError::UpstreamApi("Failed to query".to_string());
+
+		assert_ne!(dedup_key("anime", &first), dedup_key("anime", &upstream));
+	}
+
+	#[test]
+
+	fn test_escape_code_block() {
+		let escaped = escape_code_block("invalid ```json``` body");
+
+		assert!(!escaped.contains("``"));
+
+		assert!(!escape_code_block("````").contains("``"));
+
+		assert_eq!(escape_code_block("no backticks"), "no backticks");
+	}
+}
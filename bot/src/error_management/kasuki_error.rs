@@ -0,0 +1,123 @@
+use std::fmt;
+
+use crate::structure::message::error::ErrorLocalised;
+
+/// Error returned by a command, shown to the user with a localized message.
+///
+/// Errors that are not a `KasukiError` are treated as `Internal`. The codes are listed in
+/// `bot/ERROR_CODE.md` and must not change once released.
+#[derive(Debug)]
+
+pub enum KasukiError {
+	/// The options given by the user can't be used.
+	UserInput(String),
+	/// The searched media, user, character, etc. does not exist.
+	NotFound(String),
+	/// An external API (AniList, VNDB, Steam, the AI provider, ...) failed.
+	UpstreamApi(String),
+	/// The bot or the user is missing a permission.
+	Permission(String),
+	/// The user reached the hourly limit of a premium command.
+	PremiumLimit(String),
+	/// A bug or an unexpected failure.
+	Internal(String),
+}
+
+impl KasukiError {
+	pub fn code(&self) -> u16 {
+		match self {
+			KasukiError::UserInput(_) => 100,
+			KasukiError::NotFound(_) => 101,
+			KasukiError::UpstreamApi(_) => 102,
+			KasukiError::Permission(_) => 103,
+			KasukiError::PremiumLimit(_) => 104,
+			KasukiError::Internal(_) => 105,
+		}
+	}
+
+	/// Name of the kind of error, used as a metric label.
+
+	pub fn kind(&self) -> &'static str {
+		match self {
+			KasukiError::UserInput(_) => "user_input",
+			KasukiError::NotFound(_) => "not_found",
+			KasukiError::UpstreamApi(_) => "upstream_api",
+			KasukiError::Permission(_) => "permission",
+			KasukiError::PremiumLimit(_) => "premium_limit",
+			KasukiError::Internal(_) => "internal",
+		}
+	}
+
+	/// Whether the details of the error may be shown to the user. Internal errors only
+	/// show a correlation id, their details are in the logs.
+
+	pub fn show_details(&self) -> bool {
+		!matches!(self, KasukiError::Internal(_))
+	}
+
+	pub fn details(&self) -> &str {
+		match self {
+			KasukiError::UserInput(details)
+			| KasukiError::NotFound(details)
+			| KasukiError::UpstreamApi(details)
+			| KasukiError::Permission(details)
+			| KasukiError::PremiumLimit(details)
+			| KasukiError::Internal(details) => details,
+		}
+	}
+
+	pub fn localised_message<'a>(&self, localised: &'a ErrorLocalised) -> &'a str {
+		match self {
+			KasukiError::UserInput(_) => &localised.user_input,
+			KasukiError::NotFound(_) => &localised.not_found,
+			KasukiError::UpstreamApi(_) => &localised.upstream_api,
+			KasukiError::Permission(_) => &localised.permission,
+			KasukiError::PremiumLimit(_) => &localised.premium_limit,
+			KasukiError::Internal(_) => &localised.internal,
+		}
+	}
+}
+
+impl fmt::Display for KasukiError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.details())
+	}
+}
+
+impl std::error::Error for KasukiError {}
+
+impl From<anyhow::Error> for KasukiError {
+	fn from(error: anyhow::Error) -> Self {
+		match error.downcast::<KasukiError>() {
+			Ok(error) => error,
+			Err(error) => KasukiError::Internal(format!("{:#}", error)),
+		}
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use anyhow::{anyhow, Context};
+
+	#[test]
+
+	fn test_from_anyhow() {
+		let error: anyhow::Error = KasukiError::NotFound("No media found".to_string()).into();
+
+		let error = KasukiError::from(error);
+
+		assert_eq!(error.code(), 101);
+
+		assert!(error.show_details());
+
+		let error = KasukiError::from(anyhow!("database is locked").context("Failed to query"));
+
+		assert_eq!(error.code(), 105);
+
+		assert!(!error.show_details());
+
+		assert_eq!(error.details(), "Failed to query: database is locked");
+	}
+}
@@ -1 +1,3 @@
 pub mod error_dispatch;
+pub mod error_report;
+pub mod kasuki_error;
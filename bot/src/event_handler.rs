@@ -1,28 +1,33 @@
 use crate::autocomplete::autocomplete_dispatch::autocomplete_dispatching;
+use crate::background_task::activity::chapter_provider::ChapterProvider;
+use crate::background_task::activity::scheduler::ActivityScheduler;
 use crate::background_task::background_launcher::thread_management_launcher;
 use crate::background_task::server_image::calculate_user_color::{
 	color_management, get_specific_user_color,
 };
 use crate::background_task::server_image::generate_server_image::server_image_management;
+use crate::background_task::supervisor::TaskSupervisor;
+use crate::cache::{CacheBackend, NamespacedCache};
 use crate::command::command_dispatch::{check_if_module_is_on, dispatch_command};
+use crate::command::message_command_dispatch::dispatch_message_command;
 use crate::command::user_command_dispatch::dispatch_user_command;
 use crate::components::components_dispatch::components_dispatching;
 use crate::config::Config;
-use crate::constant::COMMAND_USE_PATH;
 use crate::database::prelude::{
 	GuildData, GuildSubscription, ServerUserRelation, UserData, UserSubscription,
 };
 use crate::error_management::error_dispatch;
+use crate::error_management::error_report::ErrorReporter;
+use crate::helper::command_usage::{count_usage_since, insert_command_usage};
+use crate::helper::make_graphql_cached::AnilistClient;
 use crate::new_member::new_member_message;
 use crate::register::registration_dispatcher::command_registration;
 use crate::removed_member::removed_member_message;
+use crate::shutdown::ShutdownState;
 use chrono::Utc;
-use moka::future::Cache;
-use num_bigint::BigUint;
 use reqwest::Client;
 use sea_orm::ActiveValue::Set;
 use sea_orm::{DatabaseConnection, EntityTrait};
-use serde::{Deserialize, Serialize};
 use serenity::all::{
 	CommandType, CurrentApplicationInfo, Entitlement, Guild, GuildId, GuildMembersChunkEvent,
 	Interaction, Member, Presence, Ready, User,
@@ -32,140 +37,51 @@ use serenity::gateway::{ActivityData, ChunkGuildFilter, ShardManager};
 use serenity::prelude::{Context as SerenityContext, EventHandler};
 use songbird::Songbird;
 use std::collections::HashMap;
-use std::ops::{Add, AddAssign};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info, trace};
+use tracing::field::Empty;
+use tracing::{debug, error, info, info_span, Instrument, Span};
 
 pub struct BotData {
-	pub number_of_command_use_per_command: Arc<RwLock<RootUsage>>,
 	pub config: Arc<Config>,
 	pub bot_info: Arc<RwLock<Option<CurrentApplicationInfo>>>,
-	pub anilist_cache: Arc<RwLock<Cache<String, String>>>,
-	pub vndb_cache: Arc<RwLock<Cache<String, String>>>,
-	pub already_launched: RwLock<bool>,
+	pub anilist_client: Arc<AnilistClient>,
+	pub vndb_cache: NamespacedCache,
+	pub cache_backend: Arc<dyn CacheBackend>,
+	pub already_launched: AtomicBool,
 	pub apps: Arc<RwLock<HashMap<String, u128>>>,
 	pub user_blacklist_server_image: Arc<RwLock<Vec<String>>>,
 	pub db_connection: Arc<DatabaseConnection>,
 	pub manager: Arc<Songbird>,
 	pub http_client: Client,
 	pub shard_manager: Arc<RwLock<Option<Arc<ShardManager>>>>,
+	pub tasks: Arc<TaskSupervisor>,
+	pub shutdown: Arc<ShutdownState>,
+	pub activity_scheduler: Arc<ActivityScheduler>,
+	pub error_reporter: Arc<ErrorReporter>,
+	pub chapter_provider: Arc<dyn ChapterProvider>,
 }
 use anyhow::{Context, Result};
 
 pub struct Handler;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
-
-pub struct UserUsage {
-	pub user_name: String,
-	pub usage: u128,
-	pub hourly_usage: HashMap<String, u128>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
-
-pub struct UserInfo {
-	pub user_info: HashMap<String, UserUsage>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
-
-pub struct RootUsage {
-	pub command_list: HashMap<String, UserInfo>,
-}
-
-impl RootUsage {
-	pub fn new() -> Self {
-		RootUsage {
-			command_list: HashMap::new(),
-		}
-	}
-
-	pub fn get_total_command_use(&self) -> String {
-		let mut total = BigUint::ZERO;
-
-		let command_usage = self.clone();
-
-		for (_, user_info) in command_usage.command_list.iter() {
-			for (_, user_usage) in user_info.user_info.iter() {
-				total.add_assign(user_usage.usage)
-			}
-		}
-
-		total.to_string()
-	}
-}
-
 impl BotData {
-	pub async fn get_hourly_usage(&self, command_name: String, user_id: String) -> u128 {
-		let number_of_command_use_per_command = self.number_of_command_use_per_command.clone();
-
-		let guard = number_of_command_use_per_command.read().await;
-
-		let user_map = guard
-			.command_list
-			.get(&command_name)
-			.cloned()
-			.unwrap_or_default()
-			.user_info
-			.get(&user_id)
-			.cloned()
-			.unwrap_or_default();
-
-		*user_map
-			.hourly_usage
-			.get(&chrono::Local::now().format("%H").to_string())
-			.unwrap_or(&(0u128))
-	}
-
-	// thread safe way to increment the number of command use per command
-	pub async fn increment_command_use_per_command(
-		&self, command_name: String, user_id: String, user_name: String,
-	) {
-		let number_of_command_use_per_command = self.number_of_command_use_per_command.clone();
-
-		let mut guard = number_of_command_use_per_command.write().await;
-
-		let command_map = guard
-			.command_list
-			.entry(command_name)
-			.or_insert_with(|| UserInfo {
-				user_info: HashMap::new(),
-			});
-
-		let user_map = command_map
-			.user_info
-			.entry(user_id)
-			.or_insert_with(|| UserUsage {
-				user_name,
-				usage: 0,
-				hourly_usage: Default::default(),
-			});
-
-		user_map.usage = user_map.usage.add(1);
-
-		// create a timestamp in format dd:mm:aaaa:hh
-		let timestamp = chrono::Local::now().format("%d:%m:%Y:%H").to_string();
-
-		// insert or update the hourly usage
-		let hourly_usage = user_map.hourly_usage.entry(timestamp).or_insert(0);
-
-		*hourly_usage += 1;
+	/// Number of use of a command by a user in the last hour.
+	pub async fn get_hourly_usage(&self, command_name: String, user_id: String) -> Result<u64> {
+		let since = Utc::now().naive_utc() - chrono::Duration::hours(1);
 
-		// drop the guard
-		drop(guard);
+		count_usage_since(&self.db_connection, &command_name, &user_id, since).await
+	}
 
-		// save the content as a json
-		match serde_json::to_string(&*self.number_of_command_use_per_command.read().await) {
-			Ok(content) => {
-				// save the content to the file
-				if let Err(e) = std::fs::write(COMMAND_USE_PATH, content) {
-					error!("Failed to write to file: {}", e);
-				}
-			},
-			Err(e) => error!("Error serializing data: {}", e),
-		}
+	pub async fn record_command_usage(&self, command_name: String, user_id: String) -> Result<()> {
+		insert_command_usage(
+			&self.db_connection,
+			command_name,
+			user_id,
+			Utc::now().naive_utc(),
+		)
+		.await
 	}
 }
 
@@ -231,14 +147,17 @@ impl EventHandler for Handler {
 			guild_id.clone()
 		);
 
-		let is_module_on =
-			check_if_module_is_on(guild_id.clone(), "NEW_MEMBER", bot_data.config.db.clone())
-				.await
-				.unwrap_or_else(|e| {
-					error!("Failed to get the module status. {}", e);
+		let is_module_on = check_if_module_is_on(
+			guild_id.clone(),
+			"NEW_MEMBER",
+			bot_data.db_connection.clone(),
+		)
+		.await
+		.unwrap_or_else(|e| {
+			error!("Failed to get the module status. {}", e);
 
-					false
-				});
+			false
+		});
 
 		match new_member_message(&ctx, &member).await {
 			Ok(_) => {},
@@ -281,7 +200,7 @@ impl EventHandler for Handler {
 		let is_module_on = check_if_module_is_on(
 			guild_id.to_string().clone(),
 			"NEW_MEMBER",
-			bot_data.config.db.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await
 		.unwrap_or_else(|e| {
@@ -368,7 +287,7 @@ impl EventHandler for Handler {
 		get_specific_user_color(
 			user_blacklist_server_image,
 			user.clone(),
-			bot_data.config.db.clone(),
+			bot_data.db_connection.clone(),
 		)
 		.await;
 
@@ -405,23 +324,21 @@ impl EventHandler for Handler {
 			)
 		}
 
-		// Spawns a new thread for managing various tasks
-		let guard = bot_data.already_launched.read().await;
-
-		if !(*guard) {
-			drop(guard);
-
-			let mut write_guard = bot_data.already_launched.write().await;
+		// Spawns a new thread for managing various tasks. The swap checks and sets the flag at
+		// once, so only the first of the shards ready at the same time launches them.
+		if !bot_data.already_launched.swap(true, Ordering::SeqCst) {
+			thread_management_launcher(ctx.clone(), bot_data.clone());
 
-			*write_guard = true;
+			// The commands are registered once per process, not on every shard or reconnect.
+			let http = ctx.http.clone();
 
-			tokio::spawn(thread_management_launcher(
-				ctx.clone(),
-				bot_data.clone(),
-				bot_data.config.db.clone(),
-			));
+			let remove_old_commands = bot_data.config.bot.remove_old_commands;
 
-			drop(write_guard)
+			tokio::spawn(async move {
+				if let Err(e) = command_registration(&http, remove_old_commands, false).await {
+					error!("Failed to register the commands. {:#}", e);
+				}
+			});
 		}
 
 		// Sets the bot's activity
@@ -439,68 +356,19 @@ impl EventHandler for Handler {
 		let server_number = ctx.cache.guilds().len();
 
 		info!(server_number);
-
-		// Checks if the "REMOVE_OLD_COMMAND" environment variable is set to "true" (case-insensitive)
-		let remove_old_command = bot_data.config.bot.remove_old_commands;
-
-		// Creates commands based on the value of the "REMOVE_OLD_COMMAND" environment variable
-		command_registration(&ctx.http, remove_old_command).await;
 	}
 
 	async fn interaction_create(&self, ctx: SerenityContext, interaction: Interaction) {
-		let mut user = None;
-
-		let bot_data = ctx.data::<BotData>().clone();
-
-		if let Interaction::Command(command_interaction) = interaction.clone() {
-			let mut message = String::from("");
-
-			if command_interaction.data.kind == CommandType::ChatInput {
-				match dispatch_command(&ctx, &command_interaction).await {
-					Ok(()) => return,
-					Err(e) => {
-						message = e.to_string();
-					},
-				}
-			} else if command_interaction.data.kind == CommandType::User {
-				match dispatch_user_command(&ctx, &command_interaction).await {
-					Ok(()) => return,
-					Err(e) => {
-						message = e.to_string();
-					},
-				}
-			} else if command_interaction.data.kind == CommandType::Message {
-				trace!("{:?}", command_interaction)
-			}
-
-			error_dispatch::command_dispatching(message, &command_interaction, &ctx).await;
-
-			user = Some(command_interaction.user.clone());
-		} else if let Interaction::Autocomplete(autocomplete_interaction) = interaction.clone() {
-			// Dispatch the autocomplete interaction
-			user = Some(autocomplete_interaction.user.clone());
-
-			autocomplete_dispatching(ctx, autocomplete_interaction).await;
-		} else if let Interaction::Component(component_interaction) = interaction.clone() {
-			// Dispatch the component interaction
-			user = Some(component_interaction.user.clone());
-
-			if let Err(e) =
-				components_dispatching(ctx, component_interaction, bot_data.config.db.clone()).await
-			{
-				// If an error occurs, log it
-				error!("{:?}", e)
-			}
-		}
-
-		if user.is_none() {
-			return;
-		}
+		// Root span of the interaction, the AniList, VNDB and database calls are its children.
+		let span = info_span!(
+			"interaction",
+			id = %interaction.id(),
+			kind = ?interaction.kind(),
+			guild_id = ?interaction.guild_id(),
+			command = Empty,
+		);
 
-		match add_user_data_to_db(user.unwrap(), bot_data.db_connection.clone()).await {
-			Ok(_) => {},
-			Err(e) => error!("Failed to insert user data. {}", e),
-		};
+		handle_interaction(ctx, interaction).instrument(span).await
 	}
 
 	async fn entitlement_create(&self, ctx: SerenityContext, entitlement: Entitlement) {
@@ -556,6 +424,69 @@ impl EventHandler for Handler {
 	}
 }
 
+/// Dispatches the interaction to its command, autocomplete or component and saves the user.
+
+async fn handle_interaction(ctx: SerenityContext, interaction: Interaction) {
+	let mut user = None;
+
+	let bot_data = ctx.data::<BotData>().clone();
+
+	// The interactions received while shutting down are ignored, the running ones are
+	// waited for.
+	let Some(_work) = bot_data.shutdown.start_work() else {
+		debug!("Ignored an interaction, the bot is shutting down.");
+
+		return;
+	};
+
+	if let Interaction::Command(command_interaction) = interaction.clone() {
+		Span::current().record("command", command_interaction.data.name.as_str());
+
+		let result = match command_interaction.data.kind {
+			CommandType::ChatInput => dispatch_command(&ctx, &command_interaction).await,
+			CommandType::User => dispatch_user_command(&ctx, &command_interaction).await,
+			CommandType::Message => dispatch_message_command(&ctx, &command_interaction).await,
+			_ => Ok(()),
+		};
+
+		let Err(error) = result else {
+			return;
+		};
+
+		error_dispatch::command_dispatching(error, &command_interaction, &ctx).await;
+
+		user = Some(command_interaction.user.clone());
+	} else if let Interaction::Autocomplete(autocomplete_interaction) = interaction.clone() {
+		Span::current().record("command", autocomplete_interaction.data.name.as_str());
+
+		// Dispatch the autocomplete interaction
+		user = Some(autocomplete_interaction.user.clone());
+
+		autocomplete_dispatching(ctx, autocomplete_interaction).await;
+	} else if let Interaction::Component(component_interaction) = interaction.clone() {
+		Span::current().record("command", component_interaction.data.custom_id.as_str());
+
+		// Dispatch the component interaction
+		user = Some(component_interaction.user.clone());
+
+		if let Err(e) =
+			components_dispatching(ctx, component_interaction, bot_data.db_connection.clone()).await
+		{
+			// If an error occurs, log it
+			error!("{:?}", e)
+		}
+	}
+
+	if user.is_none() {
+		return;
+	}
+
+	match add_user_data_to_db(user.unwrap(), bot_data.db_connection.clone()).await {
+		Ok(_) => {},
+		Err(e) => error!("Failed to insert user data. {}", e),
+	};
+}
+
 async fn insert_subscription(entitlement: Entitlement, connection: Arc<DatabaseConnection>) {
 	match (entitlement.guild_id, entitlement.user_id) {
 		(Some(guild_id), None) => {
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// Refuses the calls that don't send the `[grpc]` token as `authorization: Bearer <token>`.
+/// Every service of the server is wrapped with it, the reflection one included.
+#[derive(Clone)]
+
+pub struct TokenInterceptor {
+	token: Arc<str>,
+}
+
+impl TokenInterceptor {
+	pub fn new(token: &str) -> Self {
+		Self {
+			token: Arc::from(token),
+		}
+	}
+}
+
+impl Interceptor for TokenInterceptor {
+	fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+		let token = request
+			.metadata()
+			.get("authorization")
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.strip_prefix("Bearer "));
+
+		match token {
+			// An empty token would let every call through, the config refuses it too.
+			Some(token) if !self.token.is_empty() && constant_time_eq(token, &self.token) => {
+				Ok(request)
+			},
+			_ => Err(Status::unauthenticated("Invalid or missing token")),
+		}
+	}
+}
+
+/// Compares without stopping at the first difference, the time doesn't tell how much of the
+/// token was right.
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+	a.len() == b.len()
+		&& a.bytes()
+			.zip(b.bytes())
+			.fold(0, |diff, (a, b)| diff | (a ^ b))
+			== 0
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	fn request(authorization: Option<&str>) -> Request<()> {
+		let mut request = Request::new(());
+
+		if let Some(authorization) = authorization {
+			request
+				.metadata_mut()
+				.insert("authorization", authorization.parse().unwrap());
+		}
+
+		request
+	}
+
+	#[test]
+
+	fn test_token_interceptor() {
+		let mut interceptor = TokenInterceptor::new("secret");
+
+		assert!(interceptor.call(request(Some("Bearer secret"))).is_ok());
+
+		for authorization in [None, Some("Bearer wrong"), Some("secret"), Some("Bearer ")] {
+			let status = interceptor.call(request(authorization)).unwrap_err();
+
+			assert_eq!(status.code(), tonic::Code::Unauthenticated);
+		}
+
+		let mut empty = TokenInterceptor::new("");
+
+		assert!(empty.call(request(Some("Bearer "))).is_err());
+	}
+}
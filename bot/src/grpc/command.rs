@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::database::prelude::UserData;
+use crate::database::user_data::Column;
+use crate::event_handler::BotData;
+use crate::grpc::proto::command::command_service_server::CommandService;
+use crate::grpc::proto::command::{
+	CommandUsage, CommandUsageHistoryRequest, CommandUsageHistoryResponse, CommandUsageRequest,
+	CommandUsageResponse, Granularity, UsageBucket, UserUsage,
+};
+use crate::helper::command_usage::{get_usage_history, get_usage_per_command, UsageGranularity};
+use chrono::{Duration, Utc};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tonic::{Request, Response, Status};
+
+pub struct CommandServiceImpl {
+	pub bot_data: Arc<BotData>,
+}
+
+#[tonic::async_trait]
+
+impl CommandService for CommandServiceImpl {
+	async fn get_command_usage(
+		&self, request: Request<CommandUsageRequest>,
+	) -> Result<Response<CommandUsageResponse>, Status> {
+		let request = request.into_inner();
+
+		let since = request
+			.since_hours
+			.map(|hours| Utc::now().naive_utc() - Duration::hours(hours as i64));
+
+		let usage = get_usage_per_command(
+			&self.bot_data.db_connection,
+			request.command_name.as_deref(),
+			request.user_id.as_deref(),
+			since,
+		)
+		.await
+		.map_err(|e| Status::internal(format!("{:#}", e)))?;
+
+		let user_ids: Vec<String> = usage.iter().map(|count| count.user_id.clone()).collect();
+
+		let user_names: HashMap<String, String> = UserData::find()
+			.filter(Column::UserId.is_in(user_ids))
+			.all(&*self.bot_data.db_connection)
+			.await
+			.map_err(|e| Status::internal(format!("Failed to get the user names. {}", e)))?
+			.into_iter()
+			.map(|user| (user.user_id, user.username))
+			.collect();
+
+		let mut command_list: HashMap<String, Vec<UserUsage>> = HashMap::new();
+
+		for count in usage {
+			command_list
+				.entry(count.command_name)
+				.or_default()
+				.push(UserUsage {
+					user_name: user_names.get(&count.user_id).cloned().unwrap_or_default(),
+					user_id: count.user_id,
+					usage: count.usage as u64,
+				});
+		}
+
+		let mut commands: Vec<CommandUsage> = command_list
+			.into_iter()
+			.map(|(command_name, mut users)| {
+				users.sort_by(|a, b| b.usage.cmp(&a.usage));
+
+				CommandUsage {
+					command_name,
+					usage: users.iter().map(|user| user.usage).sum(),
+					users,
+				}
+			})
+			.collect();
+
+		commands.sort_by(|a, b| b.usage.cmp(&a.usage));
+
+		let total_usage = commands.iter().map(|command| command.usage).sum();
+
+		Ok(Response::new(CommandUsageResponse {
+			commands,
+			total_usage,
+		}))
+	}
+
+	async fn get_command_usage_history(
+		&self, request: Request<CommandUsageHistoryRequest>,
+	) -> Result<Response<CommandUsageHistoryResponse>, Status> {
+		let request = request.into_inner();
+
+		let granularity = match request.granularity() {
+			Granularity::Hour => UsageGranularity::Hour,
+			Granularity::Day => UsageGranularity::Day,
+		};
+
+		if request.bucket_count == 0 {
+			return Err(Status::invalid_argument(
+				"The bucket count must be at least 1",
+			));
+		}
+
+		let since = granularity.period_start(Utc::now().naive_utc())
+			- granularity.duration() * (request.bucket_count as i32 - 1);
+
+		let buckets = get_usage_history(
+			&self.bot_data.db_connection,
+			granularity,
+			since,
+			request.command_name.as_deref(),
+			request.user_id.as_deref(),
+		)
+		.await
+		.map_err(|e| Status::internal(format!("{:#}", e)))?
+		.into_iter()
+		.map(|bucket| UsageBucket {
+			start: bucket.bucket,
+			usage: bucket.usage as u64,
+		})
+		.collect();
+
+		Ok(Response::new(CommandUsageHistoryResponse { buckets }))
+	}
+}
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use crate::constant::LANG_MAP;
+use crate::database::guild_lang;
+use crate::database::module_activation::{ActiveModel, Column, Model};
+use crate::database::prelude::{GuildLang, ModuleActivation};
+use crate::event_handler::BotData;
+use crate::grpc::proto::guild::guild_service_server::GuildService;
+use crate::grpc::proto::guild::{
+	GuildSettingsRequest, GuildSettingsResponse, ModuleSettings, SetGuildLangRequest,
+	SetGuildModuleRequest,
+};
+use crate::helper::get_guild_lang::get_guild_language;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use tonic::{Request, Response, Status};
+
+pub struct GuildServiceImpl {
+	pub bot_data: Arc<BotData>,
+}
+
+#[tonic::async_trait]
+
+impl GuildService for GuildServiceImpl {
+	async fn get_guild_settings(
+		&self, request: Request<GuildSettingsRequest>,
+	) -> Result<Response<GuildSettingsResponse>, Status> {
+		let guild_id = check_guild_id(request.into_inner().guild_id)?;
+
+		self.get_settings(guild_id).await.map(Response::new)
+	}
+
+	async fn set_guild_module(
+		&self, request: Request<SetGuildModuleRequest>,
+	) -> Result<Response<GuildSettingsResponse>, Status> {
+		let request = request.into_inner();
+
+		let guild_id = check_guild_id(request.guild_id)?;
+
+		let mut row = self.get_module_activation(guild_id.clone()).await?;
+
+		match request.module.as_str() {
+			"ANILIST" => row.anilist_module = request.state,
+			"AI" => row.ai_module = request.state,
+			"GAME" => row.game_module = request.state,
+			"NEW_MEMBER" => row.new_members_module = request.state,
+			"ANIME" => row.anime_module = request.state,
+			"VN" => row.vn_module = request.state,
+			_ => {
+				return Err(Status::invalid_argument(
+					"The module specified does not exist",
+				));
+			},
+		}
+
+		ModuleActivation::insert(ActiveModel {
+			guild_id: Set(guild_id.clone()),
+			ai_module: Set(row.ai_module),
+			anilist_module: Set(row.anilist_module),
+			game_module: Set(row.game_module),
+			new_members_module: Set(row.new_members_module),
+			anime_module: Set(row.anime_module),
+			vn_module: Set(row.vn_module),
+			updated_at: Set(Utc::now().naive_utc()),
+		})
+		.on_conflict(
+			OnConflict::column(Column::GuildId)
+				.update_columns([
+					Column::AiModule,
+					Column::AnilistModule,
+					Column::GameModule,
+					Column::NewMembersModule,
+					Column::AnimeModule,
+					Column::VnModule,
+					Column::UpdatedAt,
+				])
+				.to_owned(),
+		)
+		.exec(&*self.bot_data.db_connection)
+		.await
+		.map_err(|e| Status::internal(format!("Failed to update the module. {}", e)))?;
+
+		self.get_settings(guild_id).await.map(Response::new)
+	}
+
+	async fn set_guild_lang(
+		&self, request: Request<SetGuildLangRequest>,
+	) -> Result<Response<GuildSettingsResponse>, Status> {
+		let request = request.into_inner();
+
+		let guild_id = check_guild_id(request.guild_id)?;
+
+		let lang = request.lang.to_lowercase();
+
+		if !LANG_MAP.contains_key(lang.as_str()) {
+			return Err(Status::invalid_argument(
+				"The lang specified is not supported",
+			));
+		}
+
+		GuildLang::insert(guild_lang::ActiveModel {
+			guild_id: Set(guild_id.clone()),
+			lang: Set(lang),
+			updated_at: Set(Utc::now().naive_utc()),
+		})
+		.on_conflict(
+			OnConflict::column(guild_lang::Column::GuildId)
+				.update_columns([guild_lang::Column::Lang, guild_lang::Column::UpdatedAt])
+				.to_owned(),
+		)
+		.exec(&*self.bot_data.db_connection)
+		.await
+		.map_err(|e| Status::internal(format!("Failed to update the lang. {}", e)))?;
+
+		self.get_settings(guild_id).await.map(Response::new)
+	}
+}
+
+impl GuildServiceImpl {
+	async fn get_settings(&self, guild_id: String) -> Result<GuildSettingsResponse, Status> {
+		let lang = get_guild_language(guild_id.clone(), self.bot_data.db_connection.clone()).await;
+
+		let row = self.get_module_activation(guild_id.clone()).await?;
+
+		Ok(GuildSettingsResponse {
+			guild_id,
+			lang,
+			modules: Some(ModuleSettings {
+				ai: row.ai_module,
+				anilist: row.anilist_module,
+				game: row.game_module,
+				new_member: row.new_members_module,
+				anime: row.anime_module,
+				vn: row.vn_module,
+			}),
+		})
+	}
+
+	async fn get_module_activation(&self, guild_id: String) -> Result<Model, Status> {
+		let row = ModuleActivation::find()
+			.filter(Column::GuildId.eq(guild_id.clone()))
+			.one(&*self.bot_data.db_connection)
+			.await
+			.map_err(|e| Status::internal(format!("Failed to get the module. {}", e)))?
+			.unwrap_or(Model {
+				guild_id,
+				ai_module: true,
+				anilist_module: true,
+				game_module: true,
+				new_members_module: false,
+				anime_module: true,
+				vn_module: true,
+				updated_at: Default::default(),
+			});
+
+		Ok(row)
+	}
+}
+
+fn check_guild_id(guild_id: String) -> Result<String, Status> {
+	match guild_id.parse::<u64>() {
+		Ok(_) => Ok(guild_id),
+		Err(_) => Err(Status::invalid_argument("The guild id is not valid")),
+	}
+}
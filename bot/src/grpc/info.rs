@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::constant::{APP_VERSION, LIBRARY};
+use crate::event_handler::BotData;
+use crate::grpc::proto::info::info_service_server::InfoService;
+use crate::grpc::proto::info::{
+	BotInfoRequest, BotInfoResponse, GuildCountRequest, GuildCountResponse,
+};
+use serenity::all::{Cache, Http};
+use tonic::{Request, Response, Status};
+
+pub struct InfoServiceImpl {
+	pub bot_data: Arc<BotData>,
+	pub cache: Arc<Cache>,
+	pub http: Arc<Http>,
+}
+
+#[tonic::async_trait]
+
+impl InfoService for InfoServiceImpl {
+	async fn get_bot_info(
+		&self, _request: Request<BotInfoRequest>,
+	) -> Result<Response<BotInfoResponse>, Status> {
+		// The info is refreshed by a background task, only ask discord if it did not run yet
+		let bot_info = self.bot_data.bot_info.read().await.clone();
+
+		let bot_info = match bot_info {
+			Some(bot_info) => bot_info,
+			None => self
+				.http
+				.get_current_application_info()
+				.await
+				.map_err(|e| Status::unavailable(format!("Failed to get the bot info. {}", e)))?,
+		};
+
+		let shard_count = match self.bot_data.shard_manager.read().await.clone() {
+			Some(shard_manager) => shard_manager.runners.lock().await.len() as u32,
+			None => 0,
+		};
+
+		let owner = bot_info.owner;
+
+		Ok(Response::new(BotInfoResponse {
+			bot_name: bot_info.name.to_string(),
+			bot_id: bot_info.id.to_string(),
+			description: bot_info.description.to_string(),
+			version: APP_VERSION.to_string(),
+			library: LIBRARY.to_string(),
+			owner_id: owner.as_ref().map(|owner| owner.id.to_string()),
+			owner_name: owner.map(|owner| owner.name.to_string()),
+			guild_count: self.cache.guild_count() as u64,
+			shard_count,
+		}))
+	}
+
+	async fn get_guild_count(
+		&self, _request: Request<GuildCountRequest>,
+	) -> Result<Response<GuildCountResponse>, Status> {
+		Ok(Response::new(GuildCountResponse {
+			guild_count: self.cache.guild_count() as u64,
+		}))
+	}
+}
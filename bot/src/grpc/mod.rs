@@ -0,0 +1,7 @@
+pub mod auth;
+pub mod command;
+pub mod guild;
+pub mod info;
+pub mod proto;
+pub mod server;
+pub mod shard;
@@ -0,0 +1,19 @@
+pub mod info {
+	tonic::include_proto!("info");
+}
+
+pub mod shard {
+	tonic::include_proto!("shard");
+}
+
+pub mod command {
+	tonic::include_proto!("command");
+}
+
+pub mod guild {
+	tonic::include_proto!("guild");
+}
+
+/// Encoded descriptor of every service, served through the reflection service.
+
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("kasuki_descriptor");
@@ -0,0 +1,106 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use crate::event_handler::BotData;
+use crate::grpc::auth::TokenInterceptor;
+use crate::grpc::command::CommandServiceImpl;
+use crate::grpc::guild::GuildServiceImpl;
+use crate::grpc::info::InfoServiceImpl;
+use crate::grpc::proto::command::command_service_server::CommandServiceServer;
+use crate::grpc::proto::guild::guild_service_server::GuildServiceServer;
+use crate::grpc::proto::info::info_service_server::InfoServiceServer;
+use crate::grpc::proto::shard::shard_service_server::ShardServiceServer;
+use crate::grpc::proto::FILE_DESCRIPTOR_SET;
+use crate::grpc::shard::ShardServiceImpl;
+use anyhow::{Context, Result};
+use serenity::all::{Cache, Http};
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tracing::info;
+
+/// Starts the gRPC management server on the address and port set in the `[grpc]` config section.
+///
+/// The server exposes the bot info, the shard latencies, the command usage and the
+/// per-guild settings, plus a reflection service so tools like `grpcurl` can list them.
+/// Every call must send `auth_token`, see `TokenInterceptor`.
+/// TLS is used when `use_tls` is set, with the certificate and key read from
+/// `tls_cert_path` and `tls_key_path`.
+
+pub async fn grpc_server_launcher(
+	bot_data: Arc<BotData>, cache: Arc<Cache>, http: Arc<Http>,
+) -> Result<()> {
+	let grpc_config = bot_data.config.grpc.clone();
+
+	let ip: IpAddr = grpc_config.bind_address.parse().context(format!(
+		"Invalid gRPC bind address {}",
+		grpc_config.bind_address
+	))?;
+
+	let addr = SocketAddr::new(ip, grpc_config.grpc_port);
+
+	let auth = TokenInterceptor::new(&grpc_config.auth_token);
+
+	let reflection_service = tonic_reflection::server::Builder::configure()
+		.register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+		.build_v1()
+		.context("Failed to build the reflection service")?;
+
+	let mut server = Server::builder();
+
+	if grpc_config.use_tls {
+		let cert = tokio::fs::read(&grpc_config.tls_cert_path)
+			.await
+			.context(format!(
+				"Failed to read the tls certificate {}",
+				grpc_config.tls_cert_path
+			))?;
+
+		let key = tokio::fs::read(&grpc_config.tls_key_path)
+			.await
+			.context(format!(
+				"Failed to read the tls key {}",
+				grpc_config.tls_key_path
+			))?;
+
+		server = server
+			.tls_config(ServerTlsConfig::new().identity(Identity::from_pem(cert, key)))
+			.context("Failed to configure tls for the gRPC server")?;
+	}
+
+	info!(
+		"Launching the gRPC server on {} (tls: {}).",
+		addr, grpc_config.use_tls
+	);
+
+	server
+		.add_service(InterceptedService::new(reflection_service, auth.clone()))
+		.add_service(InfoServiceServer::with_interceptor(
+			InfoServiceImpl {
+				bot_data: bot_data.clone(),
+				cache,
+				http,
+			},
+			auth.clone(),
+		))
+		.add_service(ShardServiceServer::with_interceptor(
+			ShardServiceImpl {
+				bot_data: bot_data.clone(),
+			},
+			auth.clone(),
+		))
+		.add_service(CommandServiceServer::with_interceptor(
+			CommandServiceImpl {
+				bot_data: bot_data.clone(),
+			},
+			auth.clone(),
+		))
+		.add_service(GuildServiceServer::with_interceptor(
+			GuildServiceImpl { bot_data },
+			auth,
+		))
+		.serve(addr)
+		.await
+		.context("The gRPC server stopped")?;
+
+	Ok(())
+}
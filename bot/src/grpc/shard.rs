@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::event_handler::BotData;
+use crate::grpc::proto::shard::shard_service_server::ShardService;
+use crate::grpc::proto::shard::{ShardLatenciesRequest, ShardLatenciesResponse, ShardLatency};
+use tonic::{Request, Response, Status};
+
+pub struct ShardServiceImpl {
+	pub bot_data: Arc<BotData>,
+}
+
+#[tonic::async_trait]
+
+impl ShardService for ShardServiceImpl {
+	async fn get_shard_latencies(
+		&self, _request: Request<ShardLatenciesRequest>,
+	) -> Result<Response<ShardLatenciesResponse>, Status> {
+		let shard_manager = self
+			.bot_data
+			.shard_manager
+			.read()
+			.await
+			.clone()
+			.ok_or(Status::unavailable("The shard manager is not started yet"))?;
+
+		let runners = shard_manager.runners.lock().await;
+
+		let shards = runners
+			.iter()
+			.map(|(shard_id, runner)| ShardLatency {
+				shard_id: shard_id.to_string(),
+				latency_ms: runner.latency.map(|latency| latency.as_millis() as u64),
+				stage: runner.stage.to_string(),
+			})
+			.collect();
+
+		Ok(Response::new(ShardLatenciesResponse { shards }))
+	}
+}
@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+
+use crate::database::command_usage::{ActiveModel, Column};
+use crate::database::prelude::{CommandUsage, UserData};
+use anyhow::{Context, Result};
+use chrono::{NaiveDateTime, NaiveTime, Timelike};
+use sea_orm::sea_query::Expr;
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+	ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, FromQueryResult,
+	Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Number of rows inserted per statement when importing the legacy json file.
+
+const IMPORT_CHUNK_SIZE: usize = 1_000;
+
+/// Number of use of a command by a user.
+#[derive(Debug, Clone, FromQueryResult)]
+
+pub struct UsageCount {
+	pub command_name: String,
+	pub user_id: String,
+	pub usage: i64,
+}
+
+/// Number of command use in one hour or one day, `bucket` being the start of the period.
+#[derive(Debug, Clone, FromQueryResult)]
+
+pub struct UsageBucket {
+	pub bucket: String,
+	pub usage: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+
+pub enum UsageGranularity {
+	Hour,
+	Day,
+}
+
+impl UsageGranularity {
+	pub fn duration(&self) -> chrono::Duration {
+		match self {
+			UsageGranularity::Hour => chrono::Duration::hours(1),
+			UsageGranularity::Day => chrono::Duration::days(1),
+		}
+	}
+
+	/// Start of the hour or day containing the given time.
+	pub fn period_start(&self, time: NaiveDateTime) -> NaiveDateTime {
+		match self {
+			UsageGranularity::Hour => {
+				time.date().and_time(NaiveTime::MIN) + chrono::Duration::hours(time.hour() as i64)
+			},
+			UsageGranularity::Day => time.date().and_time(NaiveTime::MIN),
+		}
+	}
+
+	// SQL expression truncating `used_at` to the start of its hour or day.
+	fn bucket_expression(&self, backend: DbBackend) -> &'static str {
+		match (backend, self) {
+			(DbBackend::Postgres, UsageGranularity::Hour) => {
+				"to_char(used_at, 'YYYY-MM-DD HH24:00')"
+			},
+			(DbBackend::Postgres, UsageGranularity::Day) => "to_char(used_at, 'YYYY-MM-DD')",
+			(DbBackend::MySql, UsageGranularity::Hour) => "date_format(used_at, '%Y-%m-%d %H:00')",
+			(DbBackend::MySql, UsageGranularity::Day) => "date_format(used_at, '%Y-%m-%d')",
+			(DbBackend::Sqlite, UsageGranularity::Hour) => "strftime('%Y-%m-%d %H:00', used_at)",
+			(DbBackend::Sqlite, UsageGranularity::Day) => "strftime('%Y-%m-%d', used_at)",
+		}
+	}
+}
+
+pub async fn insert_command_usage(
+	connection: &DatabaseConnection, command_name: String, user_id: String, used_at: NaiveDateTime,
+) -> Result<()> {
+	CommandUsage::insert(ActiveModel {
+		command_name: Set(command_name),
+		user_id: Set(user_id),
+		used_at: Set(used_at),
+		..Default::default()
+	})
+	.exec(connection)
+	.await
+	.context("Failed to insert the command usage")?;
+
+	Ok(())
+}
+
+/// Counts the use of a command by a user since the given time.
+
+pub async fn count_usage_since(
+	connection: &DatabaseConnection, command_name: &str, user_id: &str, since: NaiveDateTime,
+) -> Result<u64> {
+	CommandUsage::find()
+		.filter(Column::UserId.eq(user_id))
+		.filter(Column::CommandName.eq(command_name))
+		.filter(Column::UsedAt.gte(since))
+		.count(connection)
+		.await
+		.context("Failed to count the command usage")
+}
+
+/// Number of use grouped by command and user, optionally filtered by command, user and
+/// start time. Every retained row is counted when `since` is `None`.
+
+pub async fn get_usage_per_command(
+	connection: &DatabaseConnection, command_name: Option<&str>, user_id: Option<&str>,
+	since: Option<NaiveDateTime>,
+) -> Result<Vec<UsageCount>> {
+	let mut query = CommandUsage::find()
+		.select_only()
+		.column(Column::CommandName)
+		.column(Column::UserId)
+		.column_as(Column::Id.count(), "usage");
+
+	if let Some(command_name) = command_name {
+		query = query.filter(Column::CommandName.eq(command_name));
+	}
+
+	if let Some(user_id) = user_id {
+		query = query.filter(Column::UserId.eq(user_id));
+	}
+
+	if let Some(since) = since {
+		query = query.filter(Column::UsedAt.gte(since));
+	}
+
+	query
+		.group_by(Column::CommandName)
+		.group_by(Column::UserId)
+		.into_model::<UsageCount>()
+		.all(connection)
+		.await
+		.context("Failed to get the command usage")
+}
+
+/// Number of use per hour or per day since the given time, oldest period first.
+
+pub async fn get_usage_history(
+	connection: &DatabaseConnection, granularity: UsageGranularity, since: NaiveDateTime,
+	command_name: Option<&str>, user_id: Option<&str>,
+) -> Result<Vec<UsageBucket>> {
+	let bucket = granularity.bucket_expression(connection.get_database_backend());
+
+	let mut query = CommandUsage::find()
+		.select_only()
+		.column_as(Expr::cust(bucket), "bucket")
+		.column_as(Column::Id.count(), "usage")
+		.filter(Column::UsedAt.gte(since));
+
+	if let Some(command_name) = command_name {
+		query = query.filter(Column::CommandName.eq(command_name));
+	}
+
+	if let Some(user_id) = user_id {
+		query = query.filter(Column::UserId.eq(user_id));
+	}
+
+	query
+		.group_by(Expr::cust(bucket))
+		.order_by(Expr::cust(bucket), Order::Asc)
+		.into_model::<UsageBucket>()
+		.all(connection)
+		.await
+		.context("Failed to get the command usage history")
+}
+
+/// Deletes the command usage older than the given time and returns the number of removed rows.
+
+pub async fn prune_command_usage(
+	connection: &DatabaseConnection, older_than: NaiveDateTime,
+) -> Result<u64> {
+	let result = CommandUsage::delete_many()
+		.filter(Column::UsedAt.lt(older_than))
+		.exec(connection)
+		.await
+		.context("Failed to prune the command usage")?;
+
+	Ok(result.rows_affected)
+}
+
+/// Number of use of a command by a user in one hour, `bucket` being the start of the hour.
+#[derive(Debug, Clone, FromQueryResult)]
+
+struct UsageHourCount {
+	command_name: String,
+	user_id: String,
+	bucket: String,
+	usage: i64,
+}
+
+// Format of the db/command_use.json file used before the command_usage table.
+#[derive(Debug, Default, Serialize, Deserialize)]
+
+struct LegacyRootUsage {
+	command_list: HashMap<String, LegacyUserInfo>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+
+struct LegacyUserInfo {
+	user_info: HashMap<String, LegacyUserUsage>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+
+struct LegacyUserUsage {
+	#[serde(default)]
+	user_name: String,
+	#[serde(default)]
+	usage: u128,
+	hourly_usage: HashMap<String, u128>,
+}
+
+/// Imports the hourly usage of the legacy `db/command_use.json` file into the
+/// `command_usage` table, one row per recorded use at the start of its hour.
+///
+/// The file is renamed with an `.imported` suffix once its content is committed so it is
+/// only imported once. Returns the number of inserted rows.
+
+pub async fn import_legacy_command_usage(
+	connection: &DatabaseConnection, path: &str,
+) -> Result<u64> {
+	let content = std::fs::read_to_string(path).context(format!("Failed to read {}", path))?;
+
+	let root_usage: LegacyRootUsage =
+		serde_json::from_str(&content).context(format!("Failed to parse {}", path))?;
+
+	let transaction = connection.begin().await?;
+
+	// The rows are inserted as they are built, a large count never holds them all in memory.
+	let mut rows = Vec::with_capacity(IMPORT_CHUNK_SIZE);
+
+	let mut imported = 0;
+
+	for (command_name, user_info) in root_usage.command_list {
+		for (user_id, user_usage) in user_info.user_info {
+			for (hour, count) in user_usage.hourly_usage {
+				// the keys are in the dd:mm:yyyy:hh format
+				let used_at = match NaiveDateTime::parse_from_str(
+					&format!("{}:00", hour),
+					"%d:%m:%Y:%H:%M",
+				) {
+					Ok(used_at) => used_at,
+					Err(_) => {
+						warn!("Skipping the unknown hour {} of {}.", hour, command_name);
+
+						continue;
+					},
+				};
+
+				for _ in 0..count {
+					rows.push(ActiveModel {
+						command_name: Set(command_name.clone()),
+						user_id: Set(user_id.clone()),
+						used_at: Set(used_at),
+						..Default::default()
+					});
+
+					if rows.len() == IMPORT_CHUNK_SIZE {
+						imported += insert_rows(&transaction, std::mem::take(&mut rows)).await?;
+					}
+				}
+			}
+		}
+	}
+
+	imported += insert_rows(&transaction, rows).await?;
+
+	transaction.commit().await?;
+
+	let imported_path = format!("{}.imported", path);
+
+	std::fs::rename(path, &imported_path)
+		.context(format!("Failed to rename {} to {}", path, imported_path))?;
+
+	info!(
+		"Imported {} command use from {} into the database.",
+		imported, path
+	);
+
+	Ok(imported)
+}
+
+async fn insert_rows(connection: &impl ConnectionTrait, rows: Vec<ActiveModel>) -> Result<u64> {
+	let count = rows.len() as u64;
+
+	if count > 0 {
+		CommandUsage::insert_many(rows)
+			.exec(connection)
+			.await
+			.context("Failed to import the command usage")?;
+	}
+
+	Ok(count)
+}
+
+/// Writes the retained command usage to `path` in the format of the legacy
+/// `db/command_use.json` file, so it can be read by an older version or imported again.
+/// Returns the number of exported command use.
+
+pub async fn export_legacy_command_usage(
+	connection: &DatabaseConnection, path: &str,
+) -> Result<u64> {
+	let bucket = UsageGranularity::Hour.bucket_expression(connection.get_database_backend());
+
+	let rows = CommandUsage::find()
+		.select_only()
+		.column(Column::CommandName)
+		.column(Column::UserId)
+		.column_as(Expr::cust(bucket), "bucket")
+		.column_as(Column::Id.count(), "usage")
+		.group_by(Column::CommandName)
+		.group_by(Column::UserId)
+		.group_by(Expr::cust(bucket))
+		.into_model::<UsageHourCount>()
+		.all(connection)
+		.await
+		.context("Failed to get the command usage")?;
+
+	let user_names: HashMap<String, String> = UserData::find()
+		.all(connection)
+		.await
+		.context("Failed to get the user names")?
+		.into_iter()
+		.map(|user| (user.user_id, user.username))
+		.collect();
+
+	let mut root_usage = LegacyRootUsage::default();
+
+	let mut exported = 0;
+
+	for row in rows {
+		// the buckets are in the yyyy-mm-dd hh:00 format and the keys in the dd:mm:yyyy:hh one
+		let hour = match NaiveDateTime::parse_from_str(&row.bucket, "%Y-%m-%d %H:%M") {
+			Ok(hour) => hour.format("%d:%m:%Y:%H").to_string(),
+			Err(_) => {
+				warn!(
+					"Skipping the unknown hour {} of {}.",
+					row.bucket, row.command_name
+				);
+
+				continue;
+			},
+		};
+
+		let user_usage = root_usage
+			.command_list
+			.entry(row.command_name)
+			.or_default()
+			.user_info
+			.entry(row.user_id.clone())
+			.or_insert_with(|| LegacyUserUsage {
+				user_name: user_names.get(&row.user_id).cloned().unwrap_or_default(),
+				..Default::default()
+			});
+
+		user_usage.usage += row.usage as u128;
+
+		user_usage.hourly_usage.insert(hour, row.usage as u128);
+
+		exported += row.usage as u64;
+	}
+
+	let content =
+		serde_json::to_string(&root_usage).context("Failed to serialize the command usage")?;
+
+	std::fs::write(path, content).context(format!("Failed to write {}", path))?;
+
+	info!("Exported {} command use to {}.", exported, path);
+
+	Ok(exported)
+}
@@ -1,9 +1,7 @@
-use serenity::all::{ChannelId, CommandInteraction, Context};
+use serenity::all::{ChannelId, Context, GuildId};
 
-pub async fn get_nsfw(command: &CommandInteraction, ctx: &Context) -> bool {
-	let channel_id: ChannelId = command.channel_id;
-
-	let channel = match channel_id.to_channel(&ctx.http, command.guild_id).await {
+pub async fn get_nsfw(channel_id: ChannelId, guild_id: Option<GuildId>, ctx: &Context) -> bool {
+	let channel = match channel_id.to_channel(&ctx.http, guild_id).await {
 		Ok(chan) => chan,
 		Err(_) => return false,
 	};
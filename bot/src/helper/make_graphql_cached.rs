@@ -1,78 +1,244 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::cache::NamespacedCache;
+use crate::constant::{ANILIST_MAX_RETRY, ANILIST_RATE_LIMIT, ANILIST_RETRY_BASE_DELAY};
+use crate::error_management::kasuki_error::KasukiError;
+use crate::metrics::METRICS;
 use anyhow::Result;
 use cynic::{GraphQlResponse, Operation, QueryFragment, QueryVariables};
-use moka::future::Cache;
-use reqwest::Client;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
+use tokio::sync::Mutex;
+use tracing::{instrument, trace, warn};
 
-pub async fn make_request_anilist<
-	'a,
-	T: QueryFragment,
-	S: QueryVariables + Serialize,
-	U: for<'de> Deserialize<'de>,
->(
-	operation: Operation<T, S>, always_update: bool,
-	anilist_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<GraphQlResponse<U>> {
-	if !always_update {
-		do_request(operation, anilist_cache).await
-	} else {
-		let return_data: GraphQlResponse<U> = check_cache(operation, anilist_cache).await?;
+const ANILIST_API_URL: &str = "https://graphql.anilist.co/";
+
+/// Client used for every request to the AniList API.
+///
+/// The responses are cached by query and variables, and every request goes through a token
+/// bucket shared by the commands and the background tasks so the bot stays under the
+/// AniList rate limit. Requests answered with a 429 or a 5xx are retried with a backoff.
+
+pub struct AnilistClient {
+	http_client: Client,
+	cache: NamespacedCache,
+	rate_limiter: Mutex<TokenBucket>,
+}
+
+impl AnilistClient {
+	pub fn new(http_client: Client, cache: NamespacedCache) -> Self {
+		Self {
+			http_client,
+			cache,
+			rate_limiter: Mutex::new(TokenBucket::new(ANILIST_RATE_LIMIT)),
+		}
+	}
+
+	/// Sends the operation and returns the response, reading it from the cache unless
+	/// `always_update` is set. Only responses without errors are cached.
+
+	pub async fn request<
+		T: QueryFragment,
+		S: QueryVariables + Serialize,
+		U: for<'de> Deserialize<'de>,
+	>(
+		&self, operation: Operation<T, S>, always_update: bool,
+	) -> Result<GraphQlResponse<U>> {
+		let key = cache_key(&operation)?;
+
+		if !always_update {
+			if let Some(data) = self.cache.get(&key).await {
+				trace!("AniList cache hit");
+
+				return get_type(data);
+			}
+		}
+
+		let response_text = self.send(&operation).await?;
+
+		let response: GraphQlResponse<U> = get_type(response_text.clone())?;
+
+		if response.errors.is_none() {
+			self.cache.set(&key, response_text).await;
+		}
+
+		Ok(response)
+	}
+
+	#[instrument(name = "anilist_request", skip_all, fields(operation = ?operation.operation_name))]
+
+	async fn send<T, S: Serialize>(&self, operation: &Operation<T, S>) -> Result<String> {
+		let mut attempt = 0;
 
-		Ok(return_data)
+		loop {
+			self.acquire().await;
+
+			let resp = self
+				.http_client
+				.post(ANILIST_API_URL)
+				.header("Content-Type", "application/json")
+				.header("Accept", "application/json")
+				.json(operation)
+				.send()
+				.await
+				.map_err(|e| {
+					METRICS.record_api_request("anilist", "error".to_string());
+
+					KasukiError::UpstreamApi(format!("AniList request failed: {}", e))
+				})?;
+
+			let status = resp.status();
+
+			METRICS.record_api_request("anilist", status.as_u16().to_string());
+
+			if remaining_requests(resp.headers()) == Some(0)
+				|| status == StatusCode::TOO_MANY_REQUESTS
+			{
+				self.rate_limiter.lock().await.drain();
+			}
+
+			if status != StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+				return Ok(resp.text().await?);
+			}
+
+			if attempt >= ANILIST_MAX_RETRY {
+				return Err(KasukiError::UpstreamApi(format!(
+					"AniList answered {} after {} retries",
+					status, attempt
+				))
+				.into());
+			}
+
+			let backoff = Duration::from_millis(ANILIST_RETRY_BASE_DELAY << attempt);
+
+			let delay = if status == StatusCode::TOO_MANY_REQUESTS {
+				retry_after(resp.headers()).unwrap_or(backoff)
+			} else {
+				backoff
+			};
+
+			warn!(
+				"AniList answered {}, retrying in {:?} ({}/{}).",
+				status,
+				delay,
+				attempt + 1,
+				ANILIST_MAX_RETRY
+			);
+
+			tokio::time::sleep(delay).await;
+
+			attempt += 1;
+		}
+	}
+
+	// Waits until the shared bucket has a token for this request.
+	async fn acquire(&self) {
+		loop {
+			let wait = self.rate_limiter.lock().await.try_take();
+
+			match wait {
+				None => return,
+				Some(wait) => {
+					METRICS.record_rate_limit_wait("anilist", wait);
+
+					tokio::time::sleep(wait).await
+				},
+			}
+		}
 	}
 }
 
-async fn check_cache<
-	'a,
-	T: QueryFragment,
-	S: QueryVariables + Serialize,
-	U: for<'de> Deserialize<'de>,
->(
-	operation: Operation<T, S>, anilist_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<GraphQlResponse<U>> {
-	let anilist_cache_clone = anilist_cache.clone();
+struct TokenBucket {
+	capacity: f64,
+	tokens: f64,
+	refill_per_second: f64,
+	last_refill: Instant,
+}
 
-	let guard = anilist_cache_clone.read().await;
+impl TokenBucket {
+	fn new(requests_per_minute: u32) -> Self {
+		let capacity = requests_per_minute as f64;
 
-	let cache = guard.get(&operation.query).await;
+		Self {
+			capacity,
+			tokens: capacity,
+			refill_per_second: capacity / 60.0,
+			last_refill: Instant::now(),
+		}
+	}
+
+	fn refill(&mut self) {
+		let now = Instant::now();
+
+		let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+
+		self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+
+		self.last_refill = now;
+	}
 
-	drop(guard);
+	// Takes a token if one is available, otherwise returns how long to wait for the next one.
+	fn try_take(&mut self) -> Option<Duration> {
+		self.refill();
 
-	match cache {
-		Some(data) => get_type(data),
-		None => do_request(operation, anilist_cache).await,
+		if self.tokens >= 1.0 {
+			self.tokens -= 1.0;
+
+			None
+		} else {
+			Some(Duration::from_secs_f64(
+				(1.0 - self.tokens) / self.refill_per_second,
+			))
+		}
+	}
+
+	// Used when AniList reports that the limit is reached before the bucket is empty.
+	fn drain(&mut self) {
+		self.refill();
+
+		self.tokens = self.tokens.min(0.0);
 	}
 }
 
-async fn do_request<
+/// The query alone is not enough as the same query is used with different variables.
+
+fn cache_key<T, S: Serialize>(operation: &Operation<T, S>) -> Result<String> {
+	let variables = serde_json::to_string(&operation.variables)?;
+
+	Ok(format!("{}{}", operation.query, variables))
+}
+
+fn remaining_requests(headers: &HeaderMap) -> Option<u32> {
+	headers
+		.get("X-RateLimit-Remaining")?
+		.to_str()
+		.ok()?
+		.parse()
+		.ok()
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+	let seconds: u64 = headers.get(RETRY_AFTER)?.to_str().ok()?.parse().ok()?;
+
+	Some(Duration::from_secs(seconds))
+}
+
+/// Sends the operation through the shared AniList client.
+///
+/// With `always_update` set the request always goes to AniList and refreshes the cache,
+/// otherwise a cached response younger than `cache.ttl.anilist` is returned.
+
+pub async fn make_request_anilist<
+	'a,
 	T: QueryFragment,
 	S: QueryVariables + Serialize,
 	U: for<'de> Deserialize<'de>,
 >(
-	operation: Operation<T, S>, anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	operation: Operation<T, S>, always_update: bool, anilist_client: Arc<AnilistClient>,
 ) -> Result<GraphQlResponse<U>> {
-	let client = Client::new();
-
-	let resp = client
-		.post("https://graphql.anilist.co/")
-		.header("Content-Type", "application/json")
-		.header("Accept", "application/json")
-		.json(&operation)
-		.send()
-		.await?;
-
-	let response_text = resp.text().await?;
-
-	anilist_cache
-		.write()
-		.await
-		.insert(operation.query, response_text.clone())
-		.await;
-
-	get_type(response_text)
+	anilist_client.request(operation, always_update).await
 }
 
 fn get_type<U: for<'de> Deserialize<'de>>(value: String) -> Result<GraphQlResponse<U>> {
@@ -80,3 +246,45 @@ fn get_type<U: for<'de> Deserialize<'de>>(value: String) -> Result<GraphQlRespon
 
 	Ok(data)
 }
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+	use crate::structure::run::anilist::minimal_anime::{MinimalAnimeId, MinimalAnimeIdVariables};
+	use cynic::QueryBuilder;
+
+	#[test]
+
+	fn test_cache_key_uses_variables() {
+		let first = MinimalAnimeId::build(MinimalAnimeIdVariables { id: Some(1) });
+
+		let second = MinimalAnimeId::build(MinimalAnimeIdVariables { id: Some(2) });
+
+		assert_eq!(first.query, second.query);
+
+		assert_ne!(cache_key(&first).unwrap(), cache_key(&second).unwrap());
+
+		let same = MinimalAnimeId::build(MinimalAnimeIdVariables { id: Some(1) });
+
+		assert_eq!(cache_key(&first).unwrap(), cache_key(&same).unwrap());
+	}
+
+	#[test]
+
+	fn test_token_bucket() {
+		let mut bucket = TokenBucket::new(2);
+
+		assert!(bucket.try_take().is_none());
+
+		assert!(bucket.try_take().is_none());
+
+		assert!(bucket.try_take().is_some());
+
+		let mut bucket = TokenBucket::new(90);
+
+		bucket.drain();
+
+		assert!(bucket.try_take().is_some());
+	}
+}
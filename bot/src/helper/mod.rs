@@ -1,3 +1,4 @@
+pub mod command_usage;
 pub mod convert_flavored_markdown;
 pub mod create_default_embed;
 pub mod fuzzy_search;
@@ -7,5 +8,6 @@ pub mod get_option;
 pub mod image_saver;
 pub mod make_graphql_cached;
 pub mod read_file;
+pub mod render_table;
 pub mod trimer;
 pub mod vndbapi;
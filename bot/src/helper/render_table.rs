@@ -0,0 +1,62 @@
+/// Renders rows as a text table for a code block, each column padded to its widest cell and
+/// the header underlined.
+
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+	let mut widths: Vec<usize> = headers
+		.iter()
+		.map(|header| header.chars().count())
+		.collect();
+
+	for row in rows {
+		for (width, cell) in widths.iter_mut().zip(row) {
+			*width = (*width).max(cell.chars().count());
+		}
+	}
+
+	let render_row = |cells: Vec<&str>| {
+		cells
+			.iter()
+			.zip(&widths)
+			.map(|(cell, width)| format!("{:<width$}", cell, width = width))
+			.collect::<Vec<_>>()
+			.join("  ")
+			.trim_end()
+			.to_string()
+	};
+
+	let separator = widths
+		.iter()
+		.map(|width| "-".repeat(*width))
+		.collect::<Vec<_>>()
+		.join("  ");
+
+	let mut lines = vec![render_row(headers.to_vec()), separator];
+
+	for row in rows {
+		lines.push(render_row(row.iter().map(String::as_str).collect()));
+	}
+
+	lines.join("\n")
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_render_table() {
+		let rows = vec![
+			vec!["09:30".to_string(), "12".to_string(), "Frieren".to_string()],
+			vec!["23:00".to_string(), "1".to_string(), "Dungeon Meshi".to_string()],
+		];
+
+		assert_eq!(
+			render_table(&["Time", "Ep", "Anime"], &rows),
+			"Time   Ep  Anime\n-----  --  -------------\n09:30  12  Frieren\n23:00  1   Dungeon Meshi"
+		);
+
+		assert_eq!(render_table(&["Time"], &[]), "Time\n----");
+	}
+}
@@ -30,6 +30,18 @@ pub fn trim_webhook(desc: String, lenght_diff: i32) -> String {
 	}
 }
 
+/// Cuts `title` to `length` characters, ending with `...` when it is longer.
+
+pub fn trim_title(title: &str, length: usize) -> String {
+	if title.chars().count() <= length {
+		return title.to_string();
+	}
+
+	let trimmed: String = title.chars().take(length - 3).collect();
+
+	format!("{}...", trimmed)
+}
+
 #[cfg(test)]
 
 mod tests {
@@ -82,4 +94,14 @@ mod tests {
 
 		assert_eq!(result, "Hello, world!")
 	}
+
+	#[test]
+
+	fn test_trim_title() {
+		assert_eq!(trim_title("Frieren", 10), "Frieren");
+
+		assert_eq!(trim_title("Sousou no Frieren", 10), "Sousou ...");
+
+		assert_eq!(trim_title("葬送のフリーレン", 6), "葬送の...");
+	}
 }
@@ -1,9 +1,7 @@
 use anyhow::Result;
-use std::sync::Arc;
 
-use moka::future::Cache;
+use crate::cache::NamespacedCache;
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 
@@ -56,9 +54,7 @@ pub struct CharacterRoot {
 	pub results: Vec<Character>,
 }
 
-pub async fn get_character(
-	value: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<CharacterRoot> {
+pub async fn get_character(value: String, vndb_cache: NamespacedCache) -> Result<CharacterRoot> {
 	let value = value.to_lowercase();
 
 	let value = value.trim();
@@ -1,24 +1,21 @@
 use anyhow::Result;
-use std::sync::Arc;
+use tracing::instrument;
 
-use moka::future::Cache;
-use tokio::sync::RwLock;
+use crate::cache::NamespacedCache;
+use crate::error_management::kasuki_error::KasukiError;
+use crate::metrics::METRICS;
 
-pub async fn do_request_cached(
-	path: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<String> {
-	let cache = vndb_cache.read().await.get(&path).await;
-
-	if let Some(cached) = cache {
+pub async fn do_request_cached(path: String, vndb_cache: NamespacedCache) -> Result<String> {
+	if let Some(cached) = vndb_cache.get(&path).await {
 		return Ok(cached);
 	}
 
 	do_request(path, vndb_cache).await
 }
 
-pub async fn do_request(
-	path: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<String> {
+#[instrument(name = "vndb_request", skip(vndb_cache))]
+
+pub async fn do_request(path: String, vndb_cache: NamespacedCache) -> Result<String> {
 	let client = reqwest::Client::new();
 
 	let url = format!("https://api.vndb.org/kana{}", path);
@@ -28,35 +25,38 @@ pub async fn do_request(
 		.header("Content-Type", "application/json")
 		.header("Accept", "application/json")
 		.send()
-		.await?;
+		.await
+		.map_err(|e| {
+			METRICS.record_api_request("vndb", "error".to_string());
+
+			KasukiError::UpstreamApi(format!("VNDB request failed: {}", e))
+		})?;
+
+	METRICS.record_api_request("vndb", res.status().as_u16().to_string());
 
 	let response_text = res.text().await?;
 
-	vndb_cache
-		.write()
-		.await
-		.insert(path, response_text.clone())
-		.await;
+	vndb_cache.set(&path, response_text.clone()).await;
 
 	Ok(response_text)
 }
 
 pub async fn do_request_cached_with_json(
-	path: String, json: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
+	path: String, json: String, vndb_cache: NamespacedCache,
 ) -> Result<String> {
 	let key = format!("{}_{}", path, json);
 
-	let cache = vndb_cache.read().await.get(&key).await;
-
-	if let Some(cached) = cache {
+	if let Some(cached) = vndb_cache.get(&key).await {
 		return Ok(cached);
 	}
 
 	do_request_with_json(path, json, vndb_cache).await
 }
 
+#[instrument(name = "vndb_request", skip(json, vndb_cache))]
+
 pub async fn do_request_with_json(
-	path: String, json: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
+	path: String, json: String, vndb_cache: NamespacedCache,
 ) -> Result<String> {
 	let key = format!("{}_{}", path, json);
 
@@ -70,15 +70,18 @@ pub async fn do_request_with_json(
 		.header("Accept", "application/json")
 		.body(json)
 		.send()
-		.await?;
+		.await
+		.map_err(|e| {
+			METRICS.record_api_request("vndb", "error".to_string());
+
+			KasukiError::UpstreamApi(format!("VNDB request failed: {}", e))
+		})?;
+
+	METRICS.record_api_request("vndb", res.status().as_u16().to_string());
 
 	let response_text = res.text().await?;
 
-	vndb_cache
-		.write()
-		.await
-		.insert(key, response_text.clone())
-		.await;
+	vndb_cache.set(&key, response_text.clone()).await;
 
 	Ok(response_text)
 }
@@ -1,10 +1,8 @@
 use anyhow::Result;
 use std::fmt::Display;
-use std::sync::Arc;
 
-use moka::future::Cache;
+use crate::cache::NamespacedCache;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use tokio::sync::RwLock;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 
@@ -79,9 +77,7 @@ pub struct VNRoot {
 	pub more: bool,
 }
 
-pub async fn get_vn(
-	value: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<VNRoot> {
+pub async fn get_vn(value: String, vndb_cache: NamespacedCache) -> Result<VNRoot> {
 	let value = value.to_lowercase();
 
 	let value = value.trim();
@@ -142,7 +138,8 @@ pub enum DevStatus {
 impl Serialize for DevStatus {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
-		S: Serializer, {
+		S: Serializer,
+	{
 		let value = match self {
 			Self::Finished => 0,
 			Self::Development => 1,
@@ -157,7 +154,8 @@ impl Serialize for DevStatus {
 impl<'de> Deserialize<'de> for DevStatus {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
-		D: Deserializer<'de>, {
+		D: Deserializer<'de>,
+	{
 		let value = i8::deserialize(deserializer)?;
 
 		match value {
@@ -1,13 +1,9 @@
 use anyhow::Result;
-use std::sync::Arc;
 
-use moka::future::Cache;
+use crate::cache::NamespacedCache;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use tokio::sync::RwLock;
 
-pub async fn get_producer(
-	value: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<ProducerRoot> {
+pub async fn get_producer(value: String, vndb_cache: NamespacedCache) -> Result<ProducerRoot> {
 	let value = value.to_lowercase();
 
 	let value = value.trim();
@@ -84,7 +80,8 @@ pub enum Type {
 impl Serialize for Type {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
-		S: Serializer, {
+		S: Serializer,
+	{
 		let value = match self {
 			Self::Company => "co",
 			Self::Individual => "in",
@@ -98,7 +95,8 @@ impl Serialize for Type {
 impl<'de> Deserialize<'de> for Type {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
-		D: Deserializer<'de>, {
+		D: Deserializer<'de>,
+	{
 		let value = String::deserialize(deserializer)?;
 
 		match value.as_str() {
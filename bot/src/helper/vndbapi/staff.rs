@@ -1,13 +1,9 @@
 use anyhow::Result;
-use std::sync::Arc;
 
-use moka::future::Cache;
+use crate::cache::NamespacedCache;
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
 
-pub async fn get_staff(
-	value: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<StaffRoot> {
+pub async fn get_staff(value: String, vndb_cache: NamespacedCache) -> Result<StaffRoot> {
 	let value = value.to_lowercase();
 
 	let value = value.trim();
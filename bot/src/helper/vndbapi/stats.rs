@@ -1,10 +1,7 @@
-use std::sync::Arc;
-
-use moka::future::Cache;
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
 use tracing::trace;
 
+use crate::cache::NamespacedCache;
 use crate::helper::vndbapi::common::do_request_cached;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -26,7 +23,7 @@ pub struct Stats {
 }
 use anyhow::Result;
 
-pub async fn get_stats(vndb_cache: Arc<RwLock<Cache<String, String>>>) -> Result<Stats> {
+pub async fn get_stats(vndb_cache: NamespacedCache) -> Result<Stats> {
 	let path = "/stats".to_string();
 
 	let response = do_request_cached(path.clone(), vndb_cache).await?;
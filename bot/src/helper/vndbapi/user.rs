@@ -1,9 +1,7 @@
-use moka::future::Cache;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
 
+use crate::cache::NamespacedCache;
 use crate::helper::vndbapi::common::do_request_cached;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -19,9 +17,7 @@ pub struct VnUser {
 }
 
 use anyhow::Result;
-pub async fn get_user(
-	path: String, vndb_cache: Arc<RwLock<Cache<String, String>>>,
-) -> Result<VnUser> {
+pub async fn get_user(path: String, vndb_cache: NamespacedCache) -> Result<VnUser> {
 	let response = do_request_cached(path.clone(), vndb_cache).await?;
 
 	let response: HashMap<String, VnUser> = serde_json::from_str(&response)?;
@@ -2,30 +2,39 @@ use std::fs;
 use std::str::FromStr;
 use tracing_appender::rolling::Rotation;
 use tracing_subscriber::filter::{Directive, EnvFilter};
-use tracing_subscriber::fmt;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{fmt, Layer};
 
-use crate::constant::{LOGS_PATH, LOGS_PREFIX, LOGS_SUFFIX, OTHER_CRATE_LEVEL};
+use crate::config::LoggingConfig;
+use crate::constant::{
+	LOGS_PATH, LOGS_PREFIX, LOGS_SUFFIX, OTEL_SERVICE_NAME, OTHER_CRATE_LEVEL, SQL_QUERY_LEVEL,
+};
 use anyhow::{Context, Result};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
 use tracing_appender::non_blocking::WorkerGuard;
 
-pub fn init_logger(log: &str, max_log_retention_days: u32) -> Result<WorkerGuard> {
-	let kasuki_filter = match log {
-		"warn" => "kasuki=warn",
-		"error" => "kasuki=error",
-		"debug" => "kasuki=debug",
-		"trace" => "kasuki=trace",
-		_ => "kasuki=info",
-	};
-
-	let crate_log = get_directive(OTHER_CRATE_LEVEL)?;
+/// Keeps the loggers alive, the file logs and the spans not exported yet are flushed on drop.
 
-	let kasuki_log = get_directive(kasuki_filter)?;
+pub struct LoggerGuard {
+	_file_guard: WorkerGuard,
+	tracer_provider: Option<TracerProvider>,
+}
 
-	let filter = EnvFilter::from_default_env()
-		.add_directive(crate_log)
-		.add_directive(kasuki_log);
+impl Drop for LoggerGuard {
+	fn drop(&mut self) {
+		if let Some(tracer_provider) = self.tracer_provider.take() {
+			if let Err(e) = tracer_provider.shutdown() {
+				eprintln!("Failed to export the remaining spans. {}", e);
+			}
+		}
+	}
+}
 
+pub fn init_logger(logging: &LoggingConfig) -> Result<LoggerGuard> {
 	let log_prefix = LOGS_PREFIX;
 
 	let log_suffix = LOGS_SUFFIX;
@@ -36,33 +45,106 @@ pub fn init_logger(log: &str, max_log_retention_days: u32) -> Result<WorkerGuard
 		.filename_prefix(log_prefix)
 		.filename_suffix(log_suffix)
 		.rotation(Rotation::DAILY)
-		.max_log_files(max_log_retention_days as usize)
+		.max_log_files(logging.max_log_retention as usize)
 		.build(logs_path)
 		.context("Failed to create file appender")?;
 
 	let (file_appender_non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
-	let format = fmt::layer().with_ansi(true);
+	// The json logs hold the fields of the current span, like the id of the interaction.
+	let (format, file_format) = if logging.log_format == "json" {
+		(
+			fmt::layer().json().with_current_span(true).boxed(),
+			fmt::layer()
+				.json()
+				.with_current_span(true)
+				.with_writer(file_appender_non_blocking)
+				.boxed(),
+		)
+	} else {
+		(
+			fmt::layer().with_ansi(true).boxed(),
+			fmt::layer()
+				.with_writer(file_appender_non_blocking)
+				.with_ansi(false)
+				.boxed(),
+		)
+	};
+
+	let tracer_provider = if logging.otlp_endpoint.is_empty() {
+		None
+	} else {
+		Some(init_tracer_provider(&logging.otlp_endpoint)?)
+	};
+
+	// The database queries are only exported, they would flood the console.
+	let otlp = match &tracer_provider {
+		Some(tracer_provider) => Some(
+			tracing_opentelemetry::layer()
+				.with_tracer(tracer_provider.tracer(OTEL_SERVICE_NAME))
+				.with_filter(
+					get_filter(&logging.log_level)?.add_directive(get_directive(SQL_QUERY_LEVEL)?),
+				),
+		),
+		None => None,
+	};
 
 	let registry = tracing_subscriber::registry()
-		.with(filter)
-		.with(format)
-		.with(
-			tracing_subscriber::fmt::layer()
-				.with_writer(file_appender_non_blocking)
-				.with_ansi(false),
-		);
+		.with(format.with_filter(get_filter(&logging.log_level)?))
+		.with(file_format.with_filter(get_filter(&logging.log_level)?))
+		.with(otlp);
 
 	tracing::subscriber::set_global_default(registry)
 		.context("Failed to set global default subscriber")?;
 
-	Ok(guard)
+	Ok(LoggerGuard {
+		_file_guard: guard,
+		tracer_provider,
+	})
+}
+
+/// Creates the provider exporting the spans in batches to the OTLP gRPC collector at `endpoint`.
+
+fn init_tracer_provider(endpoint: &str) -> Result<TracerProvider> {
+	let exporter = opentelemetry_otlp::SpanExporter::builder()
+		.with_tonic()
+		.with_endpoint(endpoint)
+		.build()
+		.context("Failed to create the OTLP exporter")?;
+
+	let tracer_provider = TracerProvider::builder()
+		.with_batch_exporter(exporter, runtime::Tokio)
+		.with_resource(Resource::new(vec![KeyValue::new(
+			"service.name",
+			OTEL_SERVICE_NAME,
+		)]))
+		.build();
+
+	Ok(tracer_provider)
 }
 
 pub fn create_log_directory() -> Result<()> {
 	fs::create_dir_all("../logs").context("Failed to create log directory")
 }
 
+fn get_filter(log_level: &str) -> Result<EnvFilter> {
+	let kasuki_filter = match log_level {
+		"warn" => "kasuki=warn",
+		"error" => "kasuki=error",
+		"debug" => "kasuki=debug",
+		"trace" => "kasuki=trace",
+		_ => "kasuki=info",
+	};
+
+	let crate_log = get_directive(OTHER_CRATE_LEVEL)?;
+
+	let kasuki_log = get_directive(kasuki_filter)?;
+
+	Ok(EnvFilter::from_default_env()
+		.add_directive(crate_log)
+		.add_directive(kasuki_log))
+}
+
 fn get_directive(filter: &str) -> Result<Directive> {
 	Directive::from_str(filter).context("Failed to create directive")
 }
@@ -1,24 +1,39 @@
+use crate::background_task::activity::chapter_provider::MangaUpdatesProvider;
+use crate::cache::{create_cache_backend, NamespacedCache};
+use crate::cli::{run_operator_command, Cli, CliCommand};
 use crate::config::{Config, DbConfig};
-use crate::constant::{CACHE_MAX_CAPACITY, COMMAND_USE_PATH, TIME_BETWEEN_CACHE_UPDATE};
-use crate::event_handler::{BotData, Handler, RootUsage};
+use crate::constant::{
+	COMMAND_USE_PATH, MANGAUPDATES_API_URL, SQLITE_DEFAULT_PATH, SQLITE_IN_MEMORY,
+};
+use crate::event_handler::{BotData, Handler};
+use crate::grpc::server::grpc_server_launcher;
+use crate::helper::command_usage::import_legacy_command_usage;
+use crate::helper::make_graphql_cached::AnilistClient;
 use crate::logger::{create_log_directory, init_logger};
+use crate::metrics::server::metrics_server_launcher;
+use crate::structure::message::common::reload_localization;
 use anyhow::{Context, Result};
-use moka::future::Cache;
-use serenity::gateway::ShardManager;
+use clap::Parser;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectOptions, ConnectionTrait, DatabaseConnection};
 use serenity::prelude::GatewayIntents;
+use serenity::secrets::Token;
 use serenity::Client;
 use songbird::driver::DecodeMode;
+use std::path::Path;
 use std::process;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
-use serenity::secrets::Token;
 use tokio::sync::RwLock;
 use tracing::{error, info};
 
 mod audio;
 pub mod autocomplete;
 mod background_task;
+mod cache;
+mod check;
+mod cli;
 mod command;
 mod components;
 mod config;
@@ -27,51 +42,44 @@ mod custom_serenity_impl;
 pub mod database;
 pub mod error_management;
 mod event_handler;
+mod grpc;
 mod helper;
 mod logger;
+mod metrics;
 mod new_member;
 mod register;
 mod removed_member;
+mod shutdown;
 mod structure;
 
 #[tokio::main]
 
 async fn main() {
-	println!("Preparing bot environment please wait...");
+	let cli = Cli::parse();
 
-	// read config.toml as string
-	let config = match std::fs::read_to_string("config.toml") {
-		Ok(config) => config,
-		Err(e) => {
-			eprintln!("Error while reading config.toml: {:?}", e);
+	let command = cli.command.unwrap_or(CliCommand::Run);
 
-			process::exit(1);
-		},
-	};
+	// `kasuki check` only validates the json files, it does not need a config.
+	if let CliCommand::Check = command {
+		process::exit(check::run_check());
+	}
+
+	println!("Preparing bot environment please wait...");
 
-	let mut config: Config = match toml::from_str(&config) {
+	// Read the config file, apply the KASUKI_* environment variables and validate it.
+	let config = match Config::load(&cli.config) {
 		Ok(config) => config,
 		Err(e) => {
-			eprintln!("Error while parsing config.toml: {:?}", e);
+			eprintln!("Error while loading {}: {:#}", cli.config, e);
 
 			process::exit(1);
 		},
 	};
 
-	config.set_default_value_on_none();
-
-	let log = config.logging.log_level.clone();
-
 	let discord_token = config.bot.discord_token.clone();
 
-	let max_log_retention_days = config.logging.max_log_retention;
-
 	let config = Arc::new(config);
 
-	// Get the log level from the environment variable "RUST_LOG".
-	// If the variable is not set, default to "info".
-	let log = log.as_str();
-
 	// Create the log directory.
 	// If an error occurs, print the error and return.
 	if let Err(e) = create_log_directory() {
@@ -80,9 +88,9 @@ async fn main() {
 		process::exit(2);
 	}
 
-	// Initialize the logger with the specified log level.
+	// Initialize the logger with the log level, format and OTLP exporter of the config.
 	// If an error occurs, print the error and return.
-	let _guard = match init_logger(log, max_log_retention_days) {
+	let _guard = match init_logger(&config.logging) {
 		Ok(guard) => guard,
 		Err(e) => {
 			eprintln!("{:?}", e);
@@ -91,52 +99,70 @@ async fn main() {
 		},
 	};
 
-	// Initialize the SQL database.
-	// If an error occurs, log the error and return.
-	if let Err(e) = init_db(config.clone()).await {
-		let e = e.to_string().replace("\\\\n", "\n");
+	// The operator commands (migrate, register-commands, ...) run once and exit.
+	if !matches!(command, CliCommand::Run) {
+		let exit_code = run_operator_command(command, config).await;
 
-		error!("{}", e);
+		// Flushes the logs before exiting.
+		drop(_guard);
 
-		process::exit(4);
+		process::exit(exit_code);
 	}
 
-	let number_of_command_use_per_command: RootUsage;
+	// Load and validate every localized message once, they are then read from memory.
+	if let Err(e) = reload_localization() {
+		error!("{:#}", e);
 
-	// populate the number_of_command_use_per_command with the content of the file
-	if let Ok(content) = std::fs::read_to_string(COMMAND_USE_PATH) {
-		number_of_command_use_per_command =
-			serde_json::from_str(&content).unwrap_or_else(|_| RootUsage::new());
-	} else {
-		number_of_command_use_per_command = RootUsage::new();
+		process::exit(9);
 	}
 
-	let number_of_command_use_per_command =
-		Arc::new(RwLock::new(number_of_command_use_per_command));
-
-	let cache: Cache<String, String> = Cache::builder()
-		.time_to_live(Duration::from_secs(TIME_BETWEEN_CACHE_UPDATE))
-		.max_capacity(CACHE_MAX_CAPACITY)
-		.build();
+	// Connect to the SQL database and apply the pending migrations.
+	// If an error occurs, log the error and return.
+	let connection = match init_db(config.clone()).await {
+		Ok(connection) => connection,
+		Err(e) => {
+			let e = format!("{:#}", e).replace("\\\\n", "\n");
 
-	let anilist_cache: Arc<RwLock<Cache<String, String>>> = Arc::new(RwLock::new(cache));
+			error!("{}", e);
 
-	let cache: Cache<String, String> = Cache::builder()
-		.time_to_live(Duration::from_secs(TIME_BETWEEN_CACHE_UPDATE))
-		.max_capacity(CACHE_MAX_CAPACITY)
-		.build();
+			process::exit(4);
+		},
+	};
 
-	let vndb_cache: Arc<RwLock<Cache<String, String>>> = Arc::new(RwLock::new(cache));
+	// Move the usage of the json file used before the command_usage table into the database.
+	if Path::new(COMMAND_USE_PATH).exists() {
+		if let Err(e) = import_legacy_command_usage(&connection, COMMAND_USE_PATH).await {
+			error!("Failed to import the legacy command usage. {:#}", e);
+		}
+	}
 
-	let connection = match sea_orm::Database::connect(get_url(config.db.clone())).await {
-		Ok(connection) => connection,
+	// The cache backend is shared by the AniList and VNDB caches, each in its own namespace.
+	let cache_backend = match create_cache_backend(&config.cache).await {
+		Ok(cache_backend) => cache_backend,
 		Err(e) => {
-			error!("Failed to connect to the database. {}", e);
+			error!("Failed to init the cache. {:#}", e);
 
-			return;
+			process::exit(8);
 		},
 	};
 
+	let anilist_cache = NamespacedCache::new(
+		cache_backend.clone(),
+		"anilist",
+		Duration::from_secs(config.cache.ttl.anilist),
+	);
+
+	let vndb_cache = NamespacedCache::new(
+		cache_backend.clone(),
+		"vndb",
+		Duration::from_secs(config.cache.ttl.vndb),
+	);
+
+	let http_client = reqwest::Client::new();
+
+	// Shared by every command and background task so the AniList rate limit is respected.
+	let anilist_client = Arc::new(AnilistClient::new(http_client.clone(), anilist_cache));
+
 	// Get all the non-privileged intent.
 	let gateway_intent_non_privileged =
 		GatewayIntents::non_privileged() | GatewayIntents::GUILD_VOICE_STATES;
@@ -174,18 +200,23 @@ async fn main() {
 	let manager = songbird::Songbird::serenity_from_config(songbird_config);
 
 	let bot_data: Arc<BotData> = Arc::new(BotData {
-		number_of_command_use_per_command,
 		config,
 		bot_info: Arc::new(RwLock::new(None)),
-		anilist_cache,
+		anilist_client,
 		vndb_cache,
+		cache_backend,
 		already_launched: false.into(),
 		apps: Arc::new(Default::default()),
 		user_blacklist_server_image: Arc::new(Default::default()),
 		db_connection: Arc::new(connection),
 		manager: Arc::clone(&manager),
-		http_client: reqwest::Client::new(),
+		http_client: http_client.clone(),
 		shard_manager: Arc::new(Default::default()),
+		tasks: Arc::new(Default::default()),
+		shutdown: Arc::new(Default::default()),
+		activity_scheduler: Arc::new(Default::default()),
+		error_reporter: Arc::new(Default::default()),
+		chapter_provider: Arc::new(MangaUpdatesProvider::new(http_client, MANGAUPDATES_API_URL)),
 	});
 
 	let mut client = Client::builder(discord_token, gateway_intent)
@@ -205,11 +236,40 @@ async fn main() {
 	let mut guard = bot_data_manager.write().await;
 
 	*guard = Some(shard_manager);
+
+	drop(guard);
+
+	// Launch the gRPC management server if it is enabled.
+	if bot_data.config.grpc.grpc_is_on {
+		let bot_data = bot_data.clone();
+
+		let cache = client.cache.clone();
+
+		let http = client.http.clone();
+
+		tokio::spawn(async move {
+			if let Err(e) = grpc_server_launcher(bot_data, cache, http).await {
+				error!("gRPC server error: {:#}", e);
+			}
+		});
+	}
+
+	// Launch the Prometheus metrics endpoint if it is enabled.
+	if bot_data.config.metrics.enabled {
+		let bot_data = bot_data.clone();
+
+		let cache = client.cache.clone();
+
+		tokio::spawn(async move {
+			if let Err(e) = metrics_server_launcher(bot_data, cache).await {
+				error!("Metrics server error: {:#}", e);
+			}
+		});
+	}
+
 	// Clone the shard manager from the client.
 	let shard_manager = client.shard_manager.clone();
 
-	let shutdown = shard_manager.clone();
-
 	// Spawn a new asynchronous task for starting the client.
 	// If the client fails to start, log the error.
 	tokio::spawn(async move {
@@ -240,6 +300,18 @@ async fn main() {
 		let mut sigusr2 =
 			tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined2()).unwrap();
 
+		// SIGHUP reloads the localization files without restarting the bot.
+		let mut sighup =
+			tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).unwrap();
+
+		tokio::spawn(async move {
+			while sighup.recv().await.is_some() {
+				if let Err(e) = reload_localization() {
+					error!("Failed to reload the localization. {:#}", e);
+				}
+			}
+		});
+
 		tokio::select! {
 			_ = sigint.recv() => {},
 			_ = sigterm.recv() => {},
@@ -248,9 +320,10 @@ async fn main() {
 			_ = sigusr2.recv() => {},
 		}
 
-		info!("Received bot shutdown signal. Shutting down bot.");
+		shutdown::shutdown(bot_data, shard_manager).await;
 
-		ShardManager::shutdown_all(&shutdown).await;
+		// Flushes the logs before exiting.
+		drop(_guard);
 
 		std::process::exit(0);
 	}
@@ -278,46 +351,131 @@ async fn main() {
 			_ = ctrl_shutdown.recv() => {},
 		}
 
-		info!("Received bot shutdown signal. Shutting down bot.");
+		shutdown::shutdown(bot_data, shard_manager).await;
 
-		ShardManager::shutdown_all(&shutdown).await;
+		// Flushes the logs before exiting.
+		drop(_guard);
 
 		process::exit(0);
 	}
 }
 
-async fn init_db(config: Arc<Config>) -> Result<()> {
-	let db_config = config.db.clone();
+/// Connects to the database and applies the pending migrations.
 
-	let url = get_url(db_config);
+async fn init_db(config: Arc<Config>) -> Result<DatabaseConnection> {
+	let connection = connect_db(config).await?;
 
-	std::env::set_var("DATABASE_URL", url);
+	run_migrations(&connection).await?;
 
-	#[cfg(windows)]
-	{
-		let mut cmd = process::Command::new("./Migration.exe");
+	Ok(connection)
+}
 
-		let child = cmd.spawn().context("Failed to run Migration")?;
+/// Connects to the database, creating it first on a postgresql server that does not have it.
 
-		child
-			.wait_with_output()
-			.context("Failed to wait for Migration")?;
-	}
+async fn connect_db(config: Arc<Config>) -> Result<DatabaseConnection> {
+	let db_config = config.db.clone();
 
-	#[cfg(unix)]
+	let connection = match sea_orm::Database::connect(get_connect_options(db_config.clone())).await
 	{
-		let mut cmd = process::Command::new("./Migration");
+		Ok(connection) => connection,
+		// The server may be up without the kasuki database existing yet.
+		Err(e) if db_config.db_type == "postgresql" => {
+			info!(
+				"Failed to connect to the database, trying to create it. {}",
+				e
+			);
+
+			create_postgres_database(db_config.clone()).await?;
+
+			sea_orm::Database::connect(get_connect_options(db_config))
+				.await
+				.context("Failed to connect to the database")?
+		},
+		Err(e) => return Err(e).context("Failed to connect to the database"),
+	};
 
-		let child = cmd.spawn().context("Failed to run Migration")?;
+	Ok(connection)
+}
+
+async fn create_postgres_database(db_config: DbConfig) -> Result<()> {
+	let db_name = db_config.database.clone().unwrap_or(String::from("kasuki"));
+
+	let server_config = DbConfig {
+		database: Some(String::new()),
+		..db_config
+	};
+
+	let connection = sea_orm::Database::connect(get_url(server_config))
+		.await
+		.context("Failed to connect to the database server")?;
+
+	connection
+		.execute_unprepared(&format!("CREATE DATABASE {}", quote_identifier(&db_name)))
+		.await
+		.context(format!("Failed to create the database {}", db_name))?;
+
+	connection.close().await?;
+
+	info!("Created the database {}", db_name);
+
+	Ok(())
+}
+
+/// Quotes a postgres identifier, the `"` inside it are doubled so the name can't end the quote.
+
+fn quote_identifier(name: &str) -> String {
+	format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+async fn run_migrations(connection: &DatabaseConnection) -> Result<()> {
+	let applied = Migrator::get_applied_migrations(connection)
+		.await
+		.context("Failed to get the applied migrations")?;
 
-		child
-			.wait_with_output()
-			.context("Failed to wait for Migration")?;
+	let applied: Vec<&str> = applied.iter().map(|migration| migration.name()).collect();
+
+	info!("Applied migrations: [{}]", applied.join(", "));
+
+	let pending = Migrator::get_pending_migrations(connection)
+		.await
+		.context("Failed to get the pending migrations")?;
+
+	if pending.is_empty() {
+		info!("No pending migration.");
+
+		return Ok(());
 	}
 
+	let pending: Vec<&str> = pending.iter().map(|migration| migration.name()).collect();
+
+	info!("Pending migrations: [{}]", pending.join(", "));
+
+	Migrator::up(connection, None)
+		.await
+		.context("Failed to apply the pending migrations")?;
+
+	info!("Applied {} migration(s).", pending.len());
+
 	Ok(())
 }
 
+pub fn get_connect_options(db_config: DbConfig) -> ConnectOptions {
+	let mut options = ConnectOptions::new(get_url(db_config.clone()));
+
+	// sqlx close idle and old connection, for an in-memory database that would drop all the data.
+	if db_config.is_in_memory() {
+		let forever = Duration::from_secs(u32::MAX as u64);
+
+		options
+			.min_connections(1)
+			.max_connections(1)
+			.idle_timeout(forever)
+			.max_lifetime(forever);
+	}
+
+	options
+}
+
 pub fn get_url(db_config: DbConfig) -> String {
 	match db_config.db_type.as_str() {
 		"postgresql" => {
@@ -367,6 +525,28 @@ pub fn get_url(db_config: DbConfig) -> String {
 
 			url
 		},
+		"sqlite" => {
+			let path = db_config
+				.database
+				.unwrap_or(String::from(SQLITE_DEFAULT_PATH));
+
+			if path == SQLITE_IN_MEMORY {
+				return String::from("sqlite::memory:");
+			}
+
+			// sqlite create the file itself with mode=rwc but not the directory holding it
+			if let Some(parent) = std::path::Path::new(&path).parent() {
+				if let Err(e) = std::fs::create_dir_all(parent) {
+					error!("Failed to create the sqlite database directory. {}", e);
+
+					process::exit(7)
+				}
+			}
+
+			let url = format!("sqlite://{}?mode=rwc", path);
+
+			url
+		},
 		_ => {
 			panic!("Unsupported database type");
 		},
@@ -0,0 +1,253 @@
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use prometheus_client::encoding::EncodeLabelSet;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::family::Family;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::metrics::histogram::{exponential_buckets, Histogram};
+use prometheus_client::registry::Registry;
+
+pub mod server;
+
+/// Metrics of the bot, recorded even when the `[metrics]` endpoint is disabled.
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct CommandLabels {
+	/// Name given by `guess_command_kind`, `anime` or `admin_anilist_add_anime_activity`.
+	pub command: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct ErrorLabels {
+	/// Kind of the `KasukiError`, `not_found`, `internal`, ...
+	pub kind: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct ApiLabels {
+	/// anilist or vndb.
+	pub api: &'static str,
+	/// Status code of the response, `error` when no response was received.
+	pub status: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct RateLimitLabels {
+	pub api: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct CacheLabels {
+	pub namespace: &'static str,
+	/// hit or miss.
+	pub result: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct ShardLabels {
+	pub shard: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+
+pub struct TaskLabels {
+	pub task: &'static str,
+}
+
+type HistogramFamily<L> = Family<L, Histogram, fn() -> Histogram>;
+
+pub struct Metrics {
+	registry: Registry,
+	pub command_invocations: Family<CommandLabels, Counter>,
+	pub command_duration: HistogramFamily<CommandLabels>,
+	pub command_errors: Family<ErrorLabels, Counter>,
+	pub api_requests: Family<ApiLabels, Counter>,
+	pub rate_limit_waits: Family<RateLimitLabels, Counter>,
+	pub rate_limit_wait_seconds: Family<RateLimitLabels, Counter<f64, AtomicU64>>,
+	pub cache_requests: Family<CacheLabels, Counter>,
+	pub shard_latency: Family<ShardLabels, Gauge<f64, AtomicU64>>,
+	pub guilds: Gauge,
+	pub voice_calls: Gauge,
+	pub task_duration: HistogramFamily<TaskLabels>,
+	pub task_failures: Family<TaskLabels, Counter>,
+}
+
+impl Metrics {
+	fn new() -> Self {
+		let mut metrics = Metrics {
+			registry: Registry::with_prefix("kasuki"),
+			command_invocations: Family::default(),
+			// From 50ms to 25s.
+			command_duration: Family::new_with_constructor(|| {
+				Histogram::new(exponential_buckets(0.05, 2.0, 10))
+			}),
+			command_errors: Family::default(),
+			api_requests: Family::default(),
+			rate_limit_waits: Family::default(),
+			rate_limit_wait_seconds: Family::default(),
+			cache_requests: Family::default(),
+			shard_latency: Family::default(),
+			guilds: Gauge::default(),
+			voice_calls: Gauge::default(),
+			// From 10ms to 45 minutes, the server image of every guild takes a while.
+			task_duration: Family::new_with_constructor(|| {
+				Histogram::new(exponential_buckets(0.01, 4.0, 10))
+			}),
+			task_failures: Family::default(),
+		};
+
+		metrics.registry.register(
+			"command_invocations",
+			"Number of commands run",
+			metrics.command_invocations.clone(),
+		);
+
+		metrics.registry.register(
+			"command_duration_seconds",
+			"Time taken by the commands",
+			metrics.command_duration.clone(),
+		);
+
+		metrics.registry.register(
+			"command_errors",
+			"Number of commands that failed, by kind of error",
+			metrics.command_errors.clone(),
+		);
+
+		metrics.registry.register(
+			"api_requests",
+			"Number of requests sent to the AniList and VNDB APIs",
+			metrics.api_requests.clone(),
+		);
+
+		metrics.registry.register(
+			"rate_limit_waits",
+			"Number of requests delayed by the rate limit",
+			metrics.rate_limit_waits.clone(),
+		);
+
+		metrics.registry.register(
+			"rate_limit_wait_seconds",
+			"Time spent waiting for the rate limit",
+			metrics.rate_limit_wait_seconds.clone(),
+		);
+
+		metrics.registry.register(
+			"cache_requests",
+			"Number of cache lookups, by namespace and result",
+			metrics.cache_requests.clone(),
+		);
+
+		metrics.registry.register(
+			"shard_latency_seconds",
+			"Gateway latency of each shard, sampled by the ping history task",
+			metrics.shard_latency.clone(),
+		);
+
+		metrics
+			.registry
+			.register("guilds", "Number of guilds", metrics.guilds.clone());
+
+		metrics.registry.register(
+			"voice_calls",
+			"Number of active voice calls",
+			metrics.voice_calls.clone(),
+		);
+
+		metrics.registry.register(
+			"task_duration_seconds",
+			"Time taken by each run of the background tasks",
+			metrics.task_duration.clone(),
+		);
+
+		metrics.registry.register(
+			"task_failures",
+			"Number of runs of the background tasks that failed or panicked",
+			metrics.task_failures.clone(),
+		);
+
+		metrics
+	}
+
+	pub fn record_command(&self, command: String, duration: Duration) {
+		let labels = CommandLabels { command };
+
+		self.command_invocations.get_or_create(&labels).inc();
+
+		self.command_duration
+			.get_or_create(&labels)
+			.observe(duration.as_secs_f64());
+	}
+
+	pub fn record_api_request(&self, api: &'static str, status: String) {
+		self.api_requests
+			.get_or_create(&ApiLabels { api, status })
+			.inc();
+	}
+
+	pub fn record_rate_limit_wait(&self, api: &'static str, wait: Duration) {
+		let labels = RateLimitLabels { api };
+
+		self.rate_limit_waits.get_or_create(&labels).inc();
+
+		self.rate_limit_wait_seconds
+			.get_or_create(&labels)
+			.inc_by(wait.as_secs_f64());
+	}
+
+	pub fn record_cache_request(&self, namespace: &'static str, hit: bool) {
+		let result = if hit { "hit" } else { "miss" };
+
+		self.cache_requests
+			.get_or_create(&CacheLabels { namespace, result })
+			.inc();
+	}
+
+	/// The metrics in the Prometheus text format.
+
+	pub fn encode(&self) -> Result<String, std::fmt::Error> {
+		let mut buffer = String::new();
+
+		prometheus_client::encoding::text::encode(&mut buffer, &self.registry)?;
+
+		Ok(buffer)
+	}
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_encode() {
+		let metrics = Metrics::new();
+
+		metrics.record_command("anime".to_string(), Duration::from_millis(120));
+
+		metrics.record_cache_request("anilist", true);
+
+		metrics.record_rate_limit_wait("anilist", Duration::from_millis(500));
+
+		let text = metrics.encode().unwrap();
+
+		assert!(text.contains("kasuki_command_invocations_total{command=\"anime\"} 1"));
+
+		assert!(
+			text.contains("kasuki_cache_requests_total{namespace=\"anilist\",result=\"hit\"} 1")
+		);
+
+		assert!(text.contains("kasuki_rate_limit_wait_seconds_total{api=\"anilist\"} 0.5"));
+	}
+}
@@ -0,0 +1,73 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::event_handler::BotData;
+use crate::metrics::METRICS;
+use anyhow::{Context, Result};
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serenity::all::Cache;
+use tracing::{error, info};
+
+#[derive(Clone)]
+
+struct MetricsState {
+	bot_data: Arc<BotData>,
+	cache: Arc<Cache>,
+}
+
+/// Starts the HTTP server of the `[metrics]` config section, the metrics are served on
+/// `/metrics` in the OpenMetrics text format.
+
+pub async fn metrics_server_launcher(bot_data: Arc<BotData>, cache: Arc<Cache>) -> Result<()> {
+	let bind_address = bot_data.config.metrics.bind_address.clone();
+
+	let address: SocketAddr = bind_address
+		.parse()
+		.context(format!("Invalid metrics bind address {}", bind_address))?;
+
+	let app = Router::new()
+		.route("/metrics", get(metrics))
+		.with_state(MetricsState { bot_data, cache });
+
+	let listener = tokio::net::TcpListener::bind(address)
+		.await
+		.context(format!("Failed to bind the metrics server to {}", address))?;
+
+	info!("Launching the metrics server on {}.", address);
+
+	axum::serve(listener, app)
+		.await
+		.context("The metrics server stopped")?;
+
+	Ok(())
+}
+
+async fn metrics(State(state): State<MetricsState>) -> Response {
+	// The gauges that are cheap to read are updated on each scrape.
+	METRICS.guilds.set(state.cache.guild_count() as i64);
+
+	METRICS
+		.voice_calls
+		.set(state.bot_data.manager.iter().count() as i64);
+
+	match METRICS.encode() {
+		Ok(body) => (
+			[(
+				CONTENT_TYPE,
+				"application/openmetrics-text; version=1.0.0; charset=utf-8",
+			)],
+			body,
+		)
+			.into_response(),
+		Err(e) => {
+			error!("Failed to encode the metrics. {}", e);
+
+			StatusCode::INTERNAL_SERVER_ERROR.into_response()
+		},
+	}
+}
@@ -248,7 +248,7 @@ pub async fn new_member_message(ctx: &SerenityContext, member: &Member) -> Resul
 		"Processing new member message for guild: {}",
 		member.guild_id
 	);
-	let db_config = ctx.data::<BotData>().config.db.clone();
+	let db_connection = ctx.data::<BotData>().db_connection.clone();
 	let guild_id = member.guild_id;
 
 	let guild_settings = load_guild_settings(guild_id).await;
@@ -270,7 +270,7 @@ pub async fn new_member_message(ctx: &SerenityContext, member: &Member) -> Resul
 
 	let (_, _, _, _, image_height) = overlay_image(&mut guild_image, avatar_image).await?;
 
-	let welcome_text = load_localization_new_member(guild_id.to_string(), db_config)
+	let welcome_text = load_localization_new_member(guild_id.to_string(), None, db_connection)
 		.await
 		.map(|local| local.welcome)
 		.unwrap_or_else(|_| "Welcome $user$".to_string())
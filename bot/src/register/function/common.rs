@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use serde_json::Value;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fs;
@@ -165,6 +166,12 @@ pub fn get_permission<'a>(
 	command_build
 }
 
+/// Serializes a command the way it is sent to Discord, so it can be compared with the
+/// registered one.
+pub fn command_to_json(command: CreateCommand, name: &str) -> Result<Value> {
+	serde_json::to_value(command).context(format!("Failed to serialize the command {}", name))
+}
+
 pub fn get_vec<T: serde::Deserialize<'static> + Clone>(path: &str) -> Result<Vec<T>> {
 	let mut commands: Vec<T> = Vec::new();
 
@@ -1,27 +1,22 @@
-use std::sync::Arc;
-
-use serenity::all::{CommandType, CreateCommand, Http};
-use tracing::{error, trace};
+use serde_json::Value;
+use serenity::all::{CommandType, CreateCommand};
+use tracing::trace;
 
 use crate::register::function::common::{
-	get_option, get_permission, get_vec, get_vec_installation_context, get_vec_integration_context,
+	command_to_json, get_option, get_permission, get_vec, get_vec_installation_context,
+	get_vec_integration_context,
 };
 use crate::register::structure::command::Command;
 use anyhow::Result;
 
-pub async fn creates_commands(http: &Arc<Http>) {
-	let commands = match get_commands("./json/command") {
-		Err(e) => {
-			error!("{:?}", e);
-
-			return;
-		},
-		Ok(c) => c,
-	};
+/// Builds the commands defined in `json/command`, serialized as they are sent to Discord.
+pub fn build_commands() -> Result<Vec<Value>> {
+	let commands = get_commands("./json/command")?;
 
-	for command in commands {
-		create_command(&command, http).await;
-	}
+	commands
+		.iter()
+		.map(|command| command_to_json(create_command(command), &command.name))
+		.collect()
 }
 
 pub fn get_commands(path: &str) -> Result<Vec<Command>> {
@@ -34,7 +29,7 @@ pub fn get_commands(path: &str) -> Result<Vec<Command>> {
 	Ok(commands)
 }
 
-async fn create_command(command: &Command, http: &Arc<Http>) {
+fn create_command(command: &Command) -> CreateCommand<'_> {
 	let mut command_build = CreateCommand::new(&command.name)
 		.nsfw(command.nsfw)
 		.kind(CommandType::ChatInput)
@@ -61,12 +56,5 @@ async fn create_command(command: &Command, http: &Arc<Http>) {
 		}
 	}
 
-	let e = http.create_global_command(&command_build).await;
-
-	match e {
-		Ok(_) => (),
-		Err(e) => {
-			error!("Failed to create command: {:?}", e);
-		},
-	}
+	command_build
 }
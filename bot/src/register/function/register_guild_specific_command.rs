@@ -1,23 +1,26 @@
-use crate::register::function::common::{get_option, get_permission, get_vec};
+use crate::register::function::common::{command_to_json, get_option, get_permission, get_vec};
 use crate::register::structure::guild_command::GuildCommand;
 use anyhow::Result;
-use serenity::all::{CommandType, CreateCommand, GuildId, Http};
-use std::sync::Arc;
-use tracing::{error, trace};
-
-pub async fn creates_guild_commands(http: &Arc<Http>) {
-	let commands = match get_commands("./json/guild_command") {
-		Err(e) => {
-			error!("{:?}", e);
-
-			return;
-		},
-		Ok(c) => c,
-	};
-
-	for command in commands {
-		create_command(&command, http).await;
-	}
+use serde_json::Value;
+use serenity::all::{CommandType, CreateCommand, GuildId};
+use tracing::trace;
+
+/// Builds the commands defined in `json/guild_command` with the guild they belong to,
+/// serialized as they are sent to Discord.
+pub fn build_guild_commands() -> Result<Vec<(GuildId, Value)>> {
+	let commands = get_commands("./json/guild_command")?;
+
+	commands
+		.iter()
+		.map(|command| {
+			let guild_id = GuildId::from(command.guild_id);
+
+			Ok((
+				guild_id,
+				command_to_json(create_command(command), &command.name)?,
+			))
+		})
+		.collect()
 }
 
 pub fn get_commands(path: &str) -> Result<Vec<GuildCommand>> {
@@ -30,7 +33,7 @@ pub fn get_commands(path: &str) -> Result<Vec<GuildCommand>> {
 	Ok(commands)
 }
 
-async fn create_command(command: &GuildCommand, http: &Arc<Http>) {
+fn create_command(command: &GuildCommand) -> CreateCommand<'_> {
 	let mut command_build = CreateCommand::new(&command.name)
 		.nsfw(command.nsfw)
 		.kind(CommandType::ChatInput)
@@ -55,14 +58,5 @@ async fn create_command(command: &GuildCommand, http: &Arc<Http>) {
 		}
 	}
 
-	let guild_id = GuildId::from(command.guild_id);
-
-	let e = http.create_guild_command(guild_id, &command_build).await;
-
-	match e {
-		Ok(_) => (),
-		Err(e) => {
-			error!("Failed to create command: {:?}", e);
-		},
-	}
+	command_build
 }
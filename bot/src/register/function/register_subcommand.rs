@@ -1,28 +1,22 @@
-use std::sync::Arc;
-
-use serenity::all::{CommandType, CreateCommand, Http};
-use tracing::{error, trace};
+use serde_json::Value;
+use serenity::all::{CommandType, CreateCommand};
+use tracing::trace;
 
 use crate::register::function::common::{
-	get_permission, get_subcommand_option, get_vec, get_vec_installation_context,
+	command_to_json, get_permission, get_subcommand_option, get_vec, get_vec_installation_context,
 	get_vec_integration_context,
 };
 use crate::register::structure::subcommand::SubCommand;
 use anyhow::Result;
 
-pub async fn creates_subcommands(http: &Arc<Http>) {
-	let commands = match get_subcommands("./json/subcommand") {
-		Err(e) => {
-			error!("{:?}", e);
-
-			return;
-		},
-		Ok(c) => c,
-	};
+/// Builds the commands defined in `json/subcommand`, serialized as they are sent to Discord.
+pub fn build_subcommands() -> Result<Vec<Value>> {
+	let commands = get_subcommands("./json/subcommand")?;
 
-	for command in commands {
-		create_command(&command, http).await;
-	}
+	commands
+		.iter()
+		.map(|command| command_to_json(create_command(command), &command.name))
+		.collect()
 }
 
 pub fn get_subcommands(path: &str) -> Result<Vec<SubCommand>> {
@@ -35,7 +29,7 @@ pub fn get_subcommands(path: &str) -> Result<Vec<SubCommand>> {
 	Ok(commands)
 }
 
-async fn create_command(command: &SubCommand, http: &Arc<Http>) {
+fn create_command(command: &SubCommand) -> CreateCommand<'_> {
 	let mut command_build = CreateCommand::new(&command.name)
 		.nsfw(command.nsfw)
 		.kind(CommandType::ChatInput)
@@ -54,12 +48,5 @@ async fn create_command(command: &SubCommand, http: &Arc<Http>) {
 		None => command_build,
 	};
 
-	let e = http.create_global_command(&command_build).await;
-
-	match e {
-		Ok(_) => (),
-		Err(e) => {
-			error!("Failed to create command: {:?}", e);
-		},
-	}
+	command_build
 }
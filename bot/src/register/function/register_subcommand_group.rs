@@ -1,28 +1,22 @@
-use std::sync::Arc;
-
-use serenity::all::{CommandType, CreateCommand, Http};
-use tracing::{error, trace};
+use serde_json::Value;
+use serenity::all::{CommandType, CreateCommand};
+use tracing::trace;
 
 use crate::register::function::common::{
-	get_permission, get_subcommand_group_option, get_subcommand_option, get_vec,
+	command_to_json, get_permission, get_subcommand_group_option, get_subcommand_option, get_vec,
 	get_vec_installation_context, get_vec_integration_context,
 };
 use crate::register::structure::subcommand_group::SubCommandGroup;
 use anyhow::Result;
 
-pub async fn creates_subcommands_group(http: &Arc<Http>) {
-	let commands = match get_subcommands_group("./json/subcommand_group") {
-		Err(e) => {
-			error!("{:?}", e);
-
-			return;
-		},
-		Ok(c) => c,
-	};
+/// Builds the commands defined in `json/subcommand_group`, serialized as they are sent to Discord.
+pub fn build_subcommands_group() -> Result<Vec<Value>> {
+	let commands = get_subcommands_group("./json/subcommand_group")?;
 
-	for command in commands {
-		create_command(&command, http).await;
-	}
+	commands
+		.iter()
+		.map(|command| command_to_json(create_command(command), &command.name))
+		.collect()
 }
 
 pub fn get_subcommands_group(path: &str) -> Result<Vec<SubCommandGroup>> {
@@ -35,7 +29,7 @@ pub fn get_subcommands_group(path: &str) -> Result<Vec<SubCommandGroup>> {
 	Ok(commands)
 }
 
-async fn create_command(command: &SubCommandGroup, http: &Arc<Http>) {
+fn create_command(command: &SubCommandGroup) -> CreateCommand<'_> {
 	let mut command_build = CreateCommand::new(&command.name)
 		.nsfw(command.nsfw)
 		.kind(CommandType::ChatInput)
@@ -63,12 +57,5 @@ async fn create_command(command: &SubCommandGroup, http: &Arc<Http>) {
 		None => command_build,
 	};
 
-	let e = http.create_global_command(&command_build).await;
-
-	match e {
-		Ok(_) => (),
-		Err(e) => {
-			error!("Failed to create command: {:?}", e);
-		},
-	}
+	command_build
 }
@@ -1,28 +1,24 @@
-use std::sync::Arc;
+use serde_json::Value;
+use serenity::all::{CommandType, CreateCommand};
+use tracing::trace;
 
-use serenity::all::{CommandType, CreateCommand, Http};
-use tracing::{error, trace};
-
-use crate::register::function::common::{get_permission, get_vec, get_vec_installation_context};
+use crate::register::function::common::{
+	command_to_json, get_permission, get_vec, get_vec_installation_context,
+};
 use crate::register::structure::user_command::UserCommand;
 use anyhow::Result;
 
-pub async fn creates_user_command(http: &Arc<Http>) {
-	let commands = match get_user_command("./json/user_command") {
-		Err(e) => {
-			error!("{:?}", e);
-
-			return;
-		},
-		Ok(c) => c,
-	};
+/// Builds the commands defined in `json/user_command`, serialized as they are sent to Discord.
+pub fn build_user_command() -> Result<Vec<Value>> {
+	let commands = get_user_command("./json/user_command")?;
 
-	for command in commands {
-		create_command(&command, http).await;
-	}
+	commands
+		.iter()
+		.map(|command| command_to_json(create_command(command), &command.name))
+		.collect()
 }
 
-fn get_user_command(path: &str) -> Result<Vec<UserCommand>> {
+pub fn get_user_command(path: &str) -> Result<Vec<UserCommand>> {
 	let commands: Vec<UserCommand> = get_vec(path)?;
 
 	if commands.is_empty() {
@@ -32,7 +28,7 @@ fn get_user_command(path: &str) -> Result<Vec<UserCommand>> {
 	Ok(commands)
 }
 
-async fn create_command(command: &UserCommand, http: &Arc<Http>) {
+fn create_command(command: &UserCommand) -> CreateCommand<'_> {
 	let mut command_build = CreateCommand::new(&command.name)
 		.kind(CommandType::User)
 		.name(&command.name)
@@ -46,12 +42,5 @@ async fn create_command(command: &UserCommand, http: &Arc<Http>) {
 
 	command_build = get_permission(&command.permissions, command_build);
 
-	let e = http.create_global_command(&command_build).await;
-
-	match e {
-		Ok(_) => (),
-		Err(e) => {
-			error!("Failed to create command: {:?}", e);
-		},
-	}
+	command_build
 }
@@ -1,137 +1,371 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 
-use crate::register::function::register_command::creates_commands;
-use crate::register::function::register_guild_specific_command::{
-	creates_guild_commands, get_commands,
-};
-use crate::register::function::register_message_command::creates_message_command;
-use crate::register::function::register_subcommand::creates_subcommands;
-use crate::register::function::register_subcommand_group::creates_subcommands_group;
-use crate::register::function::register_user_command::creates_user_command;
-use serenity::all::{Command, GuildId, Http};
-use tracing::{error, info, trace};
+use crate::register::function::register_command::build_commands;
+use crate::register::function::register_guild_specific_command::build_guild_commands;
+use crate::register::function::register_message_command::build_message_command;
+use crate::register::function::register_subcommand::build_subcommands;
+use crate::register::function::register_subcommand_group::build_subcommands_group;
+use crate::register::function::register_user_command::build_user_command;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use serenity::all::{Command, CommandId, GuildId, Http};
+use tracing::{debug, error, info};
+
+/// Fields of a command and of its options that are compared with the json definitions.
+/// The other fields (id, version, application id, ...) are set by Discord.
+const COMPARED_FIELDS: [&str; 19] = [
+	"type",
+	"name",
+	"name_localizations",
+	"description",
+	"description_localizations",
+	"options",
+	"default_member_permissions",
+	"nsfw",
+	"contexts",
+	"integration_types",
+	"required",
+	"autocomplete",
+	"choices",
+	"value",
+	"channel_types",
+	"min_value",
+	"max_value",
+	"min_length",
+	"max_length",
+];
+
+/// A change needed for the registered commands to match the json definitions.
+/// `guild_id` is `None` for a global command.
+
+pub enum CommandChange {
+	Create {
+		guild_id: Option<GuildId>,
+		command: Value,
+	},
+	Update {
+		guild_id: Option<GuildId>,
+		id: CommandId,
+		command: Value,
+	},
+	Delete {
+		guild_id: Option<GuildId>,
+		id: CommandId,
+		name: String,
+	},
+}
+
+impl fmt::Display for CommandChange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let (action, guild_id, name) = match self {
+			CommandChange::Create { guild_id, command } => {
+				("create", guild_id, command_name(command))
+			},
+			CommandChange::Update {
+				guild_id, command, ..
+			} => ("update", guild_id, command_name(command)),
+			CommandChange::Delete { guild_id, name, .. } => ("delete", guild_id, name.as_str()),
+		};
+
+		match guild_id {
+			Some(guild_id) => write!(f, "{} {} in the guild {}", action, name, guild_id),
+			None => write!(f, "{} {}", action, name),
+		}
+	}
+}
 
-pub async fn command_registration(http: &Arc<Http>, is_ok: bool) {
-	if is_ok {
-		delete_command(http).await;
+/// Compares the commands registered on Discord with the json definitions and only creates,
+/// updates or deletes the ones that changed. With `dry_run` nothing is sent to Discord.
+///
+/// Commands registered on Discord that are not in the json files are only deleted when
+/// `remove_old_commands` is set. Returns the changes, applied or not.
 
-		info!("Starting to create commands...");
+pub async fn command_registration(
+	http: &Arc<Http>, remove_old_commands: bool, dry_run: bool,
+) -> Result<Vec<CommandChange>> {
+	info!("Starting to register commands...");
 
-		let start = std::time::Instant::now();
+	let start = std::time::Instant::now();
 
-		creates_commands(http).await;
+	let mut global_commands = build_commands()?;
 
-		creates_subcommands(http).await;
+	global_commands.extend(build_subcommands()?);
 
-		creates_subcommands_group(http).await;
+	global_commands.extend(build_subcommands_group()?);
 
-		creates_user_command(http).await;
+	global_commands.extend(build_user_command()?);
 
-		creates_message_command(http).await;
+	global_commands.extend(build_message_command()?);
 
-		creates_guild_commands(http).await;
+	let mut guild_commands: HashMap<GuildId, Vec<Value>> = HashMap::new();
 
-		let duration = start.elapsed();
+	for (guild_id, command) in build_guild_commands()? {
+		guild_commands.entry(guild_id).or_default().push(command);
+	}
 
-		info!("Time taken to create commands: {:?}", duration);
+	let registered = http
+		.get_global_commands()
+		.await
+		.context("Failed to get the global commands")?;
+
+	let mut changes = diff_commands(
+		None,
+		global_commands,
+		commands_to_json(registered)?,
+		remove_old_commands,
+	);
+
+	for (guild_id, commands) in guild_commands {
+		let registered = http.get_guild_commands(guild_id).await.context(format!(
+			"Failed to get the commands of the guild {}",
+			guild_id
+		))?;
+
+		changes.extend(diff_commands(
+			Some(guild_id),
+			commands,
+			commands_to_json(registered)?,
+			remove_old_commands,
+		));
+	}
 
-		info!("Done creating commands")
-	} else {
-		info!("Starting to create commands...");
+	if !dry_run {
+		for change in &changes {
+			info!("Command registration: {}", change);
 
-		let start = std::time::Instant::now();
+			if let Err(e) = apply_change(http, change).await {
+				error!("Failed to {}. {:#}", change, e);
+			}
+		}
+	}
 
-		creates_commands(http).await;
+	info!(
+		"Done registering commands, {} changes in {:?}",
+		changes.len(),
+		start.elapsed()
+	);
 
-		creates_subcommands(http).await;
+	Ok(changes)
+}
 
-		creates_subcommands_group(http).await;
+async fn apply_change(http: &Arc<Http>, change: &CommandChange) -> Result<()> {
+	match change {
+		CommandChange::Create {
+			guild_id: None,
+			command,
+		} => {
+			http.create_global_command(command).await?;
+		},
+		CommandChange::Create {
+			guild_id: Some(guild_id),
+			command,
+		} => {
+			http.create_guild_command(*guild_id, command).await?;
+		},
+		CommandChange::Update {
+			guild_id: None,
+			id,
+			command,
+		} => {
+			http.edit_global_command(*id, command).await?;
+		},
+		CommandChange::Update {
+			guild_id: Some(guild_id),
+			id,
+			command,
+		} => {
+			http.edit_guild_command(*guild_id, *id, command).await?;
+		},
+		CommandChange::Delete {
+			guild_id: None, id, ..
+		} => {
+			http.delete_global_command(*id).await?;
+		},
+		CommandChange::Delete {
+			guild_id: Some(guild_id),
+			id,
+			..
+		} => {
+			http.delete_guild_command(*guild_id, *id).await?;
+		},
+	}
 
-		creates_user_command(http).await;
+	Ok(())
+}
 
-		creates_message_command(http).await;
+fn commands_to_json(commands: Vec<Command>) -> Result<Vec<(CommandId, Value)>> {
+	commands
+		.into_iter()
+		.map(|command| {
+			let json = serde_json::to_value(&command)
+				.context(format!("Failed to serialize the command {}", command.name))?;
 
-		creates_guild_commands(http).await;
+			Ok((command.id, json))
+		})
+		.collect()
+}
 
-		let duration = start.elapsed();
+/// Commands are matched by name and type, a user command can have the name of a slash command.
+
+fn diff_commands(
+	guild_id: Option<GuildId>, commands: Vec<Value>, registered: Vec<(CommandId, Value)>,
+	remove_old_commands: bool,
+) -> Vec<CommandChange> {
+	let mut registered: HashMap<(String, u64), (CommandId, Value)> = registered
+		.into_iter()
+		.map(|(id, command)| (command_key(&command), (id, command)))
+		.collect();
+
+	let mut changes = Vec::new();
+
+	for command in commands {
+		match registered.remove(&command_key(&command)) {
+			None => changes.push(CommandChange::Create { guild_id, command }),
+			Some((id, registered)) => {
+				if !is_up_to_date(&command, &registered) {
+					changes.push(CommandChange::Update {
+						guild_id,
+						id,
+						command,
+					})
+				}
+			},
+		}
+	}
 
-		info!("Time taken to create commands: {:?}", duration);
+	for (_, (id, command)) in registered {
+		let name = command_name(&command).to_string();
 
-		info!("Done creating commands")
+		if remove_old_commands {
+			changes.push(CommandChange::Delete { guild_id, id, name });
+		} else {
+			debug!("The command {} is not in the json files, it is kept.", name);
+		}
 	}
+
+	changes
 }
 
-async fn delete_command(http: &Arc<Http>) {
-	info!("Started deleting command");
+fn command_key(command: &Value) -> (String, u64) {
+	// Discord uses 1 (chat input) when the type is not set.
+	let kind = command["type"].as_u64().unwrap_or(1);
 
-	let cmds = match Command::get_global_commands(http).await {
-		Ok(res) => res,
-		Err(e) => {
-			error!("could not get the command: {:#?}", e);
+	(command_name(command).to_string(), kind)
+}
 
-			return;
-		},
-	};
+fn command_name(command: &Value) -> &str {
+	command["name"].as_str().unwrap_or_default()
+}
 
-	for cmd in cmds {
-		trace!("Removing {:?}", cmd.name);
+fn is_up_to_date(command: &Value, registered: &Value) -> bool {
+	let command = normalize(command);
 
-		match Command::delete_global_command(http, cmd.id).await {
-			Ok(res) => res,
-			Err(e) => {
-				error!("{} for command {}", e, cmd.name);
+	let mut registered = normalize(registered);
 
-				return;
-			},
-		};
+	// Discord fills the contexts when they are not given, they are only compared when the
+	// json definition sets them.
+	if let (Value::Object(command), Value::Object(registered)) = (&command, &mut registered) {
+		for field in ["contexts", "integration_types"] {
+			if !command.contains_key(field) {
+				registered.remove(field);
+			}
+		}
+	}
+
+	command == registered
+}
+
+/// Keeps the compared fields and removes the empty and default values, Discord omits some of
+/// them while serenity sends them and the other way around.
+
+fn normalize(value: &Value) -> Value {
+	match value {
+		Value::Object(map) => Value::Object(
+			map.iter()
+				.filter(|(key, _)| COMPARED_FIELDS.contains(&key.as_str()))
+				.map(|(key, value)| {
+					let value = match (key.as_str(), value) {
+						// The permissions are a string in the API but may be serialized as a number.
+						("default_member_permissions", Value::Number(number)) => {
+							Value::String(number.to_string())
+						},
+						_ => normalize(value),
+					};
+
+					(key.clone(), value)
+				})
+				.filter(|(_, value)| !is_default(value))
+				.collect(),
+		),
+		// Arrays of numbers (contexts, channel types, ...) are sets, their order does not matter.
+		Value::Array(values) if values.iter().all(Value::is_number) => {
+			let mut values: Vec<Value> = values.to_vec();
+
+			values.sort_by_key(|value| value.as_i64());
+
+			Value::Array(values)
+		},
+		Value::Array(values) => Value::Array(values.iter().map(normalize).collect()),
+		value => value.clone(),
 	}
+}
 
-	let mut guild_ids = Vec::new();
+fn is_default(value: &Value) -> bool {
+	match value {
+		Value::Null | Value::Bool(false) => true,
+		Value::String(string) => string.is_empty(),
+		Value::Array(values) => values.is_empty(),
+		Value::Object(map) => map.is_empty(),
+		_ => false,
+	}
+}
 
-	let mut already_done = Vec::new();
+#[cfg(test)]
 
-	let commands = match get_commands("./json/guild_command") {
-		Err(e) => {
-			error!("{:?}", e);
+mod tests {
+	use super::*;
+	use serde_json::json;
 
-			return;
-		},
-		Ok(c) => c,
-	};
+	#[test]
 
-	for cmd in commands {
-		if !already_done.contains(&cmd.guild_id) {
-			guild_ids.push(cmd.guild_id);
+	fn test_diff_commands() {
+		let commands = vec![
+			json!({"type": 1, "name": "anime", "description": "Anime info", "nsfw": false,
+				"options": [{"type": 3, "name": "name", "description": "Name", "required": true}]}),
+			json!({"type": 1, "name": "manga", "description": "Manga info"}),
+			json!({"type": 2, "name": "avatar", "description": ""}),
+		];
 
-			already_done.push(cmd.guild_id);
-		}
-	}
+		let registered = vec![
+			(
+				CommandId::new(1),
+				json!({"id": "1", "version": "3", "type": 1, "name": "anime",
+					"description": "Anime info", "name_localizations": null, "contexts": [2, 0, 1],
+					"options": [{"type": 3, "name": "name", "description": "Name", "required": true,
+						"autocomplete": false}]}),
+			),
+			(
+				CommandId::new(2),
+				json!({"id": "2", "type": 1, "name": "manga", "description": "Old description"}),
+			),
+			(
+				CommandId::new(3),
+				json!({"id": "3", "type": 1, "name": "removed", "description": "Removed"}),
+			),
+		];
 
-	for guild_id in guild_ids {
-		let guild_id = GuildId::from(guild_id);
+		let changes = diff_commands(None, commands.clone(), registered.clone(), false);
 
-		let cmds = match http.get_guild_commands(guild_id).await {
-			Ok(res) => res,
-			Err(e) => {
-				error!("could not get the command: {:#?}", e);
+		let changes: Vec<String> = changes.iter().map(ToString::to_string).collect();
 
-				return;
-			},
-		};
+		assert_eq!(changes, vec!["update manga", "create avatar"]);
 
-		for cmd in cmds {
-			trace!("Removing {:?}", cmd);
+		let changes = diff_commands(Some(GuildId::new(4)), commands, registered, true);
 
-			match http.delete_guild_command(guild_id, cmd.id).await {
-				Ok(res) => res,
-				Err(e) => {
-					error!("{} for command {}", e, cmd.id);
+		assert_eq!(changes.len(), 3);
 
-					return;
-				},
-			};
-		}
+		assert_eq!(changes[2].to_string(), "delete removed in the guild 4");
 	}
-
-	info!("Done deleting command")
 }
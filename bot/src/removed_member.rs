@@ -19,7 +19,7 @@ pub async fn removed_member_message(
 	ctx: &SerenityContext, guild_id: GuildId, user: User,
 ) -> Result<()> {
 	info!("Processing removed member message for guild: {}", guild_id);
-	let db_config = ctx.data::<BotData>().config.db.clone();
+	let db_connection = ctx.data::<BotData>().db_connection.clone();
 	let guild_settings = load_guild_settings(guild_id).await;
 
 	debug!(?guild_settings, "Loaded guild settings");
@@ -59,7 +59,7 @@ pub async fn removed_member_message(
 		.await
 		.context("Failed to fetch audit logs")?;
 
-	let local = load_localization_removed_member(guild_id.to_string(), db_config)
+	let local = load_localization_removed_member(guild_id.to_string(), None, db_connection)
 		.await
 		.context("Failed to load localization for removed member")?;
 
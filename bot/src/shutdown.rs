@@ -0,0 +1,149 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::gateway::ShardManager;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+use crate::constant::SHUTDOWN_TIMEOUT;
+use crate::event_handler::BotData;
+
+/// Counts the running work (interactions, activity sends) and refuses new work once the bot
+/// is shutting down.
+#[derive(Default)]
+
+pub struct ShutdownState {
+	stopping: AtomicBool,
+	running: AtomicUsize,
+	idle: Notify,
+}
+
+/// Running work, the work is done when the guard is dropped.
+
+pub struct WorkGuard(Arc<ShutdownState>);
+
+impl ShutdownState {
+	/// Registers a new work, `None` once the bot is shutting down.
+
+	pub fn start_work(self: &Arc<Self>) -> Option<WorkGuard> {
+		// The counter is incremented before the check so `wait_idle` can't miss the work.
+		self.running.fetch_add(1, Ordering::SeqCst);
+
+		let guard = WorkGuard(self.clone());
+
+		if self.is_stopping() {
+			return None;
+		}
+
+		Some(guard)
+	}
+
+	pub fn is_stopping(&self) -> bool {
+		self.stopping.load(Ordering::SeqCst)
+	}
+
+	pub fn stop(&self) {
+		self.stopping.store(true, Ordering::SeqCst);
+	}
+
+	/// Waits until every running work is done or the timeout is reached. Returns the number of
+	/// work still running.
+
+	pub async fn wait_idle(&self, timeout: Duration) -> usize {
+		let wait = async {
+			loop {
+				let idle = self.idle.notified();
+
+				tokio::pin!(idle);
+
+				// Registers the waiter before the check, a guard dropped in between wakes it.
+				idle.as_mut().enable();
+
+				if self.running.load(Ordering::SeqCst) == 0 {
+					return;
+				}
+
+				idle.await;
+			}
+		};
+
+		let _ = tokio::time::timeout(timeout, wait).await;
+
+		self.running.load(Ordering::SeqCst)
+	}
+}
+
+impl Drop for WorkGuard {
+	fn drop(&mut self) {
+		if self.0.running.fetch_sub(1, Ordering::SeqCst) == 1 {
+			self.0.idle.notify_waiters();
+		}
+	}
+}
+
+/// Stops the bot without losing work: new interactions are refused, the running commands and
+/// activity sends get `SHUTDOWN_TIMEOUT` seconds to finish and the voice calls are left. The
+/// activity sends that did not start yet stay in the database, the next start catches up on them.
+
+pub async fn shutdown(bot_data: Arc<BotData>, shard_manager: Arc<ShardManager>) {
+	info!("Received bot shutdown signal. Shutting down bot.");
+
+	bot_data.shutdown.stop();
+
+	let still_running = bot_data
+		.shutdown
+		.wait_idle(Duration::from_secs(SHUTDOWN_TIMEOUT))
+		.await;
+
+	if still_running != 0 {
+		warn!(
+			"{} interactions or activity sends did not finish in {}s.",
+			still_running, SHUTDOWN_TIMEOUT
+		);
+	}
+
+	let calls: Vec<_> = bot_data
+		.manager
+		.iter()
+		.map(|(guild_id, _)| guild_id)
+		.collect();
+
+	for guild_id in calls {
+		if let Err(e) = bot_data.manager.remove(guild_id).await {
+			error!("Failed to leave the voice channel of {:?}. {}", guild_id, e);
+		}
+	}
+
+	ShardManager::shutdown_all(&shard_manager).await;
+
+	info!("Bot shut down.");
+}
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+
+	async fn test_wait_idle() {
+		let state = Arc::new(ShutdownState::default());
+
+		let guard = state.start_work().unwrap();
+
+		state.stop();
+
+		assert!(state.start_work().is_none());
+
+		assert_eq!(state.wait_idle(Duration::from_millis(10)).await, 1);
+
+		tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+
+			drop(guard);
+		});
+
+		assert_eq!(state.wait_idle(Duration::from_secs(5)).await, 0);
+	}
+}
@@ -1,15 +1,13 @@
 use std::sync::Arc;
 
 use cynic::{GraphQlResponse, QueryBuilder};
-use moka::future::Cache;
 use serenity::all::{
 	AutocompleteChoice, CommandInteraction, Context as SerenityContext, CreateAutocompleteResponse,
 	CreateInteractionResponse,
 };
-use tokio::sync::RwLock;
 
 use crate::constant::DEFAULT_STRING;
-use crate::helper::make_graphql_cached::make_request_anilist;
+use crate::helper::make_graphql_cached::{make_request_anilist, AnilistClient};
 
 #[cynic::schema("anilist")]
 
@@ -79,12 +77,12 @@ pub enum MediaType {
 
 pub async fn send_auto_complete(
 	ctx: &SerenityContext, autocomplete_interaction: CommandInteraction,
-	media: MediaAutocompleteVariables<'_>, anilist_cache: Arc<RwLock<Cache<String, String>>>,
+	media: MediaAutocompleteVariables<'_>, anilist_client: Arc<AnilistClient>,
 ) {
 	let operation = MediaAutocomplete::build(media);
 
 	let data: GraphQlResponse<MediaAutocomplete> =
-		match make_request_anilist(operation, false, anilist_cache).await {
+		match make_request_anilist(operation, false, anilist_client).await {
 			Ok(data) => data,
 			Err(e) => {
 				tracing::error!(?e);
@@ -1,9 +1,10 @@
 // Importing necessary libraries and modules
 use anyhow::Result;
 
-use crate::config::DbConfig;
 use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 
@@ -15,9 +16,9 @@ pub struct AddActivityLocalised {
 }
 
 pub async fn load_localization_add_activity(
-	guild_id: String, db_config: DbConfig,
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
 ) -> Result<AddActivityLocalised> {
 	let path = "json/message/admin/anilist/add_activity.json";
 
-	load_localization(guild_id, path, db_config).await
+	load_localization(guild_id, locale, path, db_connection).await
 }
@@ -0,0 +1,24 @@
+use anyhow::Result;
+
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct AddMangaActivityLocalised {
+	pub success: String,
+	pub fail: String,
+	pub fail_desc: String,
+	pub success_desc: String,
+	pub series: String,
+}
+
+pub async fn load_localization_add_manga_activity(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<AddMangaActivityLocalised> {
+	let path = "json/message/admin/anilist/add_manga_activity.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct FollowUserLocalised {
+	pub success: String,
+	pub success_desc: String,
+	pub fail: String,
+	pub fail_desc: String,
+}
+
+pub async fn load_localization_follow_user(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<FollowUserLocalised> {
+	let path = "json/message/admin/anilist/follow_user.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct UnfollowUserLocalised {
+	pub success: String,
+	pub success_desc: String,
+}
+
+pub async fn load_localization_unfollow_user(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<UnfollowUserLocalised> {
+	let path = "json/message/admin/anilist/unfollow_user.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -1,6 +1,7 @@
-use crate::config::DbConfig;
 use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 
@@ -12,9 +13,9 @@ pub struct LangLocalised {
 use anyhow::Result;
 
 pub async fn load_localization_lang(
-	guild_id: String, db_config: DbConfig,
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
 ) -> Result<LangLocalised> {
 	let path = "json/message/admin/server/lang.json";
 
-	load_localization(guild_id, path, db_config).await
+	load_localization(guild_id, locale, path, db_connection).await
 }
@@ -4,8 +4,12 @@ pub mod level;
 pub mod media;
 pub mod random;
 pub mod register;
+pub mod schedule;
+pub mod season;
 pub mod seiyuu;
 pub mod send_activity;
+pub mod send_manga_activity;
+pub mod send_user_activity;
 pub mod staff;
 pub mod studio;
 pub mod user;
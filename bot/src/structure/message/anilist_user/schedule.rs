@@ -0,0 +1,31 @@
+use anyhow::Result;
+
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct ScheduleLocalised {
+	pub title: String,
+	pub desc: String,
+	pub empty: String,
+	pub time: String,
+	pub episode: String,
+	pub anime: String,
+	pub more: String,
+	pub next_airing: String,
+	pub next_airing_desc: String,
+	pub day: String,
+	pub previous: String,
+	pub next: String,
+}
+
+pub async fn load_localization_schedule(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<ScheduleLocalised> {
+	let path = "json/message/anilist_user/schedule.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct SeasonLocalised {
+	pub title: String,
+	pub empty: String,
+	pub score: String,
+	pub episodes: String,
+	pub page: String,
+	pub select: String,
+	pub previous: String,
+	pub next: String,
+	pub winter: String,
+	pub spring: String,
+	pub summer: String,
+	pub fall: String,
+}
+
+pub async fn load_localization_season(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<SeasonLocalised> {
+	let path = "json/message/anilist_user/season.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
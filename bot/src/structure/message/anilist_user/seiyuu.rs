@@ -1,5 +1,6 @@
-use crate::config::DbConfig;
+use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 // Importing necessary libraries and modules
 use crate::structure::message::common::load_localization;
@@ -13,9 +14,9 @@ pub struct SeiyuuLocalised {
 use anyhow::Result;
 
 pub async fn load_localization_seiyuu(
-	guild_id: String, db_config: DbConfig,
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
 ) -> Result<SeiyuuLocalised> {
 	let path = "json/message/anilist_user/seiyuu.json";
 
-	load_localization(guild_id, path, db_config).await
+	load_localization(guild_id, locale, path, db_connection).await
 }
@@ -0,0 +1,23 @@
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::structure::message::common::load_localization;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct SendMangaActivityLocalised {
+	pub title: String,
+	pub desc: String,
+	pub group: String,
+}
+
+use anyhow::Result;
+
+pub async fn load_localization_send_manga_activity(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<SendMangaActivityLocalised> {
+	let path = "json/message/anilist_user/send_manga_activity.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct SendUserActivityLocalised {
+	pub title: String,
+	pub desc: String,
+	pub progress_desc: String,
+}
+
+pub async fn load_localization_send_user_activity(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<SendUserActivityLocalised> {
+	let path = "json/message/anilist_user/send_user_activity.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -6,15 +6,16 @@ pub struct PlayLocalised {
 	pub now_playing: String,
 }
 
-use crate::config::DbConfig;
 use crate::structure::message::common::load_localization;
 use anyhow::Result;
+use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 pub async fn load_localization_play_localised(
-	guild_id: String, db_config: DbConfig,
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
 ) -> Result<PlayLocalised> {
 	let path = "json/message/audio/play.json";
 
-	load_localization(guild_id, path, db_config).await
+	load_localization(guild_id, locale, path, db_connection).await
 }
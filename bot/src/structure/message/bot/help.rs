@@ -0,0 +1,22 @@
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct HelpLocalised {
+	pub title: String,
+	pub general: String,
+	pub disabled: String,
+}
+
+use anyhow::Result;
+
+pub async fn load_localization_help(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<HelpLocalised> {
+	let path = "json/message/bot/help.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
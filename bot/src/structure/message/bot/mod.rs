@@ -1,3 +1,5 @@
 pub mod credit;
+pub mod help;
 pub mod info;
 pub mod ping;
+pub mod tasks;
@@ -0,0 +1,26 @@
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+
+pub struct TasksLocalised {
+	pub title: String,
+	pub running: String,
+	pub idle: String,
+	pub never: String,
+	pub last_run: String,
+	pub interval: String,
+	pub error: String,
+}
+
+use anyhow::Result;
+
+pub async fn load_localization_tasks(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<TasksLocalised> {
+	let path = "json/message/bot/tasks.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -1,28 +1,187 @@
-use crate::config::DbConfig;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
 use crate::helper::get_guild_lang::get_guild_language;
 use crate::helper::read_file::read_file_as_string;
-use anyhow::{Context, Result};
-use std::collections::HashMap;
+use crate::structure::message::localised::{Localised, LocalisedFile, LOCALISED};
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
+use serde_json::{Map, Value};
+use tracing::{info, warn};
+
+/// Every localization file, by path then by language, already deserialized into the struct
+/// of its loader.
+
+pub type LocalizationStore = HashMap<&'static str, HashMap<String, Localised>>;
+
+/// Empty until `reload_localization` is called on startup.
+
+static LOCALIZATION: Lazy<RwLock<Arc<LocalizationStore>>> = Lazy::new(Default::default);
+
+/// Loads and validates every localization file, replacing the ones in memory.
+/// On error the files in memory are kept. Returns the number of loaded files.
+
+pub fn reload_localization() -> Result<usize> {
+	let store = load_localization_store()?;
+
+	let count = store.len();
+
+	*LOCALIZATION
+		.write()
+		.map_err(|_| anyhow!("The localization lock is poisoned"))? = Arc::new(store);
+
+	info!("Loaded {} localization files.", count);
+
+	Ok(count)
+}
+
+/// Reads every file of `LOCALISED`, checks that each of them has an English version and
+/// deserializes every language. All the problems are reported at once.
+
+pub fn load_localization_store() -> Result<LocalizationStore> {
+	let mut store = HashMap::new();
+
+	let mut problems = Vec::new();
+
+	for file in LOCALISED {
+		match parse_localization_file(file) {
+			Ok((languages, file_problems)) => {
+				problems.extend(file_problems);
+
+				store.insert(file.path, languages);
+			},
+			Err(e) => problems.push(format!("{}: {:#}", file.path, e)),
+		}
+	}
+
+	if !problems.is_empty() {
+		return Err(anyhow!(
+			"Invalid localization files:\n{}",
+			problems.join("\n")
+		));
+	}
+
+	Ok(store)
+}
+
+fn parse_localization_file(
+	file: &LocalisedFile,
+) -> Result<(HashMap<String, Localised>, Vec<String>)> {
+	let content = read_file_as_string(file.path)?;
+
+	let json: HashMap<String, Map<String, Value>> =
+		serde_json::from_str(&content).context("Failed to parse JSON data")?;
+
+	let languages: HashMap<String, Map<String, Value>> = json
+		.into_iter()
+		.map(|(lang, value)| (normalize_lang(&lang).to_string(), value))
+		.collect();
+
+	let english = languages
+		.get("en")
+		.context("Missing the English localization")?;
 
-pub async fn load_localization<'a, T: serde::Deserialize<'a> + Clone>(
-	guild_id: String, path: &str, db_config: DbConfig,
+	for (lang, value) in &languages {
+		for key in value.keys() {
+			if !english.contains_key(key) {
+				warn!("{}: {} has the unused key {}", file.path, lang, key);
+			}
+		}
+	}
+
+	let mut localised = HashMap::new();
+
+	let mut problems = Vec::new();
+
+	for (lang, value) in languages {
+		match (file.parse)(Value::Object(value)) {
+			Ok(value) => {
+				localised.insert(lang, value);
+			},
+			Err(e) => problems.push(format!("{}: {}: {}", file.path, lang, e)),
+		}
+	}
+
+	Ok((localised, problems))
+}
+
+/// Maps a Discord locale (`en-US`, `ja`, ...) or a guild language to the key used in the
+/// json files.
+
+pub fn normalize_lang(lang: &str) -> &str {
+	match lang.split('-').next().unwrap_or(lang) {
+		"ja" => "jp",
+		lang => lang,
+	}
+}
+
+/// Returns the localization of the file at `path`, in the language of the user when it is
+/// available, then in the language of the guild, then in English.
+
+pub async fn load_localization<T: Clone + 'static>(
+	guild_id: String, locale: Option<&str>, path: &str, db_connection: Arc<DatabaseConnection>,
 ) -> Result<T> {
-	let json_content = read_file_as_string(path)?;
+	let store = LOCALIZATION
+		.read()
+		.map_err(|_| anyhow!("The localization lock is poisoned"))?
+		.clone();
 
-	let json: &'a str = Box::leak(json_content.into_boxed_str());
+	let languages = store
+		.get(path)
+		.context(format!("Unknown localization file {}", path))?;
 
-	// Parse the JSON data into a HashMap and handle any potential errors
-	let json_data: HashMap<String, T> =
-		serde_json::from_str(json).context("Failed to parse JSON data")?;
+	let get = |lang: &str| -> Option<T> {
+		languages
+			.get(normalize_lang(lang))?
+			.downcast_ref::<T>()
+			.cloned()
+	};
+
+	if let Some(localised) = locale.and_then(get) {
+		return Ok(localised);
+	}
 
 	// Get the language choice for the guild
-	let lang_choice = get_guild_language(guild_id, db_config).await;
+	let lang_choice = get_guild_language(guild_id, db_connection).await;
 
-	// Retrieve the localized data for the add activity based on the language choice
-	Ok(json_data.get(lang_choice.as_str()).cloned().unwrap_or(
-		json_data
-			.get("en")
-			.cloned()
-			.context("Failed to get English localization")?,
+	if let Some(localised) = get(&lang_choice) {
+		return Ok(localised);
+	}
+
+	get("en").context(format!(
+		"{} is not deserialized into {}",
+		path,
+		std::any::type_name::<T>()
 	))
 }
+
+#[cfg(test)]
+
+mod tests {
+	use super::*;
+
+	#[test]
+
+	fn test_localization_files_are_valid() {
+		let store = load_localization_store().unwrap();
+
+		let english = &store["json/message/error/error.json"]["en"];
+
+		assert!(english
+			.downcast_ref::<crate::structure::message::error::ErrorLocalised>()
+			.is_some());
+	}
+
+	#[test]
+
+	fn test_normalize_lang() {
+		assert_eq!(normalize_lang("en-US"), "en");
+
+		assert_eq!(normalize_lang("ja"), "jp");
+
+		assert_eq!(normalize_lang("jp"), "jp");
+
+		assert_eq!(normalize_lang("fr"), "fr");
+	}
+}
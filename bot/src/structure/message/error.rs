@@ -0,0 +1,28 @@
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+
+pub struct ErrorLocalised {
+	pub title: String,
+	pub user_input: String,
+	pub not_found: String,
+	pub upstream_api: String,
+	pub permission: String,
+	pub premium_limit: String,
+	pub internal: String,
+	pub code: String,
+	pub reference: String,
+}
+
+use anyhow::Result;
+
+pub async fn load_localization_error(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<ErrorLocalised> {
+	let path = "json/message/error/error.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
@@ -0,0 +1,160 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::structure::message;
+
+/// One language of a localization file, deserialized into the struct of its loader.
+
+pub type Localised = Arc<dyn Any + Send + Sync>;
+
+/// A localization file and how its languages are deserialized.
+
+pub struct LocalisedFile {
+	pub path: &'static str,
+	pub parse: fn(Value) -> serde_json::Result<Localised>,
+}
+
+const fn localised<T: DeserializeOwned + Send + Sync + 'static>(
+	path: &'static str,
+) -> LocalisedFile {
+	LocalisedFile {
+		path,
+		parse: parse::<T>,
+	}
+}
+
+fn parse<T: DeserializeOwned + Send + Sync + 'static>(
+	value: Value,
+) -> serde_json::Result<Localised> {
+	Ok(Arc::new(serde_json::from_value::<T>(value)?))
+}
+
+/// Every localization file with the struct it is deserialized into by its loader.
+/// A new loader must be added here, `kasuki check` reports the files that are not.
+
+pub const LOCALISED: &[LocalisedFile] = &[
+	localised::<message::admin::anilist::add_activity::AddActivityLocalised>(
+		"json/message/admin/anilist/add_activity.json",
+	),
+	localised::<message::admin::anilist::add_manga_activity::AddMangaActivityLocalised>(
+		"json/message/admin/anilist/add_manga_activity.json",
+	),
+	localised::<message::admin::anilist::delete_activity::DeleteActivityLocalised>(
+		"json/message/admin/anilist/delete_activity.json",
+	),
+	localised::<message::admin::anilist::follow_user::FollowUserLocalised>(
+		"json/message/admin/anilist/follow_user.json",
+	),
+	localised::<message::admin::anilist::unfollow_user::UnfollowUserLocalised>(
+		"json/message/admin/anilist/unfollow_user.json",
+	),
+	localised::<message::admin::server::lang::LangLocalised>("json/message/admin/server/lang.json"),
+	localised::<message::admin::server::module::ModuleLocalised>(
+		"json/message/admin/server/module.json",
+	),
+	localised::<message::admin::server::new_member_setting::NewMemberSettingLocalised>(
+		"json/message/admin/server/new_member_setting.json",
+	),
+	localised::<message::ai::image::ImageLocalised>("json/message/ai/image.json"),
+	localised::<message::ai::transcript::TranscriptLocalised>("json/message/ai/transcript.json"),
+	localised::<message::ai::translation::TranslationLocalised>("json/message/ai/translation.json"),
+	localised::<message::anilist_server::list_all_activity::ListActivityLocalised>(
+		"json/message/anilist_server/list_all_activity.json",
+	),
+	localised::<message::anilist_server::list_register_user::ListUserLocalised>(
+		"json/message/anilist_server/list_register_user.json",
+	),
+	localised::<message::anilist_user::character::CharacterLocalised>(
+		"json/message/anilist_user/character.json",
+	),
+	localised::<message::anilist_user::compare::CompareLocalised>(
+		"json/message/anilist_user/compare.json",
+	),
+	localised::<message::anilist_user::level::LevelLocalised>(
+		"json/message/anilist_user/level.json",
+	),
+	localised::<message::anilist_user::media::MediaLocalised>(
+		"json/message/anilist_user/media.json",
+	),
+	localised::<message::anilist_user::random::RandomLocalised>(
+		"json/message/anilist_user/random.json",
+	),
+	localised::<message::anilist_user::register::RegisterLocalised>(
+		"json/message/anilist_user/register.json",
+	),
+	localised::<message::anilist_user::schedule::ScheduleLocalised>(
+		"json/message/anilist_user/schedule.json",
+	),
+	localised::<message::anilist_user::season::SeasonLocalised>(
+		"json/message/anilist_user/season.json",
+	),
+	localised::<message::anilist_user::seiyuu::SeiyuuLocalised>(
+		"json/message/anilist_user/seiyuu.json",
+	),
+	localised::<message::anilist_user::send_activity::SendActivityLocalised>(
+		"json/message/anilist_user/send_activity.json",
+	),
+	localised::<message::anilist_user::send_manga_activity::SendMangaActivityLocalised>(
+		"json/message/anilist_user/send_manga_activity.json",
+	),
+	localised::<message::anilist_user::send_user_activity::SendUserActivityLocalised>(
+		"json/message/anilist_user/send_user_activity.json",
+	),
+	localised::<message::anilist_user::staff::StaffLocalised>(
+		"json/message/anilist_user/staff.json",
+	),
+	localised::<message::anilist_user::studio::StudioLocalised>(
+		"json/message/anilist_user/studio.json",
+	),
+	localised::<message::anilist_user::user::UserLocalised>("json/message/anilist_user/user.json"),
+	localised::<message::anime::random_image::RandomImageLocalised>(
+		"json/message/anime/random_image.json",
+	),
+	localised::<message::anime_nsfw::random_image_nsfw::RandomImageNSFWLocalised>(
+		"json/message/anime_nsfw/random_image_nsfw.json",
+	),
+	localised::<message::audio::join::JoinLocalised>("json/message/audio/join.json"),
+	localised::<message::audio::play::PlayLocalised>("json/message/audio/play.json"),
+	localised::<message::bot::credit::CreditLocalised>("json/message/bot/credit.json"),
+	localised::<message::bot::help::HelpLocalised>("json/message/bot/help.json"),
+	localised::<message::bot::info::InfoLocalised>("json/message/bot/info.json"),
+	localised::<message::bot::ping::PingLocalised>("json/message/bot/ping.json"),
+	localised::<message::bot::tasks::TasksLocalised>("json/message/bot/tasks.json"),
+	localised::<message::error::ErrorLocalised>("json/message/error/error.json"),
+	localised::<message::game::steam_game_info::SteamGameInfoLocalised>(
+		"json/message/game/steam_game_info.json",
+	),
+	localised::<message::management::give_premium_sub::GivePremiumLocalised>(
+		"json/message/management/give_premium_sub.json",
+	),
+	localised::<message::management::kill_switch::KillSwitchLocalised>(
+		"json/message/management/kill_switch.json",
+	),
+	localised::<message::management::remove_test_sub::RemoveTestLocalised>(
+		"json/message/management/remove_test_sub.json",
+	),
+	localised::<message::module_disabled::ModuleDisabledLocalised>(
+		"json/message/module_disabled.json",
+	),
+	localised::<message::new_member::NewMember>("json/message/new_member.json"),
+	localised::<message::removed_member::RemovedMember>("json/message/removed_member.json"),
+	localised::<message::server::generate_image_pfp_server::PFPServerLocalisedImage>(
+		"json/message/server/generate_image_pfp_server.json",
+	),
+	localised::<message::server::guild::GuildLocalised>("json/message/server/guild.json"),
+	localised::<message::user::avatar::AvatarLocalised>("json/message/user/avatar.json"),
+	localised::<message::user::banner::BannerLocalised>("json/message/user/banner.json"),
+	localised::<message::user::command_usage::CommandUsageLocalised>(
+		"json/message/user/command_usage.json",
+	),
+	localised::<message::user::profile::ProfileLocalised>("json/message/user/profile.json"),
+	localised::<message::vn::character::CharacterLocalised>("json/message/vn/character.json"),
+	localised::<message::vn::game::GameLocalised>("json/message/vn/game.json"),
+	localised::<message::vn::producer::ProducerLocalised>("json/message/vn/producer.json"),
+	localised::<message::vn::staff::StaffLocalised>("json/message/vn/staff.json"),
+	localised::<message::vn::stats::StatsLocalised>("json/message/vn/stats.json"),
+	localised::<message::vn::user::UserLocalised>("json/message/vn/user.json"),
+];
@@ -7,8 +7,11 @@ pub mod anime_nsfw;
 pub mod audio;
 pub mod bot;
 pub mod common;
+pub mod error;
 pub mod game;
+pub mod localised;
 pub mod management;
+pub mod module_disabled;
 pub mod new_member;
 pub mod removed_member;
 pub mod server;
@@ -0,0 +1,22 @@
+use crate::structure::message::common::load_localization;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+
+pub struct ModuleDisabledLocalised {
+	pub title: String,
+	pub disabled: String,
+	pub kill_switch: String,
+}
+
+use anyhow::Result;
+
+pub async fn load_localization_module_disabled(
+	guild_id: String, locale: Option<&str>, db_connection: Arc<DatabaseConnection>,
+) -> Result<ModuleDisabledLocalised> {
+	let path = "json/message/module_disabled.json";
+
+	load_localization(guild_id, locale, path, db_connection).await
+}
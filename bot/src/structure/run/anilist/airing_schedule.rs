@@ -0,0 +1,96 @@
+#[cynic::schema("anilist")]
+
+mod schema {}
+
+#[derive(cynic::QueryVariables, Debug, Clone)]
+
+pub struct AiringScheduleVariables {
+	pub airing_at_greater: Option<i32>,
+	pub airing_at_lesser: Option<i32>,
+	pub media_ids: Option<Vec<Option<i32>>>,
+	pub page: Option<i32>,
+	pub per_page: Option<i32>,
+}
+
+/// The episodes airing between two timestamps, the earliest first.
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Query", variables = "AiringScheduleVariables")]
+
+pub struct AiringSchedulePage {
+	#[arguments(page: $ page, perPage: $ per_page)]
+	#[cynic(rename = "Page")]
+	pub page: Option<Page>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(variables = "AiringScheduleVariables")]
+
+pub struct Page {
+	pub page_info: Option<PageInfo>,
+	#[arguments(airingAt_greater: $ airing_at_greater, airingAt_lesser: $ airing_at_lesser, mediaId_in: $ media_ids, sort: "TIME")]
+	pub airing_schedules: Option<Vec<Option<AiringSchedule>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct PageInfo {
+	pub has_next_page: Option<bool>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct AiringSchedule {
+	pub airing_at: i32,
+	pub episode: i32,
+	pub media: Option<Media>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct Media {
+	pub id: i32,
+	pub title: Option<MediaTitle>,
+	pub site_url: Option<String>,
+	pub is_adult: Option<bool>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaTitle {
+	pub romaji: Option<String>,
+	pub english: Option<String>,
+}
+
+#[derive(cynic::QueryVariables, Debug, Clone)]
+
+pub struct CurrentListVariables {
+	pub user_id: Option<i32>,
+}
+
+/// The anime a user is currently watching.
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Query", variables = "CurrentListVariables")]
+
+pub struct CurrentList {
+	#[arguments(userId: $ user_id, type: "ANIME", status: "CURRENT")]
+	#[cynic(rename = "MediaListCollection")]
+	pub media_list_collection: Option<MediaListCollection>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaListCollection {
+	pub lists: Option<Vec<Option<MediaListGroup>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaListGroup {
+	pub entries: Option<Vec<Option<MediaList>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaList {
+	pub media_id: i32,
+}
@@ -1,13 +1,14 @@
-use crate::config::DbConfig;
 use crate::constant::COLOR;
 use crate::helper::convert_flavored_markdown::convert_anilist_flavored_to_discord_flavored_markdown;
 use crate::helper::trimer::trim;
 use crate::structure::message::anilist_user::character::load_localization_character;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::{
 	CommandInteraction, Context as SerenityContext, CreateEmbed, CreateInteractionResponse,
 	CreateInteractionResponseMessage, Timestamp,
 };
+use std::sync::Arc;
 use tracing::log::trace;
 
 #[cynic::schema("anilist")]
@@ -85,7 +86,7 @@ pub struct FuzzyDate {
 }
 pub async fn send_embed(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, character: Character,
-	db_config: DbConfig,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = match command_interaction.guild_id {
 		Some(id) => id.to_string(),
@@ -94,7 +95,12 @@ pub async fn send_embed(
 
 	trace!("{:#?}", guild_id);
 
-	let character_localised = load_localization_character(guild_id, db_config).await?;
+	let character_localised = load_localization_character(
+		guild_id,
+		Some(command_interaction.locale.as_str()),
+		db_connection,
+	)
+	.await?;
 
 	let date_of_birth_data = character.date_of_birth.clone();
 
@@ -1,15 +1,17 @@
 use std::fmt::Display;
+use std::sync::Arc;
 
-use crate::config::DbConfig;
 use crate::constant::{COLOR, UNKNOWN};
+use crate::error_management::kasuki_error::KasukiError;
 use crate::helper::convert_flavored_markdown::convert_anilist_flavored_to_discord_flavored_markdown;
 use crate::helper::general_channel_info::get_nsfw;
 use crate::helper::trimer::trim;
 use crate::structure::message::anilist_user::media::load_localization_media;
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::{
-	CommandInteraction, Context as SerenityContext, CreateEmbed, CreateInteractionResponse,
-	CreateInteractionResponseMessage, Timestamp,
+	ChannelId, CommandInteraction, Context as SerenityContext, CreateEmbed,
+	CreateInteractionResponse, CreateInteractionResponseMessage, GuildId, Timestamp,
 };
 
 #[cynic::schema("anilist")]
@@ -575,20 +577,51 @@ fn get_character(character: Vec<Option<CharacterEdge>>) -> String {
 
 pub async fn send_embed(
 	ctx: &SerenityContext, command_interaction: &CommandInteraction, data: Media,
-	db_config: DbConfig,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
+	let builder_embed = get_media_embed(
+		ctx,
+		command_interaction.channel_id,
+		command_interaction.guild_id,
+		command_interaction.locale.as_str(),
+		data,
+		db_connection,
+	)
+	.await?;
+
+	let builder_message = CreateInteractionResponseMessage::new().embed(builder_embed);
+
+	let builder = CreateInteractionResponse::Message(builder_message);
+
+	command_interaction
+		.create_response(&ctx.http, builder)
+		.await?;
+
+	Ok(())
+}
+
+/// The embed of `send_embed`, for the interactions that are not a command. An adult media is
+/// refused outside of a NSFW channel.
+
+pub async fn get_media_embed(
+	ctx: &SerenityContext, channel_id: ChannelId, guild_id: Option<GuildId>, locale: &str,
+	data: Media, db_connection: Arc<DatabaseConnection>,
+) -> Result<CreateEmbed<'static>> {
 	let is_adult = data.is_adult.unwrap_or(true);
 
-	if is_adult && !get_nsfw(command_interaction, ctx).await {
-		return Err(anyhow!("This an adult media in a non adult channel"));
+	if is_adult && !get_nsfw(channel_id, guild_id, ctx).await {
+		return Err(KasukiError::UserInput(
+			"This an adult media in a non adult channel".to_string(),
+		)
+		.into());
 	}
 
-	let guild_id = match command_interaction.guild_id {
+	let guild_id = match guild_id {
 		Some(id) => id.to_string(),
 		None => String::from("0"),
 	};
 
-	let media_localised = load_localization_media(guild_id, db_config).await?;
+	let media_localised = load_localization_media(guild_id, Some(locale), db_connection).await?;
 
 	let mut fields = Vec::new();
 
@@ -715,13 +748,5 @@ pub async fn send_embed(
 		}
 	}
 
-	let builder_message = CreateInteractionResponseMessage::new().embed(builder_embed);
-
-	let builder = CreateInteractionResponse::Message(builder_message);
-
-	command_interaction
-		.create_response(&ctx.http, builder)
-		.await?;
-
-	Ok(())
+	Ok(builder_embed)
 }
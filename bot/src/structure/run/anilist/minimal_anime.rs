@@ -41,6 +41,45 @@ pub struct Media {
 	pub next_airing_episode: Option<AiringSchedule>,
 }
 
+#[derive(cynic::QueryVariables, Debug, Clone)]
+
+pub struct MinimalMangaIdVariables {
+	pub id: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Query", variables = "MinimalMangaIdVariables")]
+
+pub struct MinimalMangaId {
+	#[arguments(id: $ id, type: "MANGA")]
+	#[cynic(rename = "Media")]
+	pub media: Option<MangaMedia>,
+}
+
+#[derive(cynic::QueryVariables, Debug, Clone)]
+
+pub struct MinimalMangaSearchVariables<'a> {
+	pub search: Option<&'a str>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Query", variables = "MinimalMangaSearchVariables")]
+
+pub struct MinimalMangaSearch {
+	#[arguments(search: $ search, type: "MANGA")]
+	#[cynic(rename = "Media")]
+	pub media: Option<MangaMedia>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Media")]
+
+pub struct MangaMedia {
+	pub id: i32,
+	pub cover_image: Option<MediaCoverImage>,
+	pub title: Option<MediaTitle>,
+}
+
 #[derive(cynic::QueryFragment, Debug, Clone)]
 
 pub struct MediaTitle {
@@ -1,7 +1,9 @@
+pub mod airing_schedule;
 pub mod character;
 pub mod media;
 pub mod minimal_anime;
 pub mod random;
+pub mod season;
 pub mod seiyuu_id;
 pub mod seiyuu_search;
 pub mod site_statistic_anime;
@@ -9,3 +11,4 @@ pub mod site_statistic_manga;
 pub mod staff;
 pub mod studio;
 pub mod user;
+pub mod user_activity;
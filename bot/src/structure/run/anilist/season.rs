@@ -0,0 +1,122 @@
+#[cynic::schema("anilist")]
+
+mod schema {}
+
+#[derive(cynic::QueryVariables, Debug, Clone)]
+
+pub struct SeasonVariables {
+	pub season: Option<MediaSeason>,
+	pub season_year: Option<i32>,
+	pub format_in: Option<Vec<Option<MediaFormat>>>,
+	pub sort: Option<Vec<Option<MediaSort>>>,
+	pub page: Option<i32>,
+	pub per_page: Option<i32>,
+}
+
+/// The anime of a season, the adult ones excluded.
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Query", variables = "SeasonVariables")]
+
+pub struct Season {
+	#[arguments(page: $ page, perPage: $ per_page)]
+	#[cynic(rename = "Page")]
+	pub page: Option<Page>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(variables = "SeasonVariables")]
+
+pub struct Page {
+	pub page_info: Option<PageInfo>,
+	#[arguments(season: $ season, seasonYear: $ season_year, format_in: $ format_in, sort: $ sort, type: "ANIME", isAdult: false)]
+	pub media: Option<Vec<Option<Media>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct PageInfo {
+	pub has_next_page: Option<bool>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct Media {
+	pub id: i32,
+	pub title: Option<MediaTitle>,
+	pub site_url: Option<String>,
+	pub episodes: Option<i32>,
+	pub mean_score: Option<i32>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaTitle {
+	pub user_preferred: Option<String>,
+	pub romaji: Option<String>,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+
+pub enum MediaSeason {
+	Winter,
+	Spring,
+	Summer,
+	Fall,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+
+pub enum MediaFormat {
+	Tv,
+	TvShort,
+	Movie,
+	Special,
+	Ova,
+	Ona,
+	Music,
+	Manga,
+	Novel,
+	OneShot,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+
+pub enum MediaSort {
+	Id,
+	IdDesc,
+	TitleRomaji,
+	TitleRomajiDesc,
+	TitleEnglish,
+	TitleEnglishDesc,
+	TitleNative,
+	TitleNativeDesc,
+	Type,
+	TypeDesc,
+	Format,
+	FormatDesc,
+	StartDate,
+	StartDateDesc,
+	EndDate,
+	EndDateDesc,
+	Score,
+	ScoreDesc,
+	Popularity,
+	PopularityDesc,
+	Trending,
+	TrendingDesc,
+	Episodes,
+	EpisodesDesc,
+	Duration,
+	DurationDesc,
+	Status,
+	StatusDesc,
+	Chapters,
+	ChaptersDesc,
+	Volumes,
+	VolumesDesc,
+	UpdatedAt,
+	UpdatedAtDesc,
+	SearchMatch,
+	Favourites,
+	FavouritesDesc,
+}
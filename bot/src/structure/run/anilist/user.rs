@@ -1,10 +1,11 @@
 use std::fmt::Display;
+use std::sync::Arc;
 
-use crate::config::DbConfig;
 use crate::constant::COLOR;
 use crate::helper::create_default_embed::get_default_embed;
 use crate::structure::message::anilist_user::user::{load_localization_user, UserLocalised};
 use anyhow::{anyhow, Result};
+use sea_orm::DatabaseConnection;
 use serenity::all::CommandInteraction;
 use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
 use serenity::model::Colour;
@@ -183,14 +184,16 @@ impl Display for UserStatisticsSort {
 }
 
 pub async fn send_embed(
-	ctx: &SerenityContext, command: &CommandInteraction, user: User, db_config: DbConfig,
+	ctx: &SerenityContext, command: &CommandInteraction, user: User,
+	db_connection: Arc<DatabaseConnection>,
 ) -> Result<()> {
 	let guild_id = match command.guild_id {
 		Some(id) => id.to_string(),
 		None => String::from("0"),
 	};
 
-	let user_localised = load_localization_user(guild_id, db_config).await?;
+	let user_localised =
+		load_localization_user(guild_id, Some(command.locale.as_str()), db_connection).await?;
 
 	let mut field = Vec::new();
 
@@ -0,0 +1,94 @@
+#[cynic::schema("anilist")]
+
+mod schema {}
+
+#[derive(cynic::QueryVariables, Debug, Clone)]
+
+pub struct UserActivityVariables {
+	pub user_ids: Option<Vec<Option<i32>>>,
+	pub page: Option<i32>,
+	pub per_page: Option<i32>,
+}
+
+/// The list updates of several users, the newest first.
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(graphql_type = "Query", variables = "UserActivityVariables")]
+
+pub struct UserActivity {
+	#[arguments(page: $ page, perPage: $ per_page)]
+	#[cynic(rename = "Page")]
+	pub page: Option<Page>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+#[cynic(variables = "UserActivityVariables")]
+
+pub struct Page {
+	pub page_info: Option<PageInfo>,
+	#[arguments(userId_in: $ user_ids, type: "MEDIA_LIST", sort: "ID_DESC")]
+	pub activities: Option<Vec<Option<ActivityUnion>>>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct PageInfo {
+	pub has_next_page: Option<bool>,
+}
+
+#[derive(cynic::InlineFragments, Debug, Clone)]
+
+pub enum ActivityUnion {
+	ListActivity(ListActivity),
+	#[cynic(fallback)]
+	Unknown,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct ListActivity {
+	pub id: i32,
+	pub user_id: Option<i32>,
+	/// What was done, `watched episode`, `read chapter`, `completed`...
+	pub status: Option<String>,
+	/// The episodes or chapters, `5` or `3 - 5`.
+	pub progress: Option<String>,
+	pub site_url: Option<String>,
+	pub created_at: i32,
+	pub user: Option<User>,
+	pub media: Option<Media>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct User {
+	pub name: String,
+	pub avatar: Option<UserAvatar>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct UserAvatar {
+	pub large: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct Media {
+	pub id: i32,
+	pub title: Option<MediaTitle>,
+	pub cover_image: Option<MediaCoverImage>,
+	pub site_url: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaTitle {
+	pub romaji: Option<String>,
+	pub english: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone)]
+
+pub struct MediaCoverImage {
+	pub large: Option<String>,
+}